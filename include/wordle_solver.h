@@ -0,0 +1,41 @@
+/* C API of the wordle_solver library, built with
+ * `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+ * Link against target/release/libwordle_solver.so (or .dylib, .dll). See src/ffi.rs. */
+
+#ifndef WORDLE_SOLVER_H
+#define WORDLE_SOLVER_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A game being solved */
+typedef struct WordleSolver WordleSolver;
+
+/* Starts a game from word lists with one word per line and a strategy name: "average",
+ * "worst-case", "gambling <factor>", "expected-tiles" or "positional-frequency". Returns NULL
+ * for invalid strings, no answers or an unknown strategy. */
+WordleSolver *wordle_solver_new(const char *allowed, const char *answers, const char *strategy);
+
+/* Keeps the candidates matching the marks the guess got ("-+o", "gyb" or emoji squares).
+ * Returns 0, or -1 for an invalid guess or marks. */
+int wordle_solver_apply_feedback(WordleSolver *solver, const char *guess, const char *marks);
+
+/* The word to play next, NULL once no candidate is left. Free it with
+ * wordle_solver_free_string. */
+char *wordle_solver_top_suggestion(const WordleSolver *solver);
+
+/* Number of words that can still be the answer */
+size_t wordle_solver_candidate_count(const WordleSolver *solver);
+
+void wordle_solver_free(WordleSolver *solver);
+
+void wordle_solver_free_string(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,186 @@
+//! Playing against a host that changes the answer, like [Absurdle].
+//!
+//! Absurdle keeps every word consistent with the feedback so far as a possible answer, and answers
+//! each guess with the feedback leaving the most of them. [`WorstCase`] picks the guess leaving the
+//! smallest largest group, but the host then plays on from that group, where the same guess may
+//! leave nothing good to follow up with. [`Adversarial`] plays the game out for a number of guesses,
+//! the host always keeping the largest group, and counts the guesses it takes to pin the answer.
+//!
+//! The follow-ups tried are the few guesses leaving the smallest largest groups of all the
+//! candidates, plus the words of the group once it is small, as for [`Lookahead`]. Past the last
+//! guess looked at, a group counts as taking the fewest guesses any strategy could need.
+//!
+//! [Absurdle]: https://qntm.org/files/absurdle/absurdle.html
+//! [`WorstCase`]: crate::solver::WorstCase
+//! [`Lookahead`]: crate::lookahead::Lookahead
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::bounds::guess_bounds;
+use crate::engine;
+use crate::mark::Mark;
+use crate::pattern::{Pattern, PATTERN_COUNT};
+use crate::solver::{DictWord, Strategy, WORD_LENGTH};
+
+/// Groups with at most this many candidates also try their own words as follow-ups
+pub const SMALL_GROUP: usize = 20;
+
+/// Scores a guess by minus the number of guesses to pin the answer when the host always keeps the
+/// largest group of candidates, looking `depth` guesses ahead. Among guesses taking as many, those
+/// leaving a smaller largest group come first.
+pub struct Adversarial {
+  guesses: Vec<DictWord>,
+  depth: usize,
+  /// How many of the guesses leaving the smallest largest groups are tried as follow-ups
+  breadth: usize,
+  /// The follow-ups of the candidate sets scored so far
+  follow_ups: Mutex<HashMap<Vec<DictWord>, Vec<DictWord>>>,
+  /// Guesses taken by the groups searched so far, and how far ahead they were looked at, with the
+  /// follow-ups of the first candidates they were reached from
+  known: Mutex<HashMap<(Vec<DictWord>, usize), usize>>,
+}
+
+/// The groups of `candidates` left by `guess`, by pattern code
+fn groups(guess: &DictWord, candidates: &[DictWord]) -> HashMap<usize, Vec<DictWord>> {
+  let mut groups: HashMap<usize, Vec<DictWord>> = HashMap::new();
+  for word in candidates {
+    groups
+      .entry(guess.pattern_code(word))
+      .or_default()
+      .push(*word);
+  }
+  groups
+}
+
+/// Size of the largest group of `candidates` left by `guess`
+fn largest_group(guess: &DictWord, candidates: &[DictWord]) -> usize {
+  let mut counts = [0usize; PATTERN_COUNT];
+  for word in candidates {
+    counts[guess.pattern_code(word)] += 1;
+  }
+  counts.iter().copied().max().unwrap_or(0)
+}
+
+/// The feedback Absurdle gives `guess`: the marks leaving the most of `candidates`, and among
+/// those the fewest greens, then the fewest yellows. Every letter is absent without candidates.
+pub fn absurdle_feedback<S: AsRef<str>>(guess: &str, candidates: &[S]) -> Vec<Mark> {
+  let mut groups: HashMap<Vec<Mark>, usize> = HashMap::new();
+  for word in candidates {
+    *groups
+      .entry(engine::compute_bucket(guess, word.as_ref()))
+      .or_default() += 1;
+  }
+  let count = |marks: &[Mark], mark: Mark| marks.iter().filter(|&&m| m == mark).count();
+  let kept = groups.into_iter().max_by(|(a, a_size), (b, b_size)| {
+    a_size
+      .cmp(b_size)
+      .then(count(b, Mark::RightPosition).cmp(&count(a, Mark::RightPosition)))
+      .then(count(b, Mark::WrongPosition).cmp(&count(a, Mark::WrongPosition)))
+  });
+  kept.map_or_else(
+    || vec![Mark::NotPresent; guess.chars().count()],
+    |(marks, _)| marks,
+  )
+}
+
+impl Adversarial {
+  /// Looks `depth` guesses ahead, trying the `breadth` guesses of `allowed` leaving the smallest
+  /// largest groups as follow-ups
+  pub fn new<S: AsRef<str>>(allowed: &[S], depth: usize, breadth: usize) -> Adversarial {
+    Adversarial {
+      guesses: allowed
+        .iter()
+        .filter_map(|word| DictWord::new(word.as_ref()))
+        .collect(),
+      depth: depth.max(1),
+      breadth,
+      follow_ups: Mutex::new(HashMap::new()),
+      known: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// The guesses leaving the smallest largest groups of `candidates`
+  fn follow_ups(&self, candidates: &[DictWord]) -> Vec<DictWord> {
+    let mut follow_ups = self.follow_ups.lock().unwrap();
+    let shortlist = follow_ups.entry(candidates.to_vec()).or_insert_with(|| {
+      let mut scored: Vec<(usize, DictWord)> = self
+        .guesses
+        .iter()
+        .map(|guess| (largest_group(guess, candidates), *guess))
+        .collect();
+      scored.sort_by_key(|&(largest, _)| largest);
+      scored
+        .into_iter()
+        .take(self.breadth)
+        .map(|(_, guess)| guess)
+        .collect()
+    });
+    shortlist.clone()
+  }
+
+  /// Guesses to pin the answer among `candidates` playing the best follow-ups, `depth` guesses
+  /// ahead
+  fn rounds(&self, candidates: &[DictWord], depth: usize, follow_ups: &[DictWord]) -> usize {
+    if candidates.len() <= 1 || depth == 0 {
+      return guess_bounds(candidates.len(), WORD_LENGTH).worst;
+    }
+    let key = (candidates.to_vec(), depth);
+    if let Some(&known) = self.known.lock().unwrap().get(&key) {
+      return known;
+    }
+
+    let own_words = match candidates.len() {
+      count if count <= SMALL_GROUP => candidates,
+      _ => &[],
+    };
+    let rounds = follow_ups
+      .iter()
+      .chain(own_words.iter())
+      .map(|guess| self.play(guess, candidates, depth, follow_ups))
+      .min()
+      .unwrap_or(usize::MAX);
+    self.known.lock().unwrap().insert(key, rounds);
+    rounds
+  }
+
+  /// Guesses to pin the answer playing `guess` first, the host keeping the largest group
+  fn play(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    depth: usize,
+    follow_ups: &[DictWord],
+  ) -> usize {
+    let groups = groups(guess, candidates);
+    let largest = groups.values().map(Vec::len).max().unwrap_or(0);
+    let solved = Pattern::SOLVED.index() as usize;
+    if groups.contains_key(&solved) && candidates.len() == 1 {
+      return 1;
+    }
+
+    // Among the largest groups, the host keeps the one taking the most guesses
+    let rest = groups
+      .iter()
+      .filter(|&(&code, group)| group.len() == largest && code != solved)
+      .map(|(_, group)| self.rounds(group, depth - 1, follow_ups))
+      .max()
+      .unwrap_or(0);
+    rest.saturating_add(1)
+  }
+}
+
+impl Strategy for Adversarial {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    let follow_ups = self.follow_ups(candidates);
+    let largest = largest_group(guess, candidates) as f64;
+    // Below 1, so it only breaks ties
+    let tie_break = largest / (candidates.len() as f64 + 1.0);
+    let rounds = self.play(guess, candidates, self.depth, &follow_ups);
+    -(rounds as f64) - tie_break
+  }
+
+  fn name(&self) -> String {
+    format!("adversarial {} {}", self.depth, self.breadth)
+  }
+}
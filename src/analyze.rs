@@ -0,0 +1,223 @@
+//! The `analyze` commands of the interactive mode, explaining the current candidates.
+
+use std::collections::HashMap;
+use wordle_solver::arena::WordArena;
+use wordle_solver::mark::Mark;
+use wordle_solver::{letters, partition};
+
+use crate::session::Session;
+use crate::{
+  best_attempt, humans, probe, reduce_dictionary, top_suggestions, web, Analysis, DictString,
+  Strategy, WORD_LENGTH,
+};
+
+const USAGE: &str =
+  "Usage: analyze letters | analyze partition <guess> | analyze odds <turns left>";
+
+/// Number of example words shown for each pattern
+const SHOWN_EXAMPLES: usize = 5;
+
+/// Handles `analyze <what>` for the current session
+pub fn run(
+  arguments: &str,
+  session: &Session,
+  dictionary: &Vec<&DictString>,
+  strategy: &dyn Strategy,
+) {
+  let arguments: Vec<&str> = arguments.split_whitespace().collect();
+  match arguments.as_slice() {
+    ["letters"] => print_letters(session),
+    ["partition", guess] if guess.chars().count() == WORD_LENGTH => {
+      print_partition(&guess.to_lowercase(), session)
+    }
+    ["odds", turns_left] => match turns_left.parse() {
+      Ok(turns_left) => print_odds(dictionary, &session.candidates, strategy, turns_left),
+      Err(_) => println!("{}", USAGE),
+    },
+    _ => println!("{}", USAGE),
+  }
+}
+
+/// Runs the `analyze` subcommand on the game given on the command line
+pub fn command(
+  analysis: Analysis,
+  dictionary: &WordArena,
+  answers: &WordArena,
+  strategy: &dyn Strategy,
+) -> Result<(), String> {
+  let dictionary: Vec<&DictString> = dictionary.iter().collect();
+  let answers: Vec<&DictString> = answers.iter().collect();
+
+  match analysis {
+    Analysis::Odds {
+      history,
+      turns_left,
+    } => {
+      let mut candidates = answers;
+      for (word, marks) in web::parse_history(&history.join("\n"))? {
+        candidates = reduce_dictionary(&word, &marks, &candidates);
+      }
+      print_odds(&dictionary, &candidates, strategy, turns_left);
+    }
+    Analysis::Game {
+      mut history,
+      transcript,
+    } => {
+      if let Some(file) = transcript {
+        history.push(std::fs::read_to_string(&file).map_err(|err| format!("{}: {}", file, err))?);
+      }
+      let history = web::parse_history(&history.join("\n"))?;
+      print_game(&dictionary, answers, &history, strategy);
+    }
+    Analysis::Humans { games } => {
+      let games = humans::read(&games)?;
+      humans::compare(&dictionary, answers, &games, strategy)?;
+    }
+  }
+
+  Ok(())
+}
+
+fn print_letters(session: &Session) {
+  let candidates = session.candidates.len();
+  println!("Letter  Candidates  Presence bits  Position bits");
+  for info in letters::letter_information(&session.candidates) {
+    println!(
+      "{:>6}  {:>10}  {:>13.3}  {:>13.3}",
+      info.letter, info.containing, info.presence_bits, info.position_bits
+    );
+  }
+  println!("({} candidates)", candidates);
+}
+
+fn print_partition(guess: &str, session: &Session) {
+  let total = session.candidates.len();
+  let mut groups = partition(guess, &session.candidates);
+  groups.sort_by_key(|(_, words)| std::cmp::Reverse(words.len()));
+
+  println!("Pattern  Candidates  Probability  Examples");
+  for (marks, words) in groups.iter() {
+    let pattern: String = marks.iter().map(|mark| mark.to_char()).collect();
+    let examples: Vec<&str> = words.iter().take(SHOWN_EXAMPLES).copied().collect();
+    println!(
+      "{:>7}  {:>10}  {:>10.1}%  {}{}",
+      pattern,
+      words.len(),
+      100.0 * words.len() as f64 / total as f64,
+      examples.join(" "),
+      if words.len() > SHOWN_EXAMPLES {
+        " ..."
+      } else {
+        ""
+      }
+    );
+  }
+  println!("({} patterns for {} candidates)", groups.len(), total);
+}
+
+/// Probability of finding the answer within `turns_left` guesses when always playing the word the
+/// solver suggests, every candidate being equally likely to be the answer.
+///
+/// This replays the solver on every pattern each suggestion can get, so it is exact but gets slow
+/// with many candidates and turns. Results are cached by candidate set and turns left.
+pub fn solve_probability<'a>(
+  dictionary: &Vec<&'a DictString>,
+  candidates: &[&'a DictString],
+  strategy: &dyn Strategy,
+  turns_left: usize,
+  cache: &mut HashMap<(Vec<&'a DictString>, usize), f64>,
+) -> f64 {
+  match (candidates.len(), turns_left) {
+    (0, _) | (_, 0) => return 0.0,
+    // The only candidate left is always the suggested word
+    (1, _) => return 1.0,
+    _ => {}
+  }
+  if let Some(&probability) = cache.get(&(candidates.to_vec(), turns_left)) {
+    return probability;
+  }
+
+  let (suggestions, guesses) = top_suggestions(dictionary, candidates, strategy, 1);
+  let attempt = best_attempt(&suggestions, &guesses, candidates.len()).unwrap();
+
+  let total = candidates.len() as f64;
+  let probability = partition(attempt, candidates)
+    .into_iter()
+    .map(|(marks, words)| {
+      let share = words.len() as f64 / total;
+      if marks.iter().all(|&mark| mark == Mark::RightPosition) {
+        share
+      } else {
+        share * solve_probability(dictionary, &words, strategy, turns_left - 1, cache)
+      }
+    })
+    .sum();
+
+  cache.insert((candidates.to_vec(), turns_left), probability);
+  probability
+}
+
+pub fn print_odds(
+  dictionary: &Vec<&DictString>,
+  candidates: &[&DictString],
+  strategy: &dyn Strategy,
+  turns_left: usize,
+) {
+  let mut cache = HashMap::new();
+  let probability = solve_probability(dictionary, candidates, strategy, turns_left, &mut cache);
+  println!(
+    "{:.2}% chance to solve within {} turns following the suggestions ({} candidates)",
+    100.0 * probability,
+    turns_left,
+    candidates.len()
+  );
+}
+
+/// Shows, for each guess of the game, the bits it was expected to give next to those of the best
+/// guess available then. The difference is information wasted by not playing the best guess.
+pub fn print_game(
+  dictionary: &Vec<&DictString>,
+  answers: Vec<&DictString>,
+  history: &[(String, Vec<Mark>)],
+  strategy: &dyn Strategy,
+) {
+  let mut candidates = answers;
+  let mut total_wasted = 0.0;
+
+  println!("Turn  Guess  Candidates  Best    Bits  Wasted   Got");
+  for (turn, (word, marks)) in history.iter().enumerate() {
+    if candidates.is_empty() {
+      println!("No candidates left, the marks don't match the answer list");
+      break;
+    }
+
+    let (suggestions, guesses) = top_suggestions(dictionary, &candidates, strategy, 1);
+    let best_word = best_attempt(&suggestions, &guesses, candidates.len()).unwrap();
+    let best_bits = suggestions[0].1;
+    let bits = probe::evaluate(word, &candidates, strategy).unwrap();
+    let wasted = (best_bits - bits).max(0.0);
+    total_wasted += wasted;
+
+    let before = candidates.len();
+    candidates = reduce_dictionary(word, marks, &candidates);
+    let got = (before as f64 / candidates.len().max(1) as f64).log2();
+
+    println!(
+      "{:>4}  {}  {:>10}  {}  {:>5.2}  {:>6.2}  {:>4.2}",
+      turn + 1,
+      word,
+      before,
+      best_word,
+      bits,
+      wasted,
+      got
+    );
+  }
+
+  println!(
+    "{:.2} bits wasted over {} guesses ({} candidates left)",
+    total_wasted,
+    history.len(),
+    candidates.len()
+  );
+}
@@ -0,0 +1,80 @@
+//! Word lists stored in a single buffer.
+//!
+//! A `Vec<String>` makes one heap allocation per word, scattered around memory. A [`WordArena`]
+//! keeps all the words back to back in one string and finds them by offset, so walking the
+//! dictionary reads memory in order, and the buffer plus its offsets can be written out or mapped
+//! as they are.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::Index;
+
+/// A list of words stored contiguously
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordArena {
+  text: String,
+  /// Where each word ends in `text`. A word starts where the previous one ends.
+  ends: Vec<usize>,
+}
+
+impl WordArena {
+  pub fn new() -> WordArena {
+    WordArena::default()
+  }
+
+  pub fn push(&mut self, word: &str) {
+    self.text.push_str(word);
+    self.ends.push(self.text.len());
+  }
+
+  pub fn len(&self) -> usize {
+    self.ends.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.ends.is_empty()
+  }
+
+  pub fn get(&self, index: usize) -> Option<&str> {
+    let end = *self.ends.get(index)?;
+    let start = if index == 0 { 0 } else { self.ends[index - 1] };
+    Some(&self.text[start..end])
+  }
+
+  pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + DoubleEndedIterator + Clone + '_ {
+    (0..self.len()).map(move |index| &self[index])
+  }
+
+  pub fn contains(&self, word: &str) -> bool {
+    self.iter().any(|candidate| candidate == word)
+  }
+
+  /// All the words back to back
+  pub fn text(&self) -> &str {
+    &self.text
+  }
+
+  /// Where each word ends in [`WordArena::text`]
+  pub fn ends(&self) -> &[usize] {
+    &self.ends
+  }
+}
+
+impl Index<usize> for WordArena {
+  type Output = str;
+
+  fn index(&self, index: usize) -> &str {
+    self.get(index).expect("word index out of bounds")
+  }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for WordArena {
+  fn from_iter<I: IntoIterator<Item = S>>(words: I) -> WordArena {
+    let mut arena = WordArena::new();
+    for word in words {
+      arena.push(word.as_ref());
+    }
+    arena
+  }
+}
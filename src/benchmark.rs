@@ -0,0 +1,378 @@
+//! The `benchmark` subcommand: the solver plays against every answer (or a sample of them) and
+//! reports how many guesses the strategy needs.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::guarantee::Guarantee;
+use wordle_solver::progress::Progress;
+use wordle_solver::random::Rng;
+
+use crate::frequency::Frequencies;
+use crate::{
+  best_attempt_with, get_suggestions, guaranteed_attempt, progress_bar, reduce_dictionary,
+  sample_attempt, top_suggestions, DictString, Strategy, DEFAULT_LAMBDA, GUARANTEE_CHOICES,
+};
+
+/// Games taking more guesses than this are failures
+pub const MAX_TRIES: usize = 6;
+
+/// Games are abandoned after this many guesses, in case the strategy goes around in circles
+pub const GIVE_UP_TRIES: usize = 20;
+
+/// Which answers the benchmark plays
+pub struct Sample<'f> {
+  /// Number of answers drawn at random, all of them once each if `None`
+  pub size: Option<usize>,
+  /// Draw common answers more often, in proportion to their frequency
+  pub frequencies: Option<&'f Frequencies>,
+  /// Seed of the draws, and of the words to try with a temperature
+  pub seed: Option<u64>,
+}
+
+/// What the benchmark plays and reports besides the averages
+pub struct Options<'o> {
+  /// First guess of every game instead of the strategy's choice
+  pub opener: Option<&'o str>,
+  /// Number of hardest answers listed
+  pub worst: usize,
+  /// CSV file the games are exported to, grouped by the feedback to the first guess
+  pub export: Option<&'o str>,
+  /// Fail when an answer isn't solved within [`MAX_TRIES`]
+  pub require_all_solved: bool,
+  /// Only play words still finding every answer within [`MAX_TRIES`], see --guarantee
+  pub guarantee: Option<&'o Guarantee>,
+  /// Draw the words to try at this temperature instead of playing the best, see --temperature
+  pub temperature: f64,
+}
+
+/// The guesses played against one answer
+pub struct Game<'a> {
+  pub answer: &'a DictString,
+  pub guesses: Vec<&'a DictString>,
+}
+
+impl<'a> Game<'a> {
+  pub fn solved(&self) -> bool {
+    self.guesses.last() == Some(&self.answer) && self.guesses.len() <= MAX_TRIES
+  }
+
+  fn sequence(&self) -> String {
+    let words: Vec<&str> = self.guesses.to_vec();
+    words.join(" -> ")
+  }
+
+  /// The marks the first guess got
+  fn first_pattern(&self) -> String {
+    engine::compute_bucket(self.guesses[0], self.answer)
+      .iter()
+      .map(|mark| mark.to_char())
+      .collect()
+  }
+}
+
+/// Plays games with the strategy, remembering the word it picks for each set of candidates, as
+/// many answers share the same first turns
+pub struct Player<'a, 'd> {
+  dictionary: &'d Vec<&'a DictString>,
+  answers: Vec<&'a DictString>,
+  strategy: &'d dyn Strategy,
+  opener: Option<&'a DictString>,
+  guarantee: Option<&'d Guarantee>,
+  /// Temperature the words to try are drawn at, with the generator drawing them
+  sampling: Option<(f64, Rng)>,
+  /// The word picked for each set of candidates and number of guesses played
+  attempts: HashMap<(Vec<&'a DictString>, usize), &'a DictString>,
+}
+
+impl<'a, 'd> Player<'a, 'd> {
+  pub fn new(
+    dictionary: &'d Vec<&'a DictString>,
+    answers: Vec<&'a DictString>,
+    strategy: &'d dyn Strategy,
+    opener: Option<&'a DictString>,
+  ) -> Player<'a, 'd> {
+    Player {
+      dictionary,
+      answers,
+      strategy,
+      opener,
+      guarantee: None,
+      sampling: None,
+      attempts: HashMap::new(),
+    }
+  }
+
+  /// Only plays words still finding every answer within [`MAX_TRIES`] when some are
+  pub fn with_guarantee(mut self, guarantee: Option<&'d Guarantee>) -> Player<'a, 'd> {
+    self.guarantee = guarantee;
+    self
+  }
+
+  /// Draws the words to try at `temperature` with `rng` instead of playing the best, see
+  /// [`sample_attempt`]. The word drawn for a set of candidates is played in every game reaching
+  /// it.
+  pub fn with_sampling(mut self, temperature: f64, rng: Rng) -> Player<'a, 'd> {
+    self.sampling = Some((temperature, rng)).filter(|&(temperature, _)| temperature > 0.0);
+    self
+  }
+
+  fn attempt(&mut self, candidates: &Vec<&'a DictString>, tries: usize) -> &'a DictString {
+    if candidates.len() == 1 {
+      return candidates[0];
+    }
+    let key = (candidates.clone(), tries);
+    if let Some(&word) = self.attempts.get(&key) {
+      return word;
+    }
+
+    let strategy = self
+      .strategy
+      .at_turn(tries + 1, candidates.len())
+      .unwrap_or(self.strategy);
+    let chance = 1.0 / candidates.len() as f64;
+    let lambda = if strategy.counts_wins() {
+      0.0
+    } else {
+      DEFAULT_LAMBDA
+    };
+    let word = match self.guarantee {
+      Some(guarantee) if tries < MAX_TRIES => {
+        let (suggestions, guesses) =
+          top_suggestions(self.dictionary, candidates, strategy, GUARANTEE_CHOICES);
+        let best = best_attempt_with(&suggestions, &guesses, chance, lambda).unwrap();
+        let turns = MAX_TRIES - tries;
+        guaranteed_attempt(
+          guarantee,
+          best,
+          &suggestions,
+          self.dictionary,
+          candidates,
+          turns,
+        )
+        .unwrap_or(best)
+      }
+      _ => match &mut self.sampling {
+        Some((temperature, rng)) => {
+          let (suggestions, guesses) = get_suggestions(self.dictionary, candidates, strategy);
+          let chance = 1.0 / candidates.len() as f64;
+          sample_attempt(
+            &suggestions,
+            &guesses,
+            chance,
+            DEFAULT_LAMBDA,
+            *temperature,
+            rng,
+          )
+          .unwrap()
+        }
+        None => {
+          let (suggestions, guesses) = top_suggestions(self.dictionary, candidates, strategy, 1);
+          best_attempt_with(&suggestions, &guesses, chance, lambda).unwrap()
+        }
+      },
+    };
+    self.attempts.insert(key, word);
+    word
+  }
+
+  pub fn play(&mut self, answer: &'a DictString) -> Game<'a> {
+    let mut candidates = self.answers.clone();
+    let mut guesses = vec![];
+
+    while guesses.len() < GIVE_UP_TRIES && !candidates.is_empty() {
+      let attempt = match (guesses.is_empty(), self.opener) {
+        (true, Some(opener)) => opener,
+        _ => self.attempt(&candidates, guesses.len()),
+      };
+      guesses.push(attempt);
+      if attempt == answer {
+        break;
+      }
+
+      let marks = engine::compute_bucket(attempt, answer);
+      candidates = reduce_dictionary(attempt, &marks, &candidates);
+    }
+
+    Game { answer, guesses }
+  }
+}
+
+/// The answers the benchmark plays, in the order played
+pub fn draw<'a>(answers: &[&'a DictString], sample: &Sample, rng: &mut Rng) -> Vec<&'a DictString> {
+  let size = match sample.size {
+    Some(size) => size,
+    None => return answers.to_vec(),
+  };
+
+  let weights: Vec<f64> = match sample.frequencies {
+    Some(frequencies) => answers.iter().map(|word| frequencies.get(word)).collect(),
+    None => vec![],
+  };
+  (0..size)
+    .map(|_| match rng.weighted_index(&weights) {
+      Some(index) => answers[index],
+      None => *rng.choose(answers).unwrap(),
+    })
+    .collect()
+}
+
+/// Writes one CSV row per answer, grouped by the marks the first guess got. The rows of a group
+/// share its size and average, and show the second guess played for it.
+fn export(file: &str, games: &[&Game]) -> io::Result<()> {
+  let mut groups: BTreeMap<String, Vec<&Game>> = BTreeMap::new();
+  for &game in games {
+    groups.entry(game.first_pattern()).or_default().push(game);
+  }
+
+  let mut out = BufWriter::new(File::create(file)?);
+  writeln!(
+    out,
+    "first_pattern,bucket_size,bucket_average,answer,guesses,second_guess,sequence"
+  )?;
+  for (pattern, games) in groups.iter() {
+    let average =
+      games.iter().map(|game| game.guesses.len()).sum::<usize>() as f64 / games.len() as f64;
+    for game in games {
+      writeln!(
+        out,
+        "{},{},{:.4},{},{},{},{}",
+        pattern,
+        games.len(),
+        average,
+        game.answer,
+        game.guesses.len(),
+        game.guesses.get(1).copied().unwrap_or(""),
+        game.sequence()
+      )?;
+    }
+  }
+
+  out.flush()
+}
+
+/// Plays the sampled answers and prints the average number of guesses, their distribution and
+/// the hardest games with the guesses that led there
+pub fn run(
+  dictionary: &WordArena,
+  answers: &WordArena,
+  strategy: &dyn Strategy,
+  sample: &Sample,
+  options: &Options,
+) -> Result<(), String> {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let answers_ref: Vec<&DictString> = answers.iter().collect();
+  let mut rng = crate::rng(sample.seed);
+  let targets = draw(&answers_ref, sample, &mut rng);
+
+  let opener = match options.opener {
+    Some(opener) => Some(
+      dictionary
+        .iter()
+        .find(|word| *word == opener.to_lowercase())
+        .ok_or_else(|| format!("The opener {:?} is not in the dictionary", opener))?,
+    ),
+    None => None,
+  };
+
+  let mut player = Player::new(&dictionary_ref, answers_ref, strategy, opener)
+    .with_guarantee(options.guarantee)
+    .with_sampling(options.temperature, rng);
+  report(&targets, |answer| Ok(player.play(answer)), options)
+}
+
+/// Plays each of `targets` once with `play` and prints the average number of guesses, their
+/// distribution and the hardest games, see [`run`]
+pub fn report<'a, F>(
+  targets: &[&'a DictString],
+  mut play: F,
+  options: &Options,
+) -> Result<(), String>
+where
+  F: FnMut(&'a DictString) -> Result<Game<'a>, String>,
+{
+  let bar = progress_bar::Bar::new("Playing");
+  let mut games: HashMap<&DictString, Game> = HashMap::new();
+  for (index, &answer) in targets.iter().enumerate() {
+    if !games.contains_key(answer) {
+      games.insert(answer, play(answer)?);
+    }
+    bar.update(&Progress {
+      done: index + 1,
+      total: targets.len(),
+      leaders: vec![],
+    });
+  }
+
+  // Averages count every draw, so that frequent answers weigh more with --by-frequency
+  let played: Vec<&Game> = targets.iter().map(|answer| &games[answer]).collect();
+  let total_guesses: usize = played.iter().map(|game| game.guesses.len()).sum();
+  let solved = played.iter().filter(|game| game.solved()).count();
+  println!(
+    "Played {} games: {:.4} guesses on average, {} solved within {}",
+    played.len(),
+    total_guesses as f64 / played.len().max(1) as f64,
+    solved,
+    MAX_TRIES
+  );
+
+  let mut distribution: Vec<usize> = vec![];
+  for game in played.iter() {
+    if distribution.len() < game.guesses.len() {
+      distribution.resize(game.guesses.len(), 0);
+    }
+    distribution[game.guesses.len() - 1] += 1;
+  }
+  for (index, count) in distribution.iter().enumerate() {
+    println!("{:>3} guesses: {}", index + 1, count);
+  }
+
+  let mut hardest: Vec<&Game> = games.values().collect();
+  hardest.sort_by(|a, b| {
+    b.guesses
+      .len()
+      .cmp(&a.guesses.len())
+      .then_with(|| a.answer.cmp(b.answer))
+  });
+  if options.worst > 0 {
+    println!("Hardest answers:");
+  }
+  for game in hardest.iter().take(options.worst) {
+    println!(
+      "  {} in {}{}: {}",
+      game.answer,
+      game.guesses.len(),
+      if game.solved() { "" } else { " (failed)" },
+      game.sequence()
+    );
+  }
+
+  let mut failed: Vec<&Game> = games.values().filter(|game| !game.solved()).collect();
+  failed.sort_by(|a, b| a.answer.cmp(b.answer));
+  if failed.is_empty() {
+    println!("Every answer was solved within {}", MAX_TRIES);
+  } else {
+    println!("Not solved within {} ({}):", MAX_TRIES, failed.len());
+  }
+  for game in failed.iter() {
+    println!("  {}: {}", game.answer, game.sequence());
+  }
+
+  if let Some(file) = options.export {
+    let mut exported: Vec<&Game> = games.values().collect();
+    exported.sort_by(|a, b| a.answer.cmp(b.answer));
+    export(file, &exported).map_err(|err| format!("{}: {}", file, err))?;
+  }
+
+  if options.require_all_solved && !failed.is_empty() {
+    return Err(format!(
+      "{} answers were not solved within {}",
+      failed.len(),
+      MAX_TRIES
+    ));
+  }
+
+  Ok(())
+}
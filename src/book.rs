@@ -0,0 +1,138 @@
+//! The `opening-book` subcommand: the best second guess after an opener, for every feedback the
+//! opener can get, to learn by heart or print out.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::str::FromStr;
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::progress::Progress;
+
+use crate::{best_attempt, progress_bar, top_suggestions, DictString, Strategy, WORD_LENGTH};
+
+/// How the book is written out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookFormat {
+  /// Aligned columns, one pattern per line
+  Text,
+  /// Comma separated values with a header line
+  Csv,
+}
+
+impl FromStr for BookFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "text" => Ok(BookFormat::Text),
+      "csv" => Ok(BookFormat::Csv),
+      _ => Err(format!("unknown format {:?}, expected text or csv", s)),
+    }
+  }
+}
+
+/// The second guess for one feedback to the opener
+struct Entry<'a> {
+  pattern: String,
+  candidates: usize,
+  guess: &'a DictString,
+  bits: f64,
+  /// Whether the second guess can be the answer
+  candidate: bool,
+}
+
+fn write_book(
+  out: &mut dyn Write,
+  opener: &str,
+  entries: &[Entry],
+  format: BookFormat,
+) -> io::Result<()> {
+  match format {
+    BookFormat::Text => {
+      writeln!(out, "Second guesses after {}", opener)?;
+      for entry in entries {
+        writeln!(
+          out,
+          "{}  {:>5}  {}  {:.3} bits{}",
+          entry.pattern,
+          entry.candidates,
+          entry.guess,
+          entry.bits,
+          if entry.candidate {
+            ""
+          } else {
+            " (not a candidate)"
+          }
+        )?;
+      }
+    }
+    BookFormat::Csv => {
+      writeln!(out, "opener,pattern,candidates,second_guess,bits,candidate")?;
+      for entry in entries {
+        writeln!(
+          out,
+          "{},{},{},{},{:.4},{}",
+          opener, entry.pattern, entry.candidates, entry.guess, entry.bits, entry.candidate
+        )?;
+      }
+    }
+  }
+
+  out.flush()
+}
+
+/// Finds the strategy's second guess for each of the `3^WORD_LENGTH` patterns the opener can get
+/// against the answers, and writes them to `output`, or prints them. Patterns no answer gives, and
+/// the all green one, are left out.
+pub fn run(
+  dictionary: &WordArena,
+  answers: &WordArena,
+  strategy: &dyn Strategy,
+  opener: &str,
+  format: BookFormat,
+  output: Option<&str>,
+) -> Result<(), String> {
+  let opener = opener.to_lowercase();
+  if opener.chars().count() != WORD_LENGTH {
+    return Err(format!("The opener needs {} letters", WORD_LENGTH));
+  }
+
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let answers_ref: Vec<&DictString> = answers.iter().collect();
+  let groups: Vec<_> = engine::partition(&opener, &answers_ref)
+    .into_iter()
+    .filter(|(_, words)| words.first() != Some(&opener.as_str()))
+    .collect();
+
+  let bar = progress_bar::Bar::new("Opening book");
+  let mut entries = vec![];
+  for (index, (marks, candidates)) in groups.iter().enumerate() {
+    let (suggestions, guesses) = top_suggestions(&dictionary_ref, candidates, strategy, 1);
+    let guess = best_attempt(&suggestions, &guesses, candidates.len()).unwrap();
+    let bits = suggestions
+      .iter()
+      .chain(guesses.iter())
+      .find(|(word, _)| *word == guess)
+      .map_or(0.0, |&(_, bits)| bits);
+
+    entries.push(Entry {
+      pattern: marks.iter().map(|mark| mark.to_char()).collect(),
+      candidates: candidates.len(),
+      guess,
+      bits,
+      candidate: candidates.contains(&guess),
+    });
+    bar.update(&Progress {
+      done: index + 1,
+      total: groups.len(),
+      leaders: vec![],
+    });
+  }
+
+  let result = match output {
+    Some(file) => File::create(file)
+      .and_then(|file| write_book(&mut BufWriter::new(file), &opener, &entries, format)),
+    None => write_book(&mut io::stdout().lock(), &opener, &entries, format),
+  };
+  result.map_err(|err| format!("{}: {}", output.unwrap_or("stdout"), err))
+}
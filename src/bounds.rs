@@ -0,0 +1,43 @@
+//! What no strategy can beat, whatever the words.
+//!
+//! A guess either is the answer or gets one of the other `3^length - 1` patterns, so in a game
+//! tree at most one answer is found on the first turn, at most `3^length - 1` on the second, and
+//! each turn after that multiplies the count by `3^length - 1` again. Filling the tree from the
+//! top gives the lowest possible average and worst case number of guesses for a set of candidates.
+
+/// Lowest possible number of guesses to find any of `candidates` equally likely words of `length`
+/// letters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuessBounds {
+  /// On average
+  pub expected: f64,
+  /// In the worst case
+  pub worst: usize,
+}
+
+/// Computes the bounds for `candidates` words of `length` letters. No guesses are needed when
+/// there are no candidates.
+pub fn guess_bounds(candidates: usize, length: usize) -> GuessBounds {
+  let branches = 3usize.saturating_pow(length as u32) - 1;
+
+  let mut left = candidates;
+  let mut found_at_turn = 1usize;
+  let mut turn = 0;
+  let mut total_guesses = 0.0;
+  while left > 0 {
+    turn += 1;
+    let found = found_at_turn.min(left);
+    total_guesses += (turn * found) as f64;
+    left -= found;
+    found_at_turn = found_at_turn.saturating_mul(branches);
+  }
+
+  GuessBounds {
+    expected: if candidates == 0 {
+      0.0
+    } else {
+      total_guesses / candidates as f64
+    },
+    worst: turn,
+  }
+}
@@ -0,0 +1,33 @@
+//! English word lists built into the program with the `bundled` feature, read with
+//! `--dict bundled` and `--guesses bundled` instead of files: the guesses Wordle accepts and the
+//! shorter list of its answers. The lists of games in other languages are read from files.
+
+/// The name standing for the bundled lists in `--dict` and `--guesses`
+pub const NAME: &str = "bundled";
+
+/// Which of the word lists of a language
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum List {
+  /// Every word the game accepts as a guess, for `--dict`
+  Guesses,
+  /// The words that can be the answer, for `--guesses`
+  Answers,
+}
+
+/// The text of the `list`, one word per line, if `language` is English
+#[cfg(feature = "bundled")]
+pub fn words(language: &str, list: List) -> Result<&'static str, String> {
+  match (language, list) {
+    ("en", List::Guesses) => Ok(include_str!("../words-wordle-set-full.txt")),
+    ("en", List::Answers) => Ok(include_str!("../words-wordle-set-reduced.txt")),
+    _ => Err(format!(
+      "The bundled word lists are English, read the lists of {:?} from files",
+      language
+    )),
+  }
+}
+
+#[cfg(not(feature = "bundled"))]
+pub fn words(_language: &str, _list: List) -> Result<&'static str, String> {
+  Err("This build doesn't include word lists, rebuild with --features bundled".to_string())
+}
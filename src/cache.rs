@@ -0,0 +1,77 @@
+//! A least recently used cache safe to share between threads.
+//!
+//! Entries are spread over several shards, each behind its own lock, so threads looking up
+//! different keys rarely wait for each other.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+const SHARDS: usize = 16;
+
+struct Shard<K, V> {
+  /// Each value along with the tick it was last used at
+  entries: HashMap<K, (Arc<V>, u64)>,
+  tick: u64,
+}
+
+pub struct LruCache<K, V> {
+  shards: Vec<Mutex<Shard<K, V>>>,
+  shard_capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+  /// A cache keeping about `capacity` entries. A capacity of 0 disables it.
+  pub fn new(capacity: usize) -> LruCache<K, V> {
+    LruCache {
+      shards: (0..SHARDS)
+        .map(|_| {
+          Mutex::new(Shard {
+            entries: HashMap::new(),
+            tick: 0,
+          })
+        })
+        .collect(),
+      shard_capacity: capacity.div_ceil(SHARDS),
+    }
+  }
+
+  fn shard(&self, key: &K) -> &Mutex<Shard<K, V>> {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    &self.shards[hasher.finish() as usize % SHARDS]
+  }
+
+  pub fn get(&self, key: &K) -> Option<Arc<V>> {
+    let mut shard = self.shard(key).lock().unwrap();
+    shard.tick += 1;
+    let tick = shard.tick;
+    shard.entries.get_mut(key).map(|(value, used)| {
+      *used = tick;
+      value.clone()
+    })
+  }
+
+  /// Stores a value, evicting the least recently used entry of its shard if it is full
+  pub fn insert(&self, key: K, value: Arc<V>) {
+    if self.shard_capacity == 0 {
+      return;
+    }
+
+    let mut shard = self.shard(&key).lock().unwrap();
+    if shard.entries.len() >= self.shard_capacity && !shard.entries.contains_key(&key) {
+      let oldest = shard
+        .entries
+        .iter()
+        .min_by_key(|(_, (_, used))| *used)
+        .map(|(key, _)| key.clone());
+      if let Some(oldest) = oldest {
+        shard.entries.remove(&oldest);
+      }
+    }
+    shard.tick += 1;
+    let tick = shard.tick;
+    shard.entries.insert(key, (value, tick));
+  }
+}
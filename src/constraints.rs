@@ -0,0 +1,209 @@
+//! What the feedback received so far says about the answer.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::mark::Mark;
+
+/// A single fact learned about the answer. Positions start at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Constraint {
+  /// The letter is at this position
+  At(usize, char),
+  /// The letter is not at this position
+  NotAt(usize, char),
+  /// The answer contains the letter at least this many times
+  AtLeast(char, usize),
+  /// The answer contains the letter at most this many times, 0 meaning it's absent
+  AtMost(char, usize),
+}
+
+impl fmt::Display for Constraint {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      Constraint::At(position, letter) => write!(f, "{} is at position {}", letter, position + 1),
+      Constraint::NotAt(position, letter) => {
+        write!(f, "{} is not at position {}", letter, position + 1)
+      }
+      Constraint::AtLeast(letter, 1) => write!(f, "{} is in the word", letter),
+      Constraint::AtLeast(letter, count) => {
+        write!(f, "{} appears at least {} times", letter, count)
+      }
+      Constraint::AtMost(letter, 0) => write!(f, "{} is not in the word", letter),
+      Constraint::AtMost(letter, count) => write!(f, "{} appears at most {} times", letter, count),
+    }
+  }
+}
+
+/// The facts learned about the answer, kept in a normalized form
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+  at: BTreeMap<usize, char>,
+  not_at: BTreeSet<(usize, char)>,
+  at_least: BTreeMap<char, usize>,
+  at_most: BTreeMap<char, usize>,
+}
+
+impl Constraints {
+  pub fn new() -> Constraints {
+    Constraints::default()
+  }
+
+  /// The raw facts given by a single row of feedback
+  pub fn from_feedback(guess: &str, marks: &[Mark]) -> Vec<Constraint> {
+    let mut constraints = Vec::new();
+    let mut present: BTreeMap<char, usize> = BTreeMap::new();
+    let mut capped: BTreeSet<char> = BTreeSet::new();
+
+    for (position, (letter, mark)) in guess.chars().zip(marks.iter()).enumerate() {
+      match mark {
+        Mark::RightPosition => {
+          constraints.push(Constraint::At(position, letter));
+          *present.entry(letter).or_default() += 1;
+        }
+        Mark::WrongPosition => {
+          constraints.push(Constraint::NotAt(position, letter));
+          *present.entry(letter).or_default() += 1;
+        }
+        Mark::NotPresent => {
+          constraints.push(Constraint::NotAt(position, letter));
+          capped.insert(letter);
+        }
+      }
+    }
+
+    for (&letter, &count) in present.iter() {
+      constraints.push(Constraint::AtLeast(letter, count));
+    }
+    for &letter in capped.iter() {
+      let count = present.get(&letter).copied().unwrap_or(0);
+      constraints.push(Constraint::AtMost(letter, count));
+    }
+
+    constraints
+  }
+
+  /// Adds a fact. Returns whether it's new, i.e. not implied by what was already known.
+  pub fn add(&mut self, constraint: Constraint) -> bool {
+    match constraint {
+      Constraint::At(position, letter) => self.at.insert(position, letter) != Some(letter),
+      Constraint::NotAt(position, letter) => {
+        let implied = self.at.get(&position).is_some_and(|&known| known != letter)
+          || self.at_most.get(&letter) == Some(&0);
+        self.not_at.insert((position, letter)) && !implied
+      }
+      Constraint::AtLeast(letter, count) => {
+        let known = self.min_count(letter);
+        let entry = self.at_least.entry(letter).or_default();
+        *entry = (*entry).max(count);
+        count > known
+      }
+      Constraint::AtMost(letter, count) => {
+        let entry = self.at_most.entry(letter).or_insert(usize::MAX);
+        let is_new = count < *entry;
+        *entry = (*entry).min(count);
+        is_new
+      }
+    }
+  }
+
+  /// Learns from a row of feedback and returns the facts that weren't known before
+  pub fn apply(&mut self, guess: &str, marks: &[Mark]) -> Vec<Constraint> {
+    let mut learned: Vec<Constraint> = Constraints::from_feedback(guess, marks)
+      .into_iter()
+      .filter(|&constraint| self.add(constraint))
+      .collect();
+
+    // Letters found to be absent make the "not at" facts about them redundant
+    learned.retain(|constraint| match *constraint {
+      Constraint::NotAt(_, letter) => self.at_most.get(&letter) != Some(&0),
+      _ => true,
+    });
+
+    learned
+  }
+
+  /// The smallest number of times the letter can appear in the answer
+  pub fn min_count(&self, letter: char) -> usize {
+    let at_least = self.at_least.get(&letter).copied().unwrap_or(0);
+    at_least.max(self.placed_count(letter))
+  }
+
+  fn placed_count(&self, letter: char) -> usize {
+    self.at.values().filter(|&&known| known == letter).count()
+  }
+
+  /// The largest number of times the letter can appear in the answer, if known
+  pub fn max_count(&self, letter: char) -> Option<usize> {
+    self.at_most.get(&letter).copied()
+  }
+
+  /// Whether the feedback said anything about the letter, i.e. it was part of a guess
+  pub fn is_known(&self, letter: char) -> bool {
+    self.min_count(letter) > 0 || self.max_count(letter).is_some()
+  }
+
+  /// The letter known to be at a position
+  pub fn letter_at(&self, position: usize) -> Option<char> {
+    self.at.get(&position).copied()
+  }
+
+  /// Whether the letter is known not to be at a position
+  pub fn excludes(&self, position: usize, letter: char) -> bool {
+    self.not_at.contains(&(position, letter)) || self.max_count(letter) == Some(0)
+  }
+
+  /// Whether a word satisfies every known fact
+  pub fn allows(&self, word: &str) -> bool {
+    for (position, letter) in word.chars().enumerate() {
+      if let Some(known) = self.letter_at(position) {
+        if known != letter {
+          return false;
+        }
+      }
+      if self.not_at.contains(&(position, letter)) {
+        return false;
+      }
+    }
+
+    let letters: BTreeSet<char> = self
+      .at
+      .values()
+      .chain(self.at_least.keys())
+      .chain(self.at_most.keys())
+      .copied()
+      .collect();
+
+    letters.into_iter().all(|letter| {
+      let count = word.chars().filter(|&c| c == letter).count();
+      count >= self.min_count(letter) && self.max_count(letter).is_none_or(|max| count <= max)
+    })
+  }
+
+  /// All the known facts, without the redundant ones
+  pub fn iter(&self) -> impl Iterator<Item = Constraint> + '_ {
+    let at = self
+      .at
+      .iter()
+      .map(|(&position, &letter)| Constraint::At(position, letter));
+    let not_at = self
+      .not_at
+      .iter()
+      .filter(move |&&(position, letter)| {
+        self.letter_at(position).is_none() && self.max_count(letter) != Some(0)
+      })
+      .map(|&(position, letter)| Constraint::NotAt(position, letter));
+    let at_least = self
+      .at_least
+      .keys()
+      .filter(move |&&letter| self.min_count(letter) > self.placed_count(letter))
+      .map(move |&letter| Constraint::AtLeast(letter, self.min_count(letter)));
+    let at_most = self
+      .at_most
+      .iter()
+      .map(|(&letter, &count)| Constraint::AtMost(letter, count));
+
+    at.chain(not_at).chain(at_least).chain(at_most)
+  }
+}
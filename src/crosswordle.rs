@@ -0,0 +1,113 @@
+//! The `crosswordle` subcommand: the reverse of solving. Given the answer and the colors of every
+//! row of a finished game, find guesses that give those colors.
+
+use std::convert::TryFrom;
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::mark::Mark;
+
+use crate::{DictString, WORD_LENGTH};
+
+/// Number of example words shown for each row
+const SHOWN_EXAMPLES: usize = 10;
+
+/// Picks one word per row, all different, depth first. In hard mode each word must also respect
+/// the feedback of the rows above it.
+fn search<'a>(
+  rows: &[Vec<Mark>],
+  options: &[Vec<&'a DictString>],
+  hard: bool,
+  chosen: &mut Vec<&'a DictString>,
+) -> bool {
+  let row = chosen.len();
+  if row == rows.len() {
+    return true;
+  }
+
+  for &word in options[row].iter() {
+    if chosen.contains(&word) {
+      continue;
+    }
+    if hard
+      && !chosen
+        .iter()
+        .zip(rows)
+        .all(|(guess, marks)| engine::respects_hard_mode(word, guess, marks))
+    {
+      continue;
+    }
+
+    chosen.push(word);
+    if search(rows, options, hard, chosen) {
+      return true;
+    }
+    chosen.pop();
+  }
+
+  false
+}
+
+/// Lists the words giving each row's colors against `answer`, then one guess sequence matching
+/// all the rows
+pub fn run(
+  dictionary: &WordArena,
+  answer: &str,
+  rows: &[String],
+  hard: bool,
+) -> Result<(), String> {
+  let answer = answer.to_lowercase();
+  if answer.chars().count() != WORD_LENGTH {
+    return Err(format!("The answer needs {} letters", WORD_LENGTH));
+  }
+
+  let rows = rows
+    .iter()
+    .map(|row| {
+      let marks = row
+        .chars()
+        .map(Mark::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| err.to_string())?;
+      if marks.len() != WORD_LENGTH {
+        return Err(format!("Expected {} marks in {:?}", WORD_LENGTH, row));
+      }
+      Ok(marks)
+    })
+    .collect::<Result<Vec<_>, String>>()?;
+
+  let options: Vec<Vec<&DictString>> = rows
+    .iter()
+    .map(|marks| {
+      dictionary
+        .iter()
+        .filter(|word| engine::compute_bucket(word, &answer) == *marks)
+        .collect()
+    })
+    .collect();
+
+  for (index, (marks, words)) in rows.iter().zip(&options).enumerate() {
+    let pattern: String = marks.iter().map(|mark| mark.to_char()).collect();
+    let examples: Vec<&str> = words.iter().take(SHOWN_EXAMPLES).copied().collect();
+    println!(
+      "Row {} ({}): {} words, {}{}",
+      index + 1,
+      pattern,
+      words.len(),
+      examples.join(" "),
+      if words.len() > SHOWN_EXAMPLES {
+        " ..."
+      } else {
+        ""
+      }
+    );
+  }
+
+  let mut chosen = vec![];
+  if search(&rows, &options, hard, &mut chosen) {
+    println!("Solution: {}", chosen.join(" "));
+  } else {
+    println!("No sequence of different words matches every row");
+  }
+
+  Ok(())
+}
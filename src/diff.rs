@@ -0,0 +1,80 @@
+//! Reporting what changed when feedback was applied.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::constraints::Constraint;
+
+/// The difference between the state of a game before and after a row of feedback
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDiff<'a> {
+  /// Number of candidates before the feedback
+  pub candidates_before: usize,
+  /// Number of candidates left after the feedback
+  pub candidates_after: usize,
+  /// Facts about the answer that the feedback revealed
+  pub learned: Vec<Constraint>,
+  /// Top suggestions from before the feedback that are no longer among the top suggestions
+  pub dropped: Vec<&'a str>,
+}
+
+impl<'a> StateDiff<'a> {
+  /// Compares the top suggestions before and after applying feedback. The learned constraints
+  /// are the ones returned by [`crate::constraints::Constraints::apply`].
+  pub fn new(
+    candidates_before: usize,
+    candidates_after: usize,
+    learned: Vec<Constraint>,
+    previous_top: &[&'a str],
+    current_top: &[&str],
+  ) -> StateDiff<'a> {
+    let dropped = previous_top
+      .iter()
+      .filter(|word| !current_top.contains(word))
+      .copied()
+      .collect();
+
+    StateDiff {
+      candidates_before,
+      candidates_after,
+      learned,
+      dropped,
+    }
+  }
+
+  /// Number of candidates the feedback ruled out
+  pub fn eliminated(&self) -> usize {
+    self.candidates_before.saturating_sub(self.candidates_after)
+  }
+}
+
+impl<'a> fmt::Display for StateDiff<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(
+      f,
+      "Eliminated {} of {} candidates, {} left",
+      self.eliminated(),
+      self.candidates_before,
+      self.candidates_after
+    )?;
+
+    if self.learned.is_empty() {
+      writeln!(f, "Learned nothing new")?;
+    } else {
+      writeln!(f, "Learned:")?;
+      for constraint in self.learned.iter() {
+        writeln!(f, "  {}", constraint)?;
+      }
+    }
+
+    if !self.dropped.is_empty() {
+      write!(f, "No longer top suggestions:")?;
+      for word in self.dropped.iter() {
+        write!(f, " {}", word)?;
+      }
+      writeln!(f)?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,99 @@
+//! How hard a word is to find.
+//!
+//! The words a solver (or a player) struggles with are the ones with many "neighbours", words
+//! that differ from them in a single position, like `hatch`, `latch`, `match` and `patch`. Greens
+//! quickly narrow the answer down to the family but then every guess only rules out a few of its
+//! members. The difficulty score of a word is its number of neighbours among the candidates.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+
+/// Number of candidates that differ from `word` at exactly one position
+pub fn neighbours<S: AsRef<str>>(word: &str, candidates: &[S]) -> usize {
+  candidates
+    .iter()
+    .filter(|candidate| {
+      let candidate = candidate.as_ref();
+      candidate.chars().count() == word.chars().count()
+        && candidate
+          .chars()
+          .zip(word.chars())
+          .filter(|(a, b)| a != b)
+          .count()
+          == 1
+    })
+    .count()
+}
+
+/// A band of difficulty scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tier {
+  /// At most one neighbour
+  Easy,
+  /// Two or three neighbours
+  Medium,
+  /// Four or more neighbours
+  Hard,
+}
+
+impl Tier {
+  pub const ALL: [Tier; 3] = [Tier::Easy, Tier::Medium, Tier::Hard];
+
+  /// The tier of a difficulty score
+  pub fn of_score(score: usize) -> Tier {
+    match score {
+      0..=1 => Tier::Easy,
+      2..=3 => Tier::Medium,
+      _ => Tier::Hard,
+    }
+  }
+
+  /// The tier of a word among the given candidates
+  pub fn of_word<S: AsRef<str>>(word: &str, candidates: &[S]) -> Tier {
+    Tier::of_score(neighbours(word, candidates))
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Tier::Easy => "easy",
+      Tier::Medium => "medium",
+      Tier::Hard => "hard",
+    }
+  }
+}
+
+impl fmt::Display for Tier {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+/// Error returned when parsing an unknown tier name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTierError(String);
+
+impl fmt::Display for ParseTierError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "unknown tier {:?}, expected easy, medium or hard",
+      self.0
+    )
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseTierError {}
+
+impl FromStr for Tier {
+  type Err = ParseTierError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Tier::ALL
+      .iter()
+      .copied()
+      .find(|tier| tier.name() == s)
+      .ok_or_else(|| ParseTierError(s.to_string()))
+  }
+}
@@ -0,0 +1,376 @@
+//! The solver core: pattern computation, dictionary reduction and the information math.
+//!
+//! Nothing in here needs `std`, only `alloc`, so this module keeps working when the crate is
+//! built with `default-features = false` (in which case the `libm` feature provides `log2`).
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::feedback::FeedbackRule;
+use crate::mark::Mark;
+
+/// Longest word supported by [`pattern_code`]. The pattern space is `3^MAX_WORD_LEN`.
+pub const MAX_WORD_LEN: usize = 12;
+
+#[cfg(feature = "std")]
+pub(crate) fn log2(x: f64) -> f64 {
+  x.log2()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+pub(crate) fn log2(x: f64) -> f64 {
+  libm::log2(x)
+}
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("the solver core needs either the `std` or the `libm` feature for its math");
+
+/// This function tries to faithfully reproduce the same algorithm as found
+/// in the original Wordle. Letters from the word get "used up" first by their presence
+/// at the exact same position (i.e. "green" marks). Then if a letter appears multiple times in
+/// the guess but at the wrong position, it will start using up the same letter in the word, to
+/// ensure that if there is just a single occurence of the guessed letter in the word, only the
+/// first occurrence in the guess gets marked "yellow" (wrong position)
+///
+/// Letters are the `char`s of the words, not their bytes, so words of any alphabet get one mark
+/// per letter.
+pub fn compute_bucket(guess: &str, word: &str) -> Vec<Mark> {
+  let guess: Vec<char> = guess.chars().collect();
+  let word: Vec<char> = word.chars().collect();
+  let mut used = vec![false; word.len()];
+  let mut result = vec![Mark::NotPresent; guess.len()];
+
+  for ((index, guess_char), word_char) in guess.iter().enumerate().zip(&word) {
+    if word_char == guess_char {
+      used[index] = true;
+      result[index] = Mark::RightPosition;
+    }
+  }
+
+  for (guess_index, guess_char) in guess.iter().enumerate() {
+    for (word_index, word_char) in word.iter().enumerate() {
+      if result[guess_index] == Mark::RightPosition {
+        continue;
+      }
+      if used[word_index] {
+        continue;
+      }
+      if word_index == guess_index {
+        continue;
+      }
+
+      if guess_char == word_char {
+        used[word_index] = true;
+        result[guess_index] = Mark::WrongPosition;
+        break;
+      }
+    }
+  }
+
+  result
+}
+
+/// Computes the base-3 code of the marks Wordle would give `guess` if the answer is `word`.
+///
+/// This is the same algorithm as [`compute_bucket`], but it works on fixed size arrays. The first
+/// letter is the least significant digit, matching [`crate::mark::pack`].
+///
+/// Panics if the guess is longer than [`MAX_WORD_LEN`].
+pub fn pattern_code(guess: &str, word: &str) -> usize {
+  let mut guess_chars = ['\0'; MAX_WORD_LEN];
+  let mut word_chars = ['\0'; MAX_WORD_LEN];
+  let mut len = 0;
+
+  for (index, (guess_char, word_char)) in guess.chars().zip(word.chars()).enumerate() {
+    guess_chars[index] = guess_char;
+    word_chars[index] = word_char;
+    len = index + 1;
+  }
+
+  let mut used = [false; MAX_WORD_LEN];
+  let mut result = [0u8; MAX_WORD_LEN];
+
+  for index in 0..len {
+    if guess_chars[index] == word_chars[index] {
+      used[index] = true;
+      result[index] = 2;
+    }
+  }
+
+  for guess_index in 0..len {
+    if result[guess_index] == 2 {
+      continue;
+    }
+    for word_index in 0..len {
+      if used[word_index] || word_index == guess_index {
+        continue;
+      }
+      if guess_chars[guess_index] == word_chars[word_index] {
+        used[word_index] = true;
+        result[guess_index] = 1;
+        break;
+      }
+    }
+  }
+
+  result[..len]
+    .iter()
+    .rev()
+    .fold(0, |code, &digit| code * 3 + digit as usize)
+}
+
+/// Whether `word` could still be the answer after `guess` was marked with `marks`
+pub fn is_consistent(guess: &str, marks: &[Mark], word: &str) -> bool {
+  compute_bucket(guess, word) == marks
+}
+
+/// Why hard mode refuses a word, as the game puts it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardModeViolation {
+  /// A green letter isn't kept at its position, counting from 0
+  Green { position: usize, letter: char },
+  /// A letter found isn't used, or not as many times as found
+  Missing { letter: char },
+  /// A letter found not to be in the word is played again, which Antiwordle refuses
+  Absent { letter: char },
+  /// The word was played already, which Antiwordle refuses
+  Repeated,
+}
+
+impl core::fmt::Display for HardModeViolation {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    match *self {
+      HardModeViolation::Green { position, letter } => {
+        let suffix = match position + 1 {
+          1 => "st",
+          2 => "nd",
+          3 => "rd",
+          _ => "th",
+        };
+        write!(
+          f,
+          "{}{} letter must be {}",
+          position + 1,
+          suffix,
+          letter.to_uppercase()
+        )
+      }
+      HardModeViolation::Missing { letter } => {
+        write!(f, "guess must contain {}", letter.to_uppercase())
+      }
+      HardModeViolation::Absent { letter } => {
+        write!(f, "guess can't contain {}", letter.to_uppercase())
+      }
+      HardModeViolation::Repeated => write!(f, "guess was already played"),
+    }
+  }
+}
+
+/// Whether `word` can be played in hard mode after `guess` got `marks`: the greens stay in place
+/// and the letters found are all used
+pub fn respects_hard_mode(word: &str, guess: &str, marks: &[Mark]) -> bool {
+  hard_mode_violation(word, guess, marks).is_none()
+}
+
+/// The first rule of hard mode `word` breaks after `guess` got `marks`, greens first, see
+/// [`respects_hard_mode`]
+pub fn hard_mode_violation(word: &str, guess: &str, marks: &[Mark]) -> Option<HardModeViolation> {
+  let word: Vec<char> = word.chars().collect();
+  let guess: Vec<char> = guess.chars().collect();
+
+  let green_moved = marks
+    .iter()
+    .enumerate()
+    .find(|&(index, &mark)| mark == Mark::RightPosition && word[index] != guess[index]);
+  if let Some((position, _)) = green_moved {
+    return Some(HardModeViolation::Green {
+      position,
+      letter: guess[position],
+    });
+  }
+
+  let missing = guess.iter().find(|&letter| {
+    let found = guess
+      .iter()
+      .zip(marks)
+      .filter(|&(l, &mark)| l == letter && mark != Mark::NotPresent)
+      .count();
+    word.iter().filter(|&l| l == letter).count() < found
+  });
+  missing.map(|&letter| HardModeViolation::Missing { letter })
+}
+
+/// The first rule of Antiwordle `word` breaks after `guess` got `marks`: those of hard mode (see
+/// [`hard_mode_violation`]), then that letters found not to be in the word can't be played and
+/// that no word is played twice
+pub fn anti_wordle_violation(word: &str, guess: &str, marks: &[Mark]) -> Option<HardModeViolation> {
+  if let Some(violation) = hard_mode_violation(word, guess, marks) {
+    return Some(violation);
+  }
+  if word == guess {
+    return Some(HardModeViolation::Repeated);
+  }
+  let found = |letter: char| {
+    guess
+      .chars()
+      .zip(marks)
+      .any(|(l, &mark)| l == letter && mark != Mark::NotPresent)
+  };
+  guess
+    .chars()
+    .find(|&letter| !found(letter) && word.contains(letter))
+    .map(|letter| HardModeViolation::Absent { letter })
+}
+
+/// Keeps only the candidates that are consistent with `guess` having been marked with `marks`
+pub fn reduce<'a, S: AsRef<str> + ?Sized>(
+  guess: &str,
+  marks: &[Mark],
+  candidates: &[&'a S],
+) -> Vec<&'a S> {
+  candidates
+    .iter()
+    .filter(|word| is_consistent(guess, marks, word.as_ref()))
+    .copied()
+    .collect()
+}
+
+/// Keeps only the candidates that are consistent with `guess` having been given `pattern` under
+/// the given feedback rule
+pub fn reduce_with<'a, R, S>(
+  rule: &R,
+  guess: &str,
+  pattern: usize,
+  candidates: &[&'a S],
+) -> Vec<&'a S>
+where
+  R: FeedbackRule + ?Sized,
+  S: AsRef<str> + ?Sized,
+{
+  candidates
+    .iter()
+    .filter(|word| rule.is_consistent(guess, pattern, word.as_ref()))
+    .copied()
+    .collect()
+}
+
+/// Counts how many candidates fall into each feedback pattern of `guess`.
+///
+/// Only non-empty buckets are returned, ordered by their pattern code (see [`pattern_code`]). The
+/// sizes always add up to the number of candidates, which is what [`entropy`],
+/// [`worst_case_information`] and [`gambling_information`] expect.
+pub fn bucket_sizes<S: AsRef<str>>(guess: &str, candidates: &[S]) -> Vec<u32> {
+  bucket_sizes_with(&crate::feedback::Wordle, guess, candidates)
+}
+
+/// Like [`bucket_sizes`], but the buckets are the patterns of the given feedback rule
+pub fn bucket_sizes_with<R, S>(rule: &R, guess: &str, candidates: &[S]) -> Vec<u32>
+where
+  R: FeedbackRule + ?Sized,
+  S: AsRef<str>,
+{
+  let mut counts = vec![0u32; rule.pattern_count(guess.chars().count())];
+
+  for candidate in candidates {
+    counts[rule.pattern_code(guess, candidate.as_ref())] += 1;
+  }
+
+  counts.into_iter().filter(|&count| count > 0).collect()
+}
+
+/// Splits the candidates into the groups Wordle can't tell apart after `guess` is played.
+///
+/// Each group is returned together with the marks that identify it, ordered by pattern code.
+/// The groups are in the same order as [`bucket_sizes`] and keep the relative order of the
+/// candidates.
+pub fn partition<'a, S: AsRef<str> + ?Sized>(
+  guess: &str,
+  candidates: &[&'a S],
+) -> Vec<(Vec<Mark>, Vec<&'a S>)> {
+  let length = guess.chars().count();
+  let mut groups: BTreeMap<usize, Vec<&'a S>> = BTreeMap::new();
+
+  for &candidate in candidates {
+    groups
+      .entry(pattern_code(guess, candidate.as_ref()))
+      .or_default()
+      .push(candidate);
+  }
+
+  groups
+    .into_iter()
+    .map(|(code, words)| (code_to_marks(code, length), words))
+    .collect()
+}
+
+fn code_to_marks(code: usize, length: usize) -> Vec<Mark> {
+  let mut rest = code as u32;
+  (0..length)
+    .map(|_| {
+      let mark = Mark::from_digit(rest % 3).unwrap();
+      rest /= 3;
+      mark
+    })
+    .collect()
+}
+
+/// Expected information (in bits) of a subdivision with the given bucket sizes.
+///
+/// This is the Shannon entropy of the distribution where each bucket is as likely as the share
+/// of candidates it holds. Empty buckets contribute nothing.
+pub fn entropy(bucket_sizes: &[u32]) -> f64 {
+  let total = bucket_sizes.iter().sum::<u32>() as f64;
+
+  bucket_sizes
+    .iter()
+    .filter(|&&size| size > 0)
+    .map(|&size| {
+      let probability = size as f64 / total;
+      probability * log2(1.0 / probability)
+    })
+    .sum()
+}
+
+/// Like [`entropy`], but each bucket is as likely as its share of the total weight, for candidates
+/// that are not all equally likely to be the answer
+pub fn weighted_entropy(bucket_weights: &[f64]) -> f64 {
+  let total: f64 = bucket_weights.iter().sum();
+  if total <= 0.0 {
+    return 0.0;
+  }
+
+  bucket_weights
+    .iter()
+    .filter(|&&weight| weight > 0.0)
+    .map(|&weight| {
+      let probability = weight / total;
+      probability * log2(1.0 / probability)
+    })
+    .sum()
+}
+
+/// Information (in bits) of a subdivision assuming the largest bucket is the one we end up in
+pub fn worst_case_information(bucket_sizes: &[u32]) -> f64 {
+  let total = bucket_sizes.iter().sum::<u32>() as f64;
+  let worst_case_count = bucket_sizes.iter().copied().max().unwrap_or(0) as f64;
+
+  log2(total / worst_case_count)
+}
+
+/// Information (in bits) of the bucket found at the `gambling_factor` percentile when buckets
+/// are ordered from largest to smallest. A factor of 0 is the worst case. Sorts `bucket_sizes`.
+pub fn gambling_information(bucket_sizes: &mut [u32], gambling_factor: f64) -> f64 {
+  let total = bucket_sizes.iter().sum::<u32>() as f64;
+  bucket_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+  let mut total_size = 0;
+  for &size in bucket_sizes.iter() {
+    total_size += size;
+    if total_size as f64 / total > gambling_factor {
+      return log2(total / size as f64);
+    }
+  }
+
+  0.0
+}
@@ -0,0 +1,84 @@
+//! What the command line reports instead of panicking.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum SolverError {
+  /// A file couldn't be read or written
+  Io { path: String, source: io::Error },
+  /// A dictionary has no word of the length `wanted`, with the length most of its words have if
+  /// any
+  EmptyDictionary {
+    path: String,
+    wanted: usize,
+    length: Option<usize>,
+  },
+  /// A line of feedback isn't a word followed by its marks
+  InvalidFeedback { input: String, reason: String },
+  /// Options that don't go together
+  ConflictingOptions(String),
+  /// A command failed, with its own message
+  Command(String),
+}
+
+impl SolverError {
+  pub fn io(path: &str, source: io::Error) -> SolverError {
+    SolverError::Io {
+      path: path.to_string(),
+      source,
+    }
+  }
+}
+
+impl fmt::Display for SolverError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SolverError::Io { path, source } if source.kind() == io::ErrorKind::NotFound => {
+        write!(f, "{}: no such file", path)
+      }
+      SolverError::Io { path, source } => write!(f, "{}: {}", path, source),
+      SolverError::EmptyDictionary {
+        path,
+        wanted,
+        length: Some(length),
+      } => write!(
+        f,
+        "{}: no word of {} letters, its words have {}, play them with --length {}",
+        path, wanted, length, length
+      ),
+      SolverError::EmptyDictionary {
+        path,
+        wanted,
+        length: None,
+      } => write!(
+        f,
+        "{}: no word of {} letters, is it a dictionary?",
+        path, wanted
+      ),
+      SolverError::InvalidFeedback { input, reason } => write!(
+        f,
+        "can't read {:?}: {}. Type a guess and its marks, e.g. `raise -+--o`",
+        input, reason
+      ),
+      SolverError::ConflictingOptions(message) => write!(f, "{}", message),
+      SolverError::Command(message) => write!(f, "{}", message),
+    }
+  }
+}
+
+impl Error for SolverError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      SolverError::Io { source, .. } => Some(source),
+      _ => None,
+    }
+  }
+}
+
+impl From<String> for SolverError {
+  fn from(message: String) -> SolverError {
+    SolverError::Command(message)
+  }
+}
@@ -0,0 +1,339 @@
+//! Feedback rules, i.e. how a game answers a guess.
+//!
+//! The reducer and the scorers only need to know which guess/answer pairs produce the same
+//! feedback, so they work with an opaque pattern code. A [`FeedbackRule`] maps each guess and
+//! answer to such a code and converts codes from and to the text users type in.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+
+use crate::engine::{self, MAX_WORD_LEN};
+use crate::mark::Mark;
+
+/// How a game computes the feedback for a guess
+pub trait FeedbackRule: Sync {
+  /// Number of distinct pattern codes for words of the given length. Codes are below this value.
+  fn pattern_count(&self, length: usize) -> usize;
+
+  /// The code of the feedback given for `guess` when the answer is `answer`
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize;
+
+  /// Whether `answer` could be the answer after the game gave `pattern` for `guess`
+  fn is_consistent(&self, guess: &str, pattern: usize, answer: &str) -> bool {
+    self.pattern_code(guess, answer) == pattern
+  }
+
+  /// Whether `pattern` means the guess was the answer
+  fn is_solved(&self, pattern: usize, length: usize) -> bool;
+
+  /// Parses the textual form of a pattern. Returns `None` if the text is not valid for this rule.
+  fn parse_pattern(&self, text: &str) -> Option<usize>;
+
+  /// Formats a pattern code for words of the given length
+  fn format_pattern(&self, pattern: usize, length: usize) -> String;
+}
+
+fn to_digits(pattern: usize, length: usize) -> impl Iterator<Item = usize> {
+  let mut rest = pattern;
+  (0..length).map(move |_| {
+    let digit = rest % 3;
+    rest /= 3;
+    digit
+  })
+}
+
+fn from_digits<I: DoubleEndedIterator<Item = usize>>(digits: I) -> usize {
+  digits.rev().fold(0, |code, digit| code * 3 + digit)
+}
+
+/// The standard Wordle rule, see [`engine::compute_bucket`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wordle;
+
+impl FeedbackRule for Wordle {
+  fn pattern_count(&self, length: usize) -> usize {
+    3usize.pow(length as u32)
+  }
+
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize {
+    engine::pattern_code(guess, answer)
+  }
+
+  fn is_solved(&self, pattern: usize, length: usize) -> bool {
+    pattern == self.pattern_count(length) - 1
+  }
+
+  fn parse_pattern(&self, text: &str) -> Option<usize> {
+    let digits = text
+      .chars()
+      .map(|c| Mark::try_from(c).ok().map(|mark| mark.to_digit() as usize))
+      .collect::<Option<Vec<_>>>()?;
+
+    Some(from_digits(digits.into_iter()))
+  }
+
+  fn format_pattern(&self, pattern: usize, length: usize) -> String {
+    to_digits(pattern, length)
+      .map(|digit| Mark::from_digit(digit as u32).unwrap().to_char())
+      .collect()
+  }
+}
+
+/// Mastermind style feedback: only the number of letters at the right position and the number
+/// of letters present at a wrong position are revealed, not which letters they are.
+///
+/// Patterns are written as `right/misplaced`, e.g. `2/1`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mastermind;
+
+impl Mastermind {
+  const BASE: usize = MAX_WORD_LEN + 1;
+
+  fn counts(guess: &str, answer: &str) -> (usize, usize) {
+    let mut answer_chars = ['\0'; MAX_WORD_LEN];
+    let mut used = [false; MAX_WORD_LEN];
+    let mut length = 0;
+    let mut right = 0;
+
+    for (index, (guess_char, answer_char)) in guess.chars().zip(answer.chars()).enumerate() {
+      answer_chars[index] = answer_char;
+      length = index + 1;
+      if guess_char == answer_char {
+        used[index] = true;
+        right += 1;
+      }
+    }
+
+    let mut misplaced = 0;
+    for (guess_index, guess_char) in guess.chars().enumerate().take(length) {
+      if answer_chars[guess_index] == guess_char {
+        continue;
+      }
+      for answer_index in 0..length {
+        if !used[answer_index] && answer_chars[answer_index] == guess_char {
+          used[answer_index] = true;
+          misplaced += 1;
+          break;
+        }
+      }
+    }
+
+    (right, misplaced)
+  }
+}
+
+impl FeedbackRule for Mastermind {
+  fn pattern_count(&self, length: usize) -> usize {
+    length * Mastermind::BASE + length + 1
+  }
+
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize {
+    let (right, misplaced) = Mastermind::counts(guess, answer);
+    right * Mastermind::BASE + misplaced
+  }
+
+  fn is_solved(&self, pattern: usize, length: usize) -> bool {
+    pattern / Mastermind::BASE == length
+  }
+
+  fn parse_pattern(&self, text: &str) -> Option<usize> {
+    let (right, misplaced) = text.split_once('/')?;
+    let right: usize = right.trim().parse().ok()?;
+    let misplaced: usize = misplaced.trim().parse().ok()?;
+
+    if right + misplaced > MAX_WORD_LEN {
+      return None;
+    }
+
+    Some(right * Mastermind::BASE + misplaced)
+  }
+
+  fn format_pattern(&self, pattern: usize, _length: usize) -> String {
+    alloc::format!(
+      "{}/{}",
+      pattern / Mastermind::BASE,
+      pattern % Mastermind::BASE
+    )
+  }
+}
+
+/// Wordle Peaks feedback: for every position, whether the answer's letter is the guessed one,
+/// or comes earlier or later in the alphabet.
+///
+/// Patterns are written with `v` (earlier), `^` (later) and `o` (correct).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Peaks;
+
+impl Peaks {
+  const EARLIER: usize = 0;
+  const LATER: usize = 1;
+  const CORRECT: usize = 2;
+}
+
+impl FeedbackRule for Peaks {
+  fn pattern_count(&self, length: usize) -> usize {
+    3usize.pow(length as u32)
+  }
+
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize {
+    let mut code = 0;
+    let mut weight = 1;
+
+    for (guess_char, answer_char) in guess.chars().zip(answer.chars()) {
+      let digit = match answer_char.cmp(&guess_char) {
+        Ordering::Less => Peaks::EARLIER,
+        Ordering::Greater => Peaks::LATER,
+        Ordering::Equal => Peaks::CORRECT,
+      };
+      code += digit * weight;
+      weight *= 3;
+    }
+
+    code
+  }
+
+  fn is_solved(&self, pattern: usize, length: usize) -> bool {
+    pattern == self.pattern_count(length) - 1
+  }
+
+  fn parse_pattern(&self, text: &str) -> Option<usize> {
+    let digits = text
+      .chars()
+      .map(|c| match c {
+        'v' => Some(Peaks::EARLIER),
+        '^' => Some(Peaks::LATER),
+        'o' => Some(Peaks::CORRECT),
+        _ => None,
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    Some(from_digits(digits.into_iter()))
+  }
+
+  fn format_pattern(&self, pattern: usize, length: usize) -> String {
+    to_digits(pattern, length)
+      .map(|digit| match digit {
+        Peaks::EARLIER => 'v',
+        Peaks::LATER => '^',
+        _ => 'o',
+      })
+      .collect()
+  }
+}
+
+/// Warmle feedback: for every position, whether the answer's letter is the guessed one, close to
+/// it in the alphabet (at most `distance` letters away, "warm") or further away ("cold"). Whether
+/// the letter appears elsewhere in the answer doesn't matter. Distances are counted in the
+/// alphabet of the game, letters outside it are always cold.
+///
+/// Patterns are written like Wordle marks: `-` (cold), `+` (warm) and `o` (correct).
+#[derive(Debug, Clone)]
+pub struct Warmle {
+  pub distance: u32,
+  /// Letters of the game, in alphabetical order
+  pub alphabet: Vec<char>,
+}
+
+impl Default for Warmle {
+  /// The original game plays English and counts letters up to 3 away as warm
+  fn default() -> Warmle {
+    Warmle::new(3, &"abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<_>>())
+  }
+}
+
+impl Warmle {
+  pub fn new(distance: u32, alphabet: &[char]) -> Warmle {
+    Warmle {
+      distance,
+      alphabet: alphabet.to_vec(),
+    }
+  }
+
+  fn is_warm(&self, guess_char: char, answer_char: char) -> bool {
+    let position = |letter| self.alphabet.iter().position(|&other| other == letter);
+    match (position(guess_char), position(answer_char)) {
+      (Some(guess), Some(answer)) => guess.abs_diff(answer) <= self.distance as usize,
+      _ => false,
+    }
+  }
+}
+
+impl FeedbackRule for Warmle {
+  fn pattern_count(&self, length: usize) -> usize {
+    3usize.pow(length as u32)
+  }
+
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize {
+    let mut code = 0;
+    let mut weight = 1;
+
+    for (guess_char, answer_char) in guess.chars().zip(answer.chars()) {
+      let mark = if guess_char == answer_char {
+        Mark::RightPosition
+      } else if self.is_warm(guess_char, answer_char) {
+        Mark::WrongPosition
+      } else {
+        Mark::NotPresent
+      };
+      code += mark.to_digit() as usize * weight;
+      weight *= 3;
+    }
+
+    code
+  }
+
+  fn is_solved(&self, pattern: usize, length: usize) -> bool {
+    pattern == self.pattern_count(length) - 1
+  }
+
+  fn parse_pattern(&self, text: &str) -> Option<usize> {
+    Wordle.parse_pattern(text)
+  }
+
+  fn format_pattern(&self, pattern: usize, length: usize) -> String {
+    Wordle.format_pattern(pattern, length)
+  }
+}
+
+/// Wraps a per-position rule (one base-3 digit per letter, like [`Wordle`] or [`Peaks`]) for
+/// games where the host lies about exactly one position of every reported pattern.
+///
+/// Scoring still uses the truthful pattern of the inner rule, but an answer is consistent with a
+/// reported pattern only if its truthful pattern differs from it at exactly one position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lying<R>(pub R);
+
+impl<R: FeedbackRule> FeedbackRule for Lying<R> {
+  fn pattern_count(&self, length: usize) -> usize {
+    self.0.pattern_count(length)
+  }
+
+  fn pattern_code(&self, guess: &str, answer: &str) -> usize {
+    self.0.pattern_code(guess, answer)
+  }
+
+  fn is_consistent(&self, guess: &str, pattern: usize, answer: &str) -> bool {
+    let length = guess.chars().count();
+    let truthful = self.0.pattern_code(guess, answer);
+
+    to_digits(truthful, length)
+      .zip(to_digits(pattern, length))
+      .filter(|(a, b)| a != b)
+      .count()
+      == 1
+  }
+
+  fn is_solved(&self, pattern: usize, length: usize) -> bool {
+    self.0.is_solved(pattern, length)
+  }
+
+  fn parse_pattern(&self, text: &str) -> Option<usize> {
+    self.0.parse_pattern(text)
+  }
+
+  fn format_pattern(&self, pattern: usize, length: usize) -> String {
+    self.0.format_pattern(pattern, length)
+  }
+}
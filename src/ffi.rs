@@ -0,0 +1,148 @@
+//! A C API for embedding the solver in programs written in other languages, built with the
+//! `ffi` feature as a `cdylib`, `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+//! The declarations are in `include/wordle_solver.h`:
+//!
+//! ```c
+//! WordleSolver *solver = wordle_solver_new(allowed, answers, "average");
+//! wordle_solver_apply_feedback(solver, "raise", "-+--o");
+//! char *word = wordle_solver_top_suggestion(solver);
+//! printf("Play %s\n", word);
+//! wordle_solver_free_string(word);
+//! wordle_solver_free(solver);
+//! ```
+//!
+//! Strings are UTF-8 and NUL terminated. Word lists have one word per line.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use crate::arena::WordArena;
+use crate::pattern::Pattern;
+use crate::solver::{strategy_by_name, SolverSession, WORD_LENGTH};
+
+/// Reads a C string, `None` for a null pointer or invalid UTF-8
+///
+/// # Safety
+///
+/// `text` must be null or point to a NUL terminated string.
+unsafe fn read_str<'a>(text: *const c_char) -> Option<&'a str> {
+  if text.is_null() {
+    return None;
+  }
+  CStr::from_ptr(text).to_str().ok()
+}
+
+/// The words of a list with the right length, lowercased
+fn words(text: &str) -> WordArena {
+  text
+    .split_whitespace()
+    .map(str::to_lowercase)
+    .filter(|word| word.chars().count() == WORD_LENGTH)
+    .collect()
+}
+
+/// Starts a game where any word of `answers` can be the answer and any of `allowed` can be
+/// played, scored with the strategy of that name (see [`strategy_by_name`]). Returns null if a
+/// string is invalid, there are no answers or the strategy is unknown. Free the solver with
+/// [`wordle_solver_free`].
+///
+/// # Safety
+///
+/// The arguments must be null or point to NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_new(
+  allowed: *const c_char,
+  answers: *const c_char,
+  strategy: *const c_char,
+) -> *mut SolverSession {
+  let (allowed, answers, strategy) =
+    match (read_str(allowed), read_str(answers), read_str(strategy)) {
+      (Some(allowed), Some(answers), Some(strategy)) => (words(allowed), words(answers), strategy),
+      _ => return ptr::null_mut(),
+    };
+  match strategy_by_name(strategy) {
+    Ok(strategy) if !answers.is_empty() => {
+      Box::into_raw(Box::new(SolverSession::new(allowed, answers, strategy)))
+    }
+    _ => ptr::null_mut(),
+  }
+}
+
+/// Keeps the candidates matching the marks `guess` got, as `-+o` marks, `gyb` letters or emoji
+/// squares. Returns 0, or -1 if the guess or the marks are invalid.
+///
+/// # Safety
+///
+/// `solver` must come from [`wordle_solver_new`] and not be freed yet. The other arguments must
+/// be null or point to NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_apply_feedback(
+  solver: *mut SolverSession,
+  guess: *const c_char,
+  marks: *const c_char,
+) -> c_int {
+  let solver = match solver.as_mut() {
+    Some(solver) => solver,
+    None => return -1,
+  };
+  let pattern: Option<Pattern> = read_str(marks).and_then(|marks| marks.parse().ok());
+  match (read_str(guess), pattern) {
+    (Some(guess), Some(pattern)) if guess.chars().count() == WORD_LENGTH => {
+      solver.apply_feedback(&guess.to_lowercase(), &pattern.marks());
+      0
+    }
+    _ => -1,
+  }
+}
+
+/// The word to play next, null once no candidate is left. Free it with
+/// [`wordle_solver_free_string`].
+///
+/// # Safety
+///
+/// `solver` must come from [`wordle_solver_new`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_top_suggestion(solver: *const SolverSession) -> *mut c_char {
+  solver
+    .as_ref()
+    .and_then(|solver| solver.best_attempt())
+    .and_then(|word| CString::new(word).ok())
+    .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Number of words that can still be the answer
+///
+/// # Safety
+///
+/// `solver` must come from [`wordle_solver_new`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_candidate_count(solver: *const SolverSession) -> usize {
+  solver
+    .as_ref()
+    .map_or(0, |solver| solver.remaining_candidates().len())
+}
+
+/// Frees a solver. Null is ignored.
+///
+/// # Safety
+///
+/// `solver` must come from [`wordle_solver_new`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_free(solver: *mut SolverSession) {
+  if !solver.is_null() {
+    drop(Box::from_raw(solver));
+  }
+}
+
+/// Frees a string returned by the solver. Null is ignored.
+///
+/// # Safety
+///
+/// `text` must come from [`wordle_solver_top_suggestion`] and not be freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_free_string(text: *mut c_char) {
+  if !text.is_null() {
+    drop(CString::from_raw(text));
+  }
+}
@@ -0,0 +1,112 @@
+//! Word frequencies, read from a file with one `word count` pair per line.
+
+use std::collections::{HashMap, HashSet};
+use wordle_solver::arena::WordArena;
+use wordle_solver::priors::{self, PriorModel};
+use wordle_solver::solver::CandidateWeights;
+
+use crate::read_lines;
+
+/// How quickly [`Frequencies::rank_weights`] drops from the common words to the rare ones
+const RANK_STEEPNESS: f64 = 10.0;
+
+/// How common each word is. Words missing from the file have a frequency of 0.
+#[derive(Debug, Clone, Default)]
+pub struct Frequencies(HashMap<String, f64>);
+
+impl Frequencies {
+  pub fn read(file: &str) -> std::io::Result<Frequencies> {
+    let mut frequencies = HashMap::new();
+    for line in read_lines(file)? {
+      let line = line?;
+      let mut fields = line.split_whitespace();
+      if let (Some(word), Some(count)) = (fields.next(), fields.next()) {
+        if let Ok(count) = count.parse::<f64>() {
+          frequencies.insert(word.to_lowercase(), count);
+        }
+      }
+    }
+
+    Ok(Frequencies(frequencies))
+  }
+
+  /// The frequencies of the given words, the others having none
+  #[cfg(test)]
+  pub fn from_counts(counts: &[(&str, f64)]) -> Frequencies {
+    Frequencies(
+      counts
+        .iter()
+        .map(|&(word, count)| (word.to_string(), count))
+        .collect(),
+    )
+  }
+
+  pub fn get(&self, word: &str) -> f64 {
+    self.0.get(word).copied().unwrap_or(0.0)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The most common words, `percent` percent of the given words. Words as common as the last one
+  /// kept are kept too.
+  pub fn common_words(&self, words: &WordArena, percent: f64) -> HashSet<String> {
+    let mut frequencies: Vec<f64> = words.iter().map(|word| self.get(word)).collect();
+    frequencies.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    let kept = ((words.len() as f64 * percent / 100.0).ceil() as usize).clamp(1, words.len());
+    let cutoff = frequencies[kept - 1];
+    words
+      .iter()
+      .filter(|word| self.get(word) >= cutoff)
+      .map(str::to_string)
+      .collect()
+  }
+
+  /// The frequency tier of each word, ranked among the given words
+  pub fn tiers(&self, words: &WordArena) -> HashMap<String, usize> {
+    let frequencies: Vec<f64> = words.iter().map(|word| self.get(word)).collect();
+    words
+      .iter()
+      .map(str::to_string)
+      .zip(priors::frequency_tiers(&frequencies))
+      .collect()
+  }
+
+  /// The weight the prior model gives to each of the words. Without frequencies, only the
+  /// features of the words count.
+  pub fn candidate_weights(&self, words: &WordArena, model: &PriorModel) -> CandidateWeights {
+    if self.is_empty() {
+      return words
+        .iter()
+        .map(|word| (crate::to_word(word), model.feature_weight(word)))
+        .collect();
+    }
+
+    self
+      .tiers(words)
+      .into_iter()
+      .map(|(word, tier)| (crate::to_word(&word), model.weight(&word, tier)))
+      .collect()
+  }
+
+  /// Weights going smoothly from 1 for the most common of the given words to nearly 0 for the
+  /// rarest, a sigmoid of their rank by frequency. Half the words are above 0.5, so common words
+  /// count about the same whatever their exact frequency. Words as common share their rank.
+  pub fn rank_weights(&self, words: &WordArena) -> CandidateWeights {
+    let mut frequencies: Vec<f64> = words.iter().map(|word| self.get(word)).collect();
+    frequencies.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    words
+      .iter()
+      .map(|word| {
+        let frequency = self.get(word);
+        let rank = frequencies.partition_point(|&other| other > frequency);
+        let position = rank as f64 / words.len() as f64;
+        let weight = 1.0 / (1.0 + (RANK_STEEPNESS * (position - 0.5)).exp());
+        (crate::to_word(word), weight)
+      })
+      .collect()
+  }
+}
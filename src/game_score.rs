@@ -0,0 +1,404 @@
+//! Scoring guesses by how the game ends: the turn the answer is found on, or failing to find it
+//! within the six guesses of Wordle.
+//!
+//! [`Optimal`](crate::optimal::Optimal) finds the answer in the fewest guesses on average, however
+//! many it takes. Players keep score differently: 1 to 6 by the turn the answer is found on, and
+//! `X` when it isn't, often counted as 7. [`GameScore`] searches the game tree like `Optimal`, each
+//! turn the answer can be found on and failing having a [`Costs`], and scores a guess by minus the
+//! expected cost of the game.
+//!
+//! The cost depends on the turn, which only the programs playing the game know, through
+//! [`Strategy::at_turn`]. Scored without it, the game is taken to be on its first turn.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::bounds::guess_bounds;
+use crate::pattern::{Pattern, PATTERN_COUNT};
+use crate::solver::{DictWord, Strategy, WORD_LENGTH};
+use crate::transposition::{Bound, Transpositions};
+
+/// Guesses Wordle allows
+pub const MAX_GUESSES: usize = 6;
+
+/// What failing costs with [`GameScore::fewest_failures`], in guesses. One answer more out of a
+/// hundred thousand failing outweighs any number of guesses.
+pub const FAILURE_COST: f64 = 1e6;
+
+/// What a game costs by how it ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Costs {
+  /// Finding the answer on each turn, from the first
+  pub solved: [f64; MAX_GUESSES],
+  /// Not finding it within [`MAX_GUESSES`]
+  pub failed: f64,
+}
+
+impl Costs {
+  /// The Wordle score: the turn the answer is found on, `failed` when it isn't
+  pub fn score(failed: f64) -> Costs {
+    let mut solved = [0.0; MAX_GUESSES];
+    for (turn, cost) in solved.iter_mut().enumerate() {
+      *cost = (turn + 1) as f64;
+    }
+    Costs { solved, failed }
+  }
+
+  /// Whether the answer is found by `turns`: nothing when it is, 1 when it isn't
+  pub fn within(turns: usize) -> Costs {
+    let mut solved = [1.0; MAX_GUESSES];
+    for cost in solved.iter_mut().take(turns) {
+      *cost = 0.0;
+    }
+    Costs {
+      solved,
+      failed: 1.0,
+    }
+  }
+
+  /// Cost of finding the answer on `turn`, counting from 1, or of failing past the last turn
+  fn at(&self, turn: usize) -> f64 {
+    match turn {
+      1..=MAX_GUESSES => self.solved[turn - 1],
+      _ => self.failed,
+    }
+  }
+}
+
+/// A set of candidates and the turn they are played from
+type Position = (Vec<DictWord>, usize);
+
+/// The game tree search shared by the turns of a [`GameScore`]
+struct Search {
+  guesses: Vec<DictWord>,
+  costs: Costs,
+  /// How many guesses to try at each turn, all of them when `None`
+  breadth: Option<usize>,
+  /// Expected cost of the candidate sets searched so far, by the turn they were searched from
+  known: Transpositions,
+  /// The expected cost of the candidate sets being scored, and the bound of the last guess the
+  /// search tries for them
+  scored: Mutex<HashMap<Position, (f64, f64)>>,
+}
+
+impl Search {
+  fn new(guesses: Vec<DictWord>, costs: Costs, breadth: Option<usize>) -> Search {
+    Search {
+      guesses,
+      costs,
+      breadth,
+      known: Transpositions::new(),
+      scored: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Lowest expected cost of finding any of `candidates` equally likely words from `turn` on,
+  /// filling the game tree from the top as [`guess_bounds`](crate::bounds::guess_bounds) does
+  fn lower_bound(&self, candidates: usize, turn: usize) -> f64 {
+    if candidates == 0 {
+      return 0.0;
+    }
+
+    let mut left = candidates;
+    let mut found_at_turn = 1usize;
+    let mut turn = turn;
+    let mut total = 0.0;
+    while left > 0 {
+      let found = if turn > MAX_GUESSES {
+        left
+      } else {
+        found_at_turn.min(left)
+      };
+      total += found as f64 * self.costs.at(turn);
+      left -= found;
+      found_at_turn = found_at_turn.saturating_mul(PATTERN_COUNT - 1);
+      turn += 1;
+    }
+    total / candidates as f64
+  }
+
+  /// Lowest expected cost from `turn` on, or some cost at least `limit` when it isn't below
+  /// `limit`
+  fn search(&self, candidates: &[DictWord], turn: usize, limit: f64) -> f64 {
+    let bound = self.lower_bound(candidates.len(), turn);
+    // From the turn finding the answer costs as much as failing, every guess costs the same
+    let settled = self.costs.at(turn) >= self.costs.failed;
+    if candidates.len() <= 1 || settled || bound >= limit {
+      return bound;
+    }
+    let key = Transpositions::key(candidates, turn);
+    match self.known.get(key) {
+      Some(Bound::Exact(known)) => return known,
+      Some(Bound::AtLeast(known)) if known >= limit => return known,
+      _ => {}
+    }
+
+    // A candidate telling all the others apart reaches the lower bound
+    let splitting = candidates.iter().any(|guess| {
+      let mut codes: Vec<_> = candidates
+        .iter()
+        .map(|word| guess.pattern_code(word))
+        .collect();
+      codes.sort_unstable();
+      codes.windows(2).all(|pair| pair[0] != pair[1])
+    });
+    if splitting {
+      return self.known.exact(key, bound);
+    }
+
+    let mut best = None;
+    let mut limit = limit;
+    for (bound, guess) in self.options(candidates, turn) {
+      if bound >= limit {
+        break;
+      }
+      if let Ok(expected) = self.play(guess, candidates, turn, bound, limit) {
+        best = Some(expected);
+        limit = expected;
+      }
+    }
+
+    match best {
+      Some(best) => self.known.exact(key, best),
+      None => self.known.at_least(key, limit),
+    }
+  }
+
+  /// The guesses to try for `candidates` on `turn` with their bounds, most promising first. Many
+  /// guesses can share a bound when few turns cost anything, those with the lowest expected
+  /// number of guesses come first then.
+  fn options(&self, candidates: &[DictWord], turn: usize) -> Vec<(f64, &DictWord)> {
+    let mut options: Vec<(f64, f64, &DictWord)> = self
+      .guesses
+      .iter()
+      .filter_map(|guess| {
+        let counts = bucket_counts(guess, candidates)?;
+        let guesses = counts
+          .iter()
+          .map(|&count| count as f64 * guess_bounds(count, WORD_LENGTH).expected)
+          .sum();
+        Some((self.bound(&counts, candidates.len(), turn), guesses, guess))
+      })
+      .collect();
+    options.sort_by(|(a, a_guesses, _), (b, b_guesses, _)| {
+      a.partial_cmp(b)
+        .unwrap()
+        .then(a_guesses.partial_cmp(b_guesses).unwrap())
+    });
+    if let Some(breadth) = self.breadth {
+      options.truncate(breadth);
+    }
+    options
+      .into_iter()
+      .map(|(bound, _, guess)| (bound, guess))
+      .collect()
+  }
+
+  /// Lowest expected cost playing `guess` on `turn`, from the sizes of the buckets it leaves.
+  /// `None` when it leaves all the candidates together.
+  fn guess_bound(&self, guess: &DictWord, candidates: &[DictWord], turn: usize) -> Option<f64> {
+    let counts = bucket_counts(guess, candidates)?;
+    Some(self.bound(&counts, candidates.len(), turn))
+  }
+
+  /// Lowest expected cost of a guess on `turn` leaving buckets of `counts` candidates
+  fn bound(&self, counts: &[usize; PATTERN_COUNT], candidates: usize, turn: usize) -> f64 {
+    let solved = Pattern::SOLVED.index() as usize;
+    let total = candidates as f64;
+    counts
+      .iter()
+      .enumerate()
+      .filter(|&(_, &count)| count > 0)
+      .map(|(code, &count)| match code == solved {
+        true => self.costs.at(turn) / total,
+        false => count as f64 / total * self.lower_bound(count, turn + 1),
+      })
+      .sum()
+  }
+
+  /// Expected cost playing `guess` on `turn`, starting from its `bound`. Once it can't get below
+  /// `limit`, the error has how much it costs at least.
+  fn play(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    turn: usize,
+    bound: f64,
+    limit: f64,
+  ) -> Result<f64, f64> {
+    let mut words: Vec<(usize, DictWord)> = candidates
+      .iter()
+      .map(|word| (guess.pattern_code(word), *word))
+      .collect();
+    words.sort_by_key(|&(code, _)| code);
+
+    let solved = Pattern::SOLVED.index() as usize;
+    let total = candidates.len() as f64;
+    let mut expected = bound;
+    let mut start = 0;
+    while start < words.len() {
+      let code = words[start].0;
+      let end = start
+        + words[start..]
+          .iter()
+          .take_while(|(c, _)| *c == code)
+          .count();
+      if code != solved {
+        let bucket: Vec<DictWord> = words[start..end].iter().map(|&(_, word)| word).collect();
+        let share = bucket.len() as f64 / total;
+        let others = expected - share * self.lower_bound(bucket.len(), turn + 1);
+        let rest = self.search(&bucket, turn + 1, (limit - others) / share);
+        expected = others + share * rest;
+        if expected >= limit {
+          return Err(expected);
+        }
+      }
+      start = end;
+    }
+
+    Ok(expected)
+  }
+}
+
+/// How many of `candidates` get each pattern from `guess`. `None` when they all get the same one
+/// and `guess` isn't among them, telling nothing.
+fn bucket_counts(guess: &DictWord, candidates: &[DictWord]) -> Option<[usize; PATTERN_COUNT]> {
+  let mut counts = [0usize; PATTERN_COUNT];
+  for word in candidates {
+    counts[guess.pattern_code(word)] += 1;
+  }
+  if counts.contains(&candidates.len()) && !candidates.contains(guess) {
+    return None;
+  }
+  Some(counts)
+}
+
+/// Scores guesses on one turn of a [`GameScore`]
+struct TurnScore {
+  search: Arc<Search>,
+  turn: usize,
+  name: String,
+}
+
+/// The best guesses get their exact expected cost, the others one they cost at least, as
+/// [`Optimal`](crate::optimal::Optimal) does
+impl Strategy for TurnScore {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    let search = &self.search;
+    let (best, last_tried) = {
+      let mut scored = search.scored.lock().unwrap();
+      *scored
+        .entry((candidates.to_vec(), self.turn))
+        .or_insert_with(|| {
+          let last_tried = search
+            .options(candidates, self.turn)
+            .last()
+            .map_or(0.0, |&(bound, _)| bound);
+          let _search =
+            tracing::debug_span!("search", turn = self.turn, candidates = candidates.len())
+              .entered();
+          let best = search.search(candidates, self.turn, f64::INFINITY);
+          tracing::debug!(cost = best, "searched the game tree");
+          (best, last_tried)
+        })
+    };
+    let expected = match search.guess_bound(guess, candidates, self.turn) {
+      Some(bound) if bound > best => bound,
+      Some(bound) if bound <= last_tried => {
+        // Just above the best, to tell the guesses tying with it from the worse ones
+        let limit = best + 1e-9;
+        match search.play(guess, candidates, self.turn, bound, limit) {
+          Ok(expected) | Err(expected) => expected,
+        }
+      }
+      // Telling nothing, it costs at least what the candidates cost from the next turn
+      None => (best + 1e-9).max(search.lower_bound(candidates.len(), self.turn + 1)),
+      // Those not tried count as just worse than the best, like the guesses found no better
+      Some(_) => best + 1e-9,
+    };
+    -expected
+  }
+
+  fn counts_wins(&self) -> bool {
+    true
+  }
+
+  fn name(&self) -> String {
+    self.name.clone()
+  }
+}
+
+/// Scores a guess by minus the expected cost of the game playing it and then the best guesses,
+/// see [`Costs`]. Past the last turn, guesses are scored as on the last turn.
+pub struct GameScore {
+  /// The strategy of each turn, from the first
+  turns: Vec<TurnScore>,
+}
+
+impl GameScore {
+  /// Searches with any of `allowed` as guesses for the lowest expected cost
+  pub fn new<S: AsRef<str>>(allowed: &[S], costs: Costs, name: &str) -> GameScore {
+    let guesses = allowed
+      .iter()
+      .filter_map(|word| DictWord::new(word.as_ref()))
+      .collect();
+    GameScore::with_search(Search::new(guesses, costs, None), name)
+  }
+
+  /// Minimizes the expected Wordle score, failing scoring `failed`
+  pub fn expected_score<S: AsRef<str>>(allowed: &[S], failed: f64) -> GameScore {
+    let name = format!("expected-score {}", failed);
+    GameScore::new(allowed, Costs::score(failed), &name)
+  }
+
+  /// Maximizes the chance of finding the answer by `turns`, whatever it takes after. Scores are
+  /// minus the chance of missing it.
+  pub fn within<S: AsRef<str>>(allowed: &[S], turns: usize) -> GameScore {
+    GameScore::new(allowed, Costs::within(turns), &format!("within {}", turns))
+  }
+
+  /// Minimizes the chance of not finding the answer within [`MAX_GUESSES`], then the expected
+  /// score. Scores are minus the expected score, failing scoring [`FAILURE_COST`].
+  pub fn fewest_failures<S: AsRef<str>>(allowed: &[S]) -> GameScore {
+    GameScore::new(allowed, Costs::score(FAILURE_COST), "fewest-failures")
+  }
+
+  /// Only tries the `breadth` most promising guesses at each turn
+  pub fn with_breadth(self, breadth: usize) -> GameScore {
+    let search = &self.turns[0].search;
+    let guesses = search.guesses.clone();
+    let name = self.turns[0].name.clone();
+    GameScore::with_search(Search::new(guesses, search.costs, Some(breadth)), &name)
+  }
+
+  fn with_search(search: Search, name: &str) -> GameScore {
+    let search = Arc::new(search);
+    let turns = (1..=MAX_GUESSES)
+      .map(|turn| TurnScore {
+        search: search.clone(),
+        turn,
+        name: name.to_string(),
+      })
+      .collect();
+    GameScore { turns }
+  }
+}
+
+impl Strategy for GameScore {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.turns[0].score(guess, candidates)
+  }
+
+  fn counts_wins(&self) -> bool {
+    true
+  }
+
+  fn name(&self) -> String {
+    self.turns[0].name()
+  }
+
+  fn at_turn(&self, turn: usize, _candidates: usize) -> Option<&dyn Strategy> {
+    let turn = turn.clamp(1, MAX_GUESSES);
+    Some(&self.turns[turn - 1])
+  }
+}
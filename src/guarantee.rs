@@ -0,0 +1,132 @@
+//! Staying within the guess limit: telling which guesses still find every answer in time.
+//!
+//! The strategies rank guesses by what they tell on average, and now and then the best of them
+//! leaves a group that can't be split in the turns left, like `_ight` with two guesses to go.
+//! [`Guarantee`] plays each group out in the worst case, with the guesses leaving the smallest
+//! largest group first, and stops at the first guess that makes it in time.
+//!
+//! The search is bounded: only a few guesses are tried at each turn. A guess it doesn't find a
+//! way for may still fit with guesses it didn't try, but those it finds a way for surely fit.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::bounds::guess_bounds;
+use crate::pattern::{Pattern, PATTERN_COUNT};
+use crate::solver::{DictWord, WORD_LENGTH};
+
+/// How many guesses are tried at each turn by default
+pub const DEFAULT_BREADTH: usize = 10;
+
+/// Finds the guesses solving every candidate within a number of turns
+pub struct Guarantee {
+  guesses: Vec<DictWord>,
+  /// How many guesses to try at each turn
+  breadth: usize,
+  /// Whether the candidate sets searched so far are solved within so many turns
+  known: Mutex<HashMap<(Vec<DictWord>, usize), bool>>,
+}
+
+impl Guarantee {
+  /// Searches with any of `allowed` as guesses, [`DEFAULT_BREADTH`] of them at each turn
+  pub fn new<S: AsRef<str>>(allowed: &[S]) -> Guarantee {
+    Guarantee {
+      guesses: allowed
+        .iter()
+        .filter_map(|word| DictWord::new(word.as_ref()))
+        .collect(),
+      breadth: DEFAULT_BREADTH,
+      known: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Tries the `breadth` guesses leaving the smallest largest groups at each turn
+  pub fn with_breadth(mut self, breadth: usize) -> Guarantee {
+    self.breadth = breadth.max(1);
+    self
+  }
+
+  /// Whether playing `guess` first finds any of `candidates` within `turns` guesses, `guess`
+  /// included
+  pub fn fits(&self, guess: &DictWord, candidates: &[DictWord], turns: usize) -> bool {
+    if turns == 0 {
+      return candidates.is_empty();
+    }
+
+    let mut groups: HashMap<usize, Vec<DictWord>> = HashMap::new();
+    for word in candidates {
+      groups
+        .entry(guess.pattern_code(word))
+        .or_default()
+        .push(*word);
+    }
+
+    let solved = Pattern::SOLVED.index() as usize;
+    groups
+      .iter()
+      .filter(|&(&code, _)| code != solved)
+      .all(|(_, group)| self.solvable(group, turns - 1))
+  }
+
+  /// A guess the search finds to be playable first for [`Guarantee::fits`], if any
+  pub fn find(&self, candidates: &[DictWord], turns: usize) -> Option<DictWord> {
+    // With as many turns as candidates, playing them one after the other always fits
+    let in_turn = if candidates.len() <= turns {
+      candidates
+    } else {
+      &[]
+    };
+    self
+      .options(candidates)
+      .into_iter()
+      .chain(in_turn.iter().copied())
+      .find(|guess| self.fits(guess, candidates, turns))
+  }
+
+  /// Whether some guesses find any of `candidates` within `turns` guesses
+  pub fn solvable(&self, candidates: &[DictWord], turns: usize) -> bool {
+    // Playing the candidates one after the other finds the answer in as many guesses
+    if candidates.len() <= turns {
+      return true;
+    }
+    if guess_bounds(candidates.len(), WORD_LENGTH).worst > turns {
+      return false;
+    }
+    let key = (candidates.to_vec(), turns);
+    if let Some(&known) = self.known.lock().unwrap().get(&key) {
+      return known;
+    }
+
+    let solvable = self
+      .options(candidates)
+      .iter()
+      .any(|guess| self.fits(guess, candidates, turns));
+    self.known.lock().unwrap().insert(key, solvable);
+    solvable
+  }
+
+  /// The guesses to try for `candidates`, leaving the smallest largest groups first and the
+  /// candidates first among equals. Guesses leaving all the candidates together are left out.
+  fn options(&self, candidates: &[DictWord]) -> Vec<DictWord> {
+    let mut options: Vec<(usize, bool, DictWord)> = self
+      .guesses
+      .iter()
+      .filter_map(|guess| {
+        let mut counts = [0usize; PATTERN_COUNT];
+        for word in candidates {
+          counts[guess.pattern_code(word)] += 1;
+        }
+        let largest = counts.iter().copied().max().unwrap_or(0);
+        let candidate = candidates.contains(guess);
+        (largest < candidates.len() || candidate).then_some((largest, !candidate, *guess))
+      })
+      .collect();
+    options.sort_by_key(|&(largest, not_candidate, _)| (largest, not_candidate));
+
+    options
+      .into_iter()
+      .take(self.breadth)
+      .map(|(_, _, guess)| guess)
+      .collect()
+  }
+}
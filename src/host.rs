@@ -0,0 +1,72 @@
+//! The `host` subcommand: the program keeps a secret answer and colors the guesses typed, playing
+//! the game for a human instead of solving it.
+//!
+//! The evil host plays like Absurdle: it picks no answer, marks each guess so as to keep as many
+//! answers possible as it can, and only settles on one when the guesses leave no other.
+
+use std::io::{self, BufRead};
+use wordle_solver::adversarial::absurdle_feedback;
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::mark::Mark;
+use wordle_solver::random::Rng;
+
+use crate::practice;
+use crate::tally::Tally;
+use crate::theme::Theme;
+use crate::{reduce_dictionary, DictString};
+
+/// Hosts a game with an answer drawn from `answers` with `rng`, or an evil game keeping all the
+/// answers it can. Guesses must be words of `dictionary`, `max_guesses` of them per game. With
+/// `looping` new games follow until the input ends, their results tallied.
+pub fn run(
+  dictionary: WordArena,
+  answers: WordArena,
+  max_guesses: usize,
+  evil: bool,
+  looping: bool,
+  theme: &Theme,
+  mut rng: Rng,
+) {
+  let answers: Vec<&DictString> = answers.iter().collect();
+  if answers.is_empty() {
+    return;
+  }
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  let show = |guess: &str, marks: &[Mark]| theme.paint(guess, marks);
+  let mut tally = Tally::default();
+
+  loop {
+    println!("Guess the word in {} tries:", max_guesses);
+    let mut candidates = answers.clone();
+    let result = if evil {
+      let mark = |guess: &str| {
+        let marks = absurdle_feedback(guess, &candidates);
+        candidates = reduce_dictionary(guess, &marks, &candidates);
+        marks
+      };
+      practice::play(mark, &dictionary, max_guesses, show, &mut lines)
+    } else {
+      candidates = vec![*rng.choose(&answers).unwrap()];
+      let mark = |guess: &str| engine::compute_bucket(guess, candidates[0]);
+      practice::play(mark, &dictionary, max_guesses, show, &mut lines)
+    };
+    match result {
+      Some((tries, true)) => {
+        println!("Solved in {}!", tries);
+        tally.record(Some(tries));
+      }
+      // The evil host only now picks the answer among those the marks left
+      Some((_, false)) => {
+        println!("The word was {:?}", rng.choose(&candidates).unwrap());
+        tally.record(None);
+      }
+      None => return,
+    }
+    if !looping {
+      return;
+    }
+    tally.print();
+  }
+}
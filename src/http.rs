@@ -0,0 +1,112 @@
+//! Just enough HTTP/1.1 for the built-in servers: one request per connection, no keep-alive.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Largest request body read, larger ones are answered with 413
+const MAX_BODY: usize = 64 * 1024;
+
+/// How long a client may take to send each part of its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Request {
+  pub method: String,
+  pub path: String,
+  pub body: String,
+}
+
+/// Reads a request, answering 413 and failing when its body is over [`MAX_BODY`]
+pub fn read_request(stream: &TcpStream) -> io::Result<Request> {
+  stream.set_read_timeout(Some(READ_TIMEOUT))?;
+  let mut reader = BufReader::new(stream);
+
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  let mut parts = request_line.split_whitespace();
+  let method = parts.next().unwrap_or_default().to_string();
+  let path = parts.next().unwrap_or_default().to_string();
+
+  let mut content_length = 0;
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+      break;
+    }
+    if let Some((name, value)) = header.split_once(':') {
+      if name.trim().eq_ignore_ascii_case("content-length") {
+        content_length = value.trim().parse().unwrap_or(0);
+      }
+    }
+  }
+
+  if content_length > MAX_BODY {
+    respond(
+      stream,
+      "413 Payload Too Large",
+      "text/plain",
+      "Request body too large",
+    )?;
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("request body of {} bytes is too large", content_length),
+    ));
+  }
+
+  let mut body = vec![0; content_length];
+  reader.read_exact(&mut body)?;
+
+  Ok(Request {
+    method,
+    path,
+    body: String::from_utf8_lossy(&body).into_owned(),
+  })
+}
+
+pub fn respond(stream: &TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+  respond_bytes(stream, status, content_type, body.as_bytes())
+}
+
+/// Like [`respond`], for bodies that aren't text such as WebAssembly modules
+pub fn respond_bytes(
+  mut stream: &TcpStream,
+  status: &str,
+  content_type: &str,
+  body: &[u8],
+) -> io::Result<()> {
+  write!(
+    stream,
+    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+    status,
+    content_type,
+    body.len()
+  )?;
+  stream.write_all(body)?;
+  stream.flush()
+}
+
+/// Quotes and escapes a string for use in a JSON document
+pub fn json_string(value: &str) -> String {
+  let mut quoted = String::with_capacity(value.len() + 2);
+  quoted.push('"');
+  for c in value.chars() {
+    match c {
+      '"' => quoted.push_str("\\\""),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('"');
+  quoted
+}
+
+/// Formats a number for a JSON document. JSON has no infinities or NaN, those become `null`.
+pub fn json_number(value: f64) -> String {
+  if value.is_finite() {
+    value.to_string()
+  } else {
+    "null".to_string()
+  }
+}
@@ -0,0 +1,216 @@
+//! Games played by people, imported from CSV dumps, and how they compare with the solver's.
+//!
+//! The CSV file needs a header line naming an `answer` and a `guesses` column, the guesses of a
+//! game separated by spaces or semicolons. Other columns, like a puzzle number, are ignored.
+
+use std::collections::HashMap;
+use std::fs;
+use wordle_solver::engine;
+
+use crate::benchmark::{Player, MAX_TRIES};
+use crate::{probe, reduce_dictionary, top_suggestions, DictString, Strategy, WORD_LENGTH};
+
+/// Number of most played openers listed
+const SHOWN_OPENERS: usize = 10;
+
+/// A game played by a person
+pub struct HumanGame {
+  pub answer: String,
+  pub guesses: Vec<String>,
+}
+
+impl HumanGame {
+  pub fn solved(&self) -> bool {
+    self.guesses.last() == Some(&self.answer) && self.guesses.len() <= MAX_TRIES
+  }
+}
+
+/// Reads the games of a CSV file
+pub fn read(file: &str) -> Result<Vec<HumanGame>, String> {
+  let text = fs::read_to_string(file).map_err(|err| format!("{}: {}", file, err))?;
+  let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+  let cells = |line: &str| -> Vec<String> {
+    line
+      .split(',')
+      .map(|cell| cell.trim().trim_matches('"').to_lowercase())
+      .collect()
+  };
+  let header = cells(lines.next().unwrap_or(""));
+  let column = |name: &str| {
+    header
+      .iter()
+      .position(|cell| cell == name)
+      .ok_or_else(|| format!("{}: no {:?} column in the header", file, name))
+  };
+  let (answer_column, guesses_column) = (column("answer")?, column("guesses")?);
+
+  lines
+    .enumerate()
+    .map(|(index, line)| {
+      let row = cells(line);
+      let answer = row.get(answer_column).cloned().unwrap_or_default();
+      let guesses: Vec<String> = row
+        .get(guesses_column)
+        .map(|cell| {
+          cell
+            .split(|c: char| c == ';' || c.is_whitespace())
+            .filter(|guess| !guess.is_empty())
+            .map(str::to_string)
+            .collect()
+        })
+        .unwrap_or_default();
+
+      let lengths_ok = std::iter::once(&answer)
+        .chain(guesses.iter())
+        .all(|word| word.chars().count() == WORD_LENGTH);
+      if guesses.is_empty() || !lengths_ok {
+        return Err(format!(
+          "{} line {}: expected an answer and guesses of {} letters",
+          file,
+          index + 2,
+          WORD_LENGTH
+        ));
+      }
+      Ok(HumanGame { answer, guesses })
+    })
+    .collect()
+}
+
+/// Bits gathered over the guesses played at one turn of the games
+#[derive(Default)]
+struct TurnStats {
+  guesses: usize,
+  candidates: usize,
+  bits: f64,
+  best_bits: f64,
+}
+
+fn print_distribution(title: &str, counts: impl Iterator<Item = Option<usize>>) {
+  let mut distribution = [0; MAX_TRIES + 1];
+  let mut games = 0;
+  for count in counts {
+    games += 1;
+    distribution[count.map_or(MAX_TRIES, |count| count - 1)] += 1;
+  }
+
+  let columns: Vec<String> = distribution
+    .iter()
+    .enumerate()
+    .map(|(index, count)| {
+      let label = if index == MAX_TRIES {
+        "X".to_string()
+      } else {
+        (index + 1).to_string()
+      };
+      format!(
+        "{}: {:>5.1}%",
+        label,
+        100.0 * *count as f64 / games.max(1) as f64
+      )
+    })
+    .collect();
+  println!("{:<7} {}", title, columns.join("  "));
+}
+
+/// Compares the games with the solver's on the same answers: the distribution of guesses needed,
+/// then for each turn how many bits people got out of their guesses next to the best guess
+/// available, and the openers they play most
+pub fn compare(
+  dictionary: &Vec<&DictString>,
+  answers: Vec<&DictString>,
+  games: &[HumanGame],
+  strategy: &dyn Strategy,
+) -> Result<(), String> {
+  let mut player = Player::new(dictionary, answers.clone(), strategy, None);
+  let mut solver_guesses: HashMap<&str, Option<usize>> = HashMap::new();
+  for game in games {
+    let answer = *answers
+      .iter()
+      .find(|&&word| word == game.answer)
+      .ok_or_else(|| format!("The answer {:?} is not in the answer list", game.answer))?;
+    solver_guesses.entry(answer).or_insert_with(|| {
+      let played = player.play(answer);
+      Some(played.guesses.len()).filter(|_| played.solved())
+    });
+  }
+
+  println!("Guesses needed on {} games:", games.len());
+  print_distribution(
+    "People",
+    games
+      .iter()
+      .map(|game| Some(game.guesses.len()).filter(|_| game.solved())),
+  );
+  print_distribution(
+    "Solver",
+    games
+      .iter()
+      .map(|game| solver_guesses[game.answer.as_str()]),
+  );
+
+  let mut best_bits: HashMap<Vec<&DictString>, f64> = HashMap::new();
+  let mut turns: Vec<TurnStats> = vec![];
+  let mut openers: HashMap<&str, (usize, f64)> = HashMap::new();
+  for game in games {
+    let mut candidates = answers.clone();
+    for (turn, guess) in game.guesses.iter().enumerate() {
+      if candidates.len() <= 1 {
+        break;
+      }
+
+      let best = *best_bits
+        .entry(candidates.clone())
+        .or_insert_with(|| top_suggestions(dictionary, &candidates, strategy, 1).0[0].1);
+      let bits = probe::evaluate(guess, &candidates, strategy).unwrap();
+
+      if turns.len() <= turn {
+        turns.resize_with(turn + 1, TurnStats::default);
+      }
+      let stats = &mut turns[turn];
+      stats.guesses += 1;
+      stats.candidates += candidates.len();
+      stats.bits += bits;
+      stats.best_bits += best;
+      if turn == 0 {
+        let opener = openers.entry(guess).or_insert((0, bits));
+        opener.0 += 1;
+      }
+
+      let marks = engine::compute_bucket(guess, &game.answer);
+      candidates = reduce_dictionary(guess, &marks, &candidates);
+    }
+  }
+
+  println!("Turn  Guesses  Candidates  Bits  Best  Lost");
+  for (turn, stats) in turns.iter().enumerate() {
+    let count = stats.guesses as f64;
+    println!(
+      "{:>4}  {:>7}  {:>10.1}  {:>4.2}  {:>4.2}  {:>4.2}",
+      turn + 1,
+      stats.guesses,
+      stats.candidates as f64 / count,
+      stats.bits / count,
+      stats.best_bits / count,
+      (stats.best_bits - stats.bits) / count
+    );
+  }
+
+  let mut openers: Vec<(&str, (usize, f64))> = openers.into_iter().collect();
+  openers.sort_by(|a, b| (b.1).0.cmp(&(a.1).0).then_with(|| a.0.cmp(b.0)));
+  let opening_best = turns
+    .first()
+    .map_or(0.0, |stats| stats.best_bits / stats.guesses as f64);
+  println!("Most played openers:");
+  for (opener, (count, bits)) in openers.iter().take(SHOWN_OPENERS) {
+    println!(
+      "  {}  {:>5} games  {:.2} bits, {:.2} lost",
+      opener,
+      count,
+      bits,
+      opening_best - bits
+    );
+  }
+
+  Ok(())
+}
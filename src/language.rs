@@ -0,0 +1,161 @@
+//! How localized Wordle clones treat letters, chosen with `--language`.
+//!
+//! Clones usually fold accented letters into plain ones (é is played and marked as e), spell some
+//! letters with two tiles (ß as ss) and keep others distinct (ñ in Spanish). A rule file has one
+//! rule per line, `#` starting a comment:
+//!
+//! ```text
+//! alphabet abcdefghijklmnñopqrstuvwxyz
+//! fold á a
+//! fold ß ss
+//! ```
+//!
+//! Folds are applied in order to dictionary words and typed guesses. Words with letters outside
+//! the alphabet afterwards are dropped from the dictionaries.
+
+use std::fs;
+use std::str::FromStr;
+
+const ENGLISH_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Language {
+  /// Letters of the game, in alphabetical order
+  alphabet: Vec<char>,
+  /// Text replaced by other text, e.g. an accented letter by the plain one
+  folds: Vec<(String, String)>,
+}
+
+impl Language {
+  pub fn english() -> Language {
+    Language {
+      alphabet: ENGLISH_ALPHABET.chars().collect(),
+      folds: vec![],
+    }
+  }
+
+  fn with_folds(alphabet: &str, folds: &[(&str, &str)]) -> Language {
+    Language {
+      alphabet: alphabet.chars().collect(),
+      folds: folds
+        .iter()
+        .map(|&(from, to)| (from.to_string(), to.to_string()))
+        .collect(),
+    }
+  }
+
+  /// Ñ is a letter of its own, accents are dropped
+  pub fn spanish() -> Language {
+    Language::with_folds(
+      "abcdefghijklmnñopqrstuvwxyz",
+      &[
+        ("á", "a"),
+        ("é", "e"),
+        ("í", "i"),
+        ("ó", "o"),
+        ("ú", "u"),
+        ("ü", "u"),
+      ],
+    )
+  }
+
+  /// Umlauts are letters of their own, ß is spelled ss
+  pub fn german() -> Language {
+    Language::with_folds("abcdefghijklmnopqrstuvwxyzäöü", &[("ß", "ss")])
+  }
+
+  /// Accents are dropped and ligatures spelled out
+  pub fn french() -> Language {
+    Language::with_folds(
+      ENGLISH_ALPHABET,
+      &[
+        ("à", "a"),
+        ("â", "a"),
+        ("æ", "ae"),
+        ("ç", "c"),
+        ("é", "e"),
+        ("è", "e"),
+        ("ê", "e"),
+        ("ë", "e"),
+        ("î", "i"),
+        ("ï", "i"),
+        ("ô", "o"),
+        ("œ", "oe"),
+        ("ù", "u"),
+        ("û", "u"),
+        ("ü", "u"),
+        ("ÿ", "y"),
+      ],
+    )
+  }
+
+  /// The digits of Primel, where the words are numbers
+  pub fn digits() -> Language {
+    Language::with_folds("0123456789", &[])
+  }
+
+  /// The built-in rules of this language code (en, es, de or fr), or else the rules in this file
+  pub fn load(name: &str) -> Result<Language, String> {
+    match name {
+      "en" => Ok(Language::english()),
+      "es" => Ok(Language::spanish()),
+      "de" => Ok(Language::german()),
+      "fr" => Ok(Language::french()),
+      file => {
+        let text = fs::read_to_string(file).map_err(|err| {
+          format!(
+            "unknown language {:?}, expected en, es, de, fr or a rule file ({})",
+            file, err
+          )
+        })?;
+        text.parse().map_err(|err| format!("{}: {}", file, err))
+      }
+    }
+  }
+
+  /// The word as the game spells it: lowercase, with the folds applied
+  pub fn fold(&self, word: &str) -> String {
+    self
+      .folds
+      .iter()
+      .fold(word.to_lowercase(), |word, (from, to)| {
+        word.replace(from, to)
+      })
+  }
+
+  /// Whether all the letters of `word` are in the alphabet
+  pub fn spells(&self, word: &str) -> bool {
+    word.chars().all(|letter| self.alphabet.contains(&letter))
+  }
+
+  pub fn alphabet(&self) -> &[char] {
+    &self.alphabet
+  }
+}
+
+impl FromStr for Language {
+  type Err = String;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    let mut language = Language::english();
+    for line in text.lines() {
+      let line = line.split('#').next().unwrap_or("").trim();
+      match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [] => {}
+        ["alphabet", letters] => language.alphabet = letters.to_lowercase().chars().collect(),
+        ["fold", from, to] => language
+          .folds
+          .push((from.to_lowercase(), to.to_lowercase())),
+        // Without a replacement, the text is removed, like the dot of the Catalan l·l
+        ["fold", from] => language.folds.push((from.to_lowercase(), String::new())),
+        _ => {
+          return Err(format!(
+            "expected `alphabet <letters>` or `fold <from> [to]`, got {:?}",
+            line
+          ))
+        }
+      }
+    }
+    Ok(language)
+  }
+}
@@ -0,0 +1,60 @@
+//! The `learn-priors` subcommand: fits a prior model to the answers of past games.
+
+use std::collections::HashMap;
+use std::fs;
+use wordle_solver::arena::WordArena;
+use wordle_solver::priors::PriorModel;
+
+use crate::frequency::Frequencies;
+use crate::language::Language;
+use crate::{practice, read_dict};
+
+/// Fits the frequency tier and feature weights to the answers found in practice stats files (`logs`) and
+/// answer lists (`answers`), then writes the model to `out`
+pub fn run(
+  answer_dictionary: &WordArena,
+  frequencies: &Frequencies,
+  logs: &[String],
+  answers: &[String],
+  out: &str,
+  language: &Language,
+) -> Result<(), String> {
+  if frequencies.is_empty() {
+    return Err("learn-priors needs word frequencies, see --frequencies".to_string());
+  }
+
+  let tiers: HashMap<String, usize> = frequencies.tiers(answer_dictionary);
+  let mut past_answers: Vec<String> = logs
+    .iter()
+    .flat_map(|log| practice::read_records(log))
+    .map(|record| record.word)
+    .collect();
+  for file in answers {
+    let words = read_dict(file, language).map_err(|err| err.to_string())?;
+    past_answers.extend(words.iter().map(str::to_string));
+  }
+
+  let answer_tiers: Vec<(&str, usize)> = past_answers
+    .iter()
+    .filter_map(|word| tiers.get(word).map(|&tier| (word.as_str(), tier)))
+    .collect();
+  if answer_tiers.is_empty() {
+    return Err("No answers from the reduced guess dictionary found in the logs".to_string());
+  }
+
+  let word_tiers: Vec<(&str, usize)> = tiers
+    .iter()
+    .map(|(word, &tier)| (word.as_str(), tier))
+    .collect();
+  let model = PriorModel::fit(&answer_tiers, &word_tiers);
+  fs::write(out, model.to_string()).map_err(|err| format!("Can't write {}: {}", out, err))?;
+
+  print!("{}", model);
+  println!(
+    "Learned from {} answers ({} not in the reduced guess dictionary), saved to {}",
+    answer_tiers.len(),
+    past_answers.len() - answer_tiers.len(),
+    out
+  );
+  Ok(())
+}
@@ -0,0 +1,284 @@
+//! Wordle with words of other lengths than [`WORD_LENGTH`], from `--length` or a dictionary of
+//! such words. The words are decoded to a [`Word`] of the length, the game being compiled for each
+//! length [`crate::play_length`] plays, and scored with the strategies that only look at the
+//! groups the candidates are split into or at letter tables, see [`get_word_suggestions`].
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{self, BufRead};
+use wordle_solver::arena::WordArena;
+use wordle_solver::mark::Mark;
+use wordle_solver::solver::{get_word_suggestions, Scored, Strategy, DEFAULT_LAMBDA, WORD_LENGTH};
+use wordle_solver::word::Word;
+
+use crate::benchmark::{self, Game};
+use crate::{best_attempt_with, DictString, SHOWN_GUESSES};
+
+/// What is played with the words
+pub enum Play<'p> {
+  /// The interactive game, each line being the guess followed by its marks
+  Interactive,
+  /// The game against this answer
+  Solve(&'p str),
+  /// The games against the answers of the sample, see [`benchmark::run`]
+  Benchmark(benchmark::Sample<'p>, benchmark::Options<'p>),
+}
+
+/// Decodes a dictionary word of `N` letters
+fn decode<const N: usize>(word: &str) -> Word<N> {
+  Word::new(word).expect("dictionary words have the same length")
+}
+
+/// The words of `dictionary` and of `candidates` ranked by `strategy`, see
+/// [`crate::get_suggestions`]
+fn suggestions<'a, const N: usize>(
+  dictionary: &[&'a DictString],
+  candidates: &[&'a DictString],
+  strategy: &dyn Strategy,
+) -> Result<(Scored<'a>, Scored<'a>), String> {
+  get_word_suggestions::<N>(dictionary, candidates, strategy).ok_or_else(|| {
+    format!(
+      "The {} strategy only plays words of {} letters",
+      strategy.name(),
+      WORD_LENGTH
+    )
+  })
+}
+
+/// The word to try among `candidates`, the best suggestion unless the best candidate scores as
+/// well with its chance to win right away
+fn attempt<'a, const N: usize>(
+  dictionary: &[&'a DictString],
+  candidates: &[&'a DictString],
+  strategy: &dyn Strategy,
+  tries: usize,
+  show: bool,
+) -> Result<&'a DictString, String> {
+  if let [answer] = candidates {
+    return Ok(answer);
+  }
+  let strategy = strategy
+    .at_turn(tries + 1, candidates.len())
+    .unwrap_or(strategy);
+  let (suggestions, guesses) = suggestions::<N>(dictionary, candidates, strategy)?;
+  if show {
+    println!(
+      "Suggestions: {:?} {:?}",
+      suggestions.len(),
+      suggestions.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+    println!(
+      "Guesses: {:?} {:?}",
+      guesses.len(),
+      guesses.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+  }
+  let lambda = if strategy.counts_wins() {
+    0.0
+  } else {
+    DEFAULT_LAMBDA
+  };
+  let chance = 1.0 / candidates.len() as f64;
+  best_attempt_with(&suggestions, &guesses, chance, lambda)
+    .ok_or_else(|| "No word to try".to_string())
+}
+
+/// The candidates giving the marks `guess` got
+fn reduce<'a, const N: usize>(
+  guess: &Word<N>,
+  marks: &[Mark; N],
+  candidates: &[&'a DictString],
+) -> Vec<&'a DictString> {
+  candidates
+    .iter()
+    .copied()
+    .filter(|&candidate| guess.marks(&decode(candidate)) == *marks)
+    .collect()
+}
+
+/// Reads a line of input into the guess and its marks
+fn read_turn<const N: usize>(line: &str) -> Result<(Word<N>, [Mark; N]), String> {
+  let expected = || {
+    format!(
+      "Expected a {} letter guess and its marks, `-` if the letter is not in the answer, `+` if \
+       it is elsewhere and `o` if it's right",
+      N
+    )
+  };
+  let (guess, marks) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+    &[guess, marks] => (guess.to_lowercase(), marks),
+    _ => return Err(expected()),
+  };
+  let guess = Word::new(&guess).ok_or_else(expected)?;
+  let marks = marks
+    .chars()
+    .map(Mark::try_from)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|err| err.to_string())?;
+  let marks = <[Mark; N]>::try_from(marks).map_err(|_| expected())?;
+  Ok((guess, marks))
+}
+
+/// Plays the game interactively, suggesting a guess each turn
+fn interactive<const N: usize>(
+  dictionary: &[&DictString],
+  answers: &[&DictString],
+  strategy: &dyn Strategy,
+) -> Result<(), String> {
+  let mut candidates = answers.to_vec();
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  for tries in 0.. {
+    match candidates.as_slice() {
+      [] => {
+        println!("No words match, check the marks");
+        return Ok(());
+      }
+      [answer] => {
+        println!("The answer is {:?}", answer);
+        return Ok(());
+      }
+      _ => println!("{} candidates left", candidates.len()),
+    }
+    let attempt = attempt::<N>(dictionary, &candidates, strategy, tries, true)?;
+    println!("Suggest you try {:?}", attempt);
+
+    let (guess, marks) = loop {
+      let line = match lines.next() {
+        Some(Ok(line)) => line,
+        _ => return Ok(()),
+      };
+      match read_turn::<N>(&line) {
+        Ok(turn) => break turn,
+        Err(err) => println!("{}", err),
+      }
+    };
+    if marks == [Mark::RightPosition; N] {
+      println!("Solved!");
+      return Ok(());
+    }
+    candidates = reduce(&guess, &marks, &candidates);
+  }
+  Ok(())
+}
+
+/// Plays against `answer`, trying `opener` first if given and then the word `pick` picks among
+/// the candidates left after the guesses played, the game shown when `show`
+fn solve<'a, const N: usize, F>(
+  answers: &[&'a DictString],
+  answer: &'a DictString,
+  opener: Option<&'a DictString>,
+  show: bool,
+  mut pick: F,
+) -> Result<Game<'a>, String>
+where
+  F: FnMut(&[&'a DictString], usize) -> Result<&'a DictString, String>,
+{
+  let target = decode::<N>(answer);
+  let mut candidates = answers.to_vec();
+  let mut guesses = vec![];
+  while guesses.len() < benchmark::GIVE_UP_TRIES && !candidates.is_empty() {
+    let attempt = match (guesses.is_empty(), opener) {
+      (true, Some(opener)) => opener,
+      _ => pick(&candidates, guesses.len())?,
+    };
+    guesses.push(attempt);
+    if show {
+      println!("Try {:?}, word {:?}", guesses.len(), attempt);
+    }
+    if attempt == answer {
+      if show {
+        println!("Actually guessed it!");
+      }
+      break;
+    }
+    let guess = decode::<N>(attempt);
+    let marks = guess.marks(&target);
+    if show {
+      let marks: String = marks.iter().map(|mark| mark.to_char()).collect();
+      println!("Outcome: {}", marks);
+    }
+    candidates = reduce(&guess, &marks, &candidates);
+  }
+  if show && candidates.is_empty() {
+    println!("Stumped, is {:?} in the dictionary?", answer);
+  }
+  Ok(Game { answer, guesses })
+}
+
+/// Plays `play` with the words of `N` letters of `dictionary`, the answers being those of
+/// `answers`
+pub fn run<const N: usize>(
+  play: &Play,
+  dictionary: &WordArena,
+  answers: &WordArena,
+  strategy: &dyn Strategy,
+) -> Result<(), String> {
+  let dictionary: Vec<&DictString> = dictionary.iter().collect();
+  let answers: Vec<&DictString> = answers.iter().collect();
+  match play {
+    Play::Interactive => interactive::<N>(&dictionary, &answers, strategy),
+    Play::Solve(answer) => {
+      solve::<N, _>(&answers, answer, None, true, |candidates, tries| {
+        attempt::<N>(&dictionary, candidates, strategy, tries, true)
+      })?;
+      Ok(())
+    }
+    Play::Benchmark(sample, options) => {
+      let opener = match options.opener {
+        Some(opener) => Some(
+          dictionary
+            .iter()
+            .copied()
+            .find(|word| *word == opener.to_lowercase())
+            .ok_or_else(|| format!("The opener {:?} is not in the dictionary", opener))?,
+        ),
+        None => None,
+      };
+      let targets = benchmark::draw(&answers, sample, &mut crate::rng(sample.seed));
+      // Many answers share the same first turns, the word picked for each is kept
+      let mut attempts: HashMap<(Vec<&DictString>, usize), &DictString> = HashMap::new();
+      let play = |answer| {
+        solve::<N, _>(&answers, answer, opener, false, |candidates, tries| {
+          let key = (candidates.to_vec(), tries);
+          if let Some(&word) = attempts.get(&key) {
+            return Ok(word);
+          }
+          let word = attempt::<N>(&dictionary, candidates, strategy, tries, false)?;
+          attempts.insert(key, word);
+          Ok(word)
+        })
+      };
+      benchmark::report(&targets, play, options)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use wordle_solver::solver::Average;
+
+  const WORDS: [&str; 6] = ["bare", "care", "core", "cure", "pure", "bore"];
+
+  #[test]
+  fn turns_have_the_length_of_the_game() {
+    let (guess, marks) = read_turn::<4>("CORE -ooo").unwrap();
+    assert_eq!(guess.to_string(), "core");
+    assert_eq!(reduce(&guess, &marks, &WORDS), vec!["bore"]);
+    assert!(read_turn::<4>("cores -+ooo").is_err());
+    assert!(read_turn::<4>("core -+o").is_err());
+  }
+
+  #[test]
+  fn every_answer_is_found() {
+    for answer in WORDS {
+      let game = solve::<4, _>(&WORDS, answer, None, false, |candidates, tries| {
+        attempt::<4>(&WORDS, candidates, &Average, tries, false)
+      })
+      .unwrap();
+      assert_eq!(game.guesses.last(), Some(&answer));
+      assert!(game.guesses.len() <= 3, "{:?}", game.guesses);
+    }
+  }
+}
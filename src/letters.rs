@@ -0,0 +1,61 @@
+//! What asking about a single letter would reveal.
+//!
+//! A guess tests several letters at once. Looking at them one by one shows which letters of a
+//! suggested guess carry most of its information: a letter found in half of the candidates splits
+//! them evenly, while one found in all or none of them says nothing.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::engine;
+
+/// The information about the candidates a single letter would give
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LetterInformation {
+  pub letter: char,
+  /// Number of candidates containing the letter
+  pub containing: usize,
+  /// Bits gained by learning whether the answer contains the letter
+  pub presence_bits: f64,
+  /// Bits gained by learning every position the letter is at in the answer
+  pub position_bits: f64,
+}
+
+/// The information of every letter found in the candidates, most informative positions first
+pub fn letter_information<S: AsRef<str>>(candidates: &[S]) -> Vec<LetterInformation> {
+  let mut letters: BTreeMap<char, BTreeMap<u32, u32>> = BTreeMap::new();
+
+  for candidate in candidates {
+    let mut positions: BTreeMap<char, u32> = BTreeMap::new();
+    for (position, letter) in candidate.as_ref().chars().enumerate() {
+      *positions.entry(letter).or_default() |= 1 << position;
+    }
+    for (letter, mask) in positions {
+      *letters.entry(letter).or_default().entry(mask).or_default() += 1;
+    }
+  }
+
+  let total = candidates.len() as u32;
+  let mut information: Vec<LetterInformation> = letters
+    .into_iter()
+    .map(|(letter, masks)| {
+      let containing: u32 = masks.values().sum();
+      let mut position_sizes: Vec<u32> = masks.into_values().collect();
+      position_sizes.push(total - containing);
+
+      LetterInformation {
+        letter,
+        containing: containing as usize,
+        presence_bits: engine::entropy(&[containing, total - containing]),
+        position_bits: engine::entropy(&position_sizes),
+      }
+    })
+    .collect();
+
+  information.sort_by(|a, b| {
+    b.position_bits
+      .partial_cmp(&a.position_bits)
+      .unwrap_or(core::cmp::Ordering::Equal)
+  });
+  information
+}
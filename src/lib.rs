@@ -0,0 +1,51 @@
+//! Wordle solving by means of set subdivision
+//!
+//! The [`engine`] and [`scoring`] modules only need `alloc`. Disable the default features and
+//! enable `libm` to use them on targets without the standard library. The `solver` module, which
+//! picks the guesses to play, needs `std`. The default `cli` feature only matters to the command
+//! line program, depend on the library with `default-features = false, features = ["std"]` to
+//! leave out its dependencies. The `wasm` feature adds bindings for running it in
+//! a browser, the `ffi` feature a C API.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod adversarial;
+pub mod arena;
+pub mod bounds;
+pub mod constraints;
+pub mod diff;
+pub mod difficulty;
+pub mod engine;
+pub mod feedback;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod game_score;
+#[cfg(feature = "std")]
+pub mod guarantee;
+pub mod heuristics;
+pub mod letters;
+#[cfg(feature = "std")]
+pub mod lookahead;
+pub mod mark;
+#[cfg(feature = "std")]
+pub mod optimal;
+pub mod pattern;
+pub mod priors;
+pub mod progress;
+pub mod random;
+pub mod scoring;
+#[cfg(feature = "std")]
+pub mod solver;
+#[cfg(feature = "std")]
+pub mod transposition;
+#[cfg(feature = "std")]
+pub mod tree;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod word;
+
+pub use engine::{bucket_sizes, entropy, partition};
@@ -0,0 +1,88 @@
+//! The `list` subcommand: words matching constraints given directly on the command line rather
+//! than through a history of guesses and feedback.
+
+use std::io::{self, Write};
+use wordle_solver::arena::WordArena;
+use wordle_solver::constraints::{Constraint, Constraints};
+
+use crate::WORD_LENGTH;
+
+/// Builds the constraints described by the `--green`, `--yellow` and `--gray` options
+fn parse_constraints(
+  green: Option<&str>,
+  yellow: Option<&str>,
+  gray: Option<&str>,
+) -> Result<Constraints, String> {
+  let mut constraints = Constraints::new();
+
+  if let Some(green) = green {
+    if green.chars().count() != WORD_LENGTH {
+      return Err(format!(
+        "--green needs {} characters, one per position",
+        WORD_LENGTH
+      ));
+    }
+    for (position, letter) in green.chars().enumerate() {
+      if letter != '.' {
+        constraints.add(Constraint::At(position, letter));
+      }
+    }
+  }
+
+  for entry in yellow
+    .unwrap_or("")
+    .split(|c: char| c == ';' || c.is_whitespace())
+  {
+    if entry.is_empty() {
+      continue;
+    }
+    let (letter, positions) = entry.split_once(':').unwrap_or((entry, ""));
+    let mut letters = letter.chars();
+    let letter = match (letters.next(), letters.next()) {
+      (Some(letter), None) => letter,
+      _ => {
+        return Err(format!(
+          "Expected a single letter in --yellow entry {:?}",
+          entry
+        ))
+      }
+    };
+
+    constraints.add(Constraint::AtLeast(letter, 1));
+    for position in positions.split(',').filter(|p| !p.is_empty()) {
+      match position.parse::<usize>() {
+        Ok(position) if (1..=WORD_LENGTH).contains(&position) => {
+          constraints.add(Constraint::NotAt(position - 1, letter));
+        }
+        _ => return Err(format!("Invalid position {:?} in --yellow", position)),
+      }
+    }
+  }
+
+  // A gray letter that is also green or yellow means there are no more copies of it
+  for letter in gray.unwrap_or("").chars() {
+    constraints.add(Constraint::AtMost(letter, constraints.min_count(letter)));
+  }
+
+  Ok(constraints)
+}
+
+/// Prints the words of `dictionary` that satisfy the constraints, one per line
+pub fn run(
+  dictionary: &WordArena,
+  green: Option<&str>,
+  yellow: Option<&str>,
+  gray: Option<&str>,
+) -> Result<(), String> {
+  let constraints = parse_constraints(green, yellow, gray)?;
+
+  let mut out = io::stdout().lock();
+  for word in dictionary.iter().filter(|word| constraints.allows(word)) {
+    // Stop quietly when the output is closed early, e.g. piped into `head`
+    if writeln!(out, "{}", word).is_err() {
+      break;
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,208 @@
+//! Scoring a guess together with the guesses played after it.
+//!
+//! The greedy strategies only look at what a guess tells right away, and sometimes pick an opener
+//! that splits the candidates well but leaves groups no second guess splits further. [`Lookahead`]
+//! plays the best follow-up in each group a guess leaves, and so on for a number of guesses, and
+//! scores the information of them all.
+//!
+//! Trying every allowed word as the follow-up for every group of every guess would take hours for
+//! the opener. The follow-ups tried are the few guesses most informative about all the candidates,
+//! plus the words of the group itself, which are the best choice for small groups.
+//!
+//! In hard mode, the follow-ups must keep the greens and use the letters found so far. A guess
+//! leaving groups that only their own words can split, like `_ight`, then looks as bad as it is.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::engine;
+use crate::mark::{self, Mark};
+use crate::optimal::Optimal;
+use crate::scoring::ScoringBuffers;
+use crate::solver::{DictWord, Strategy, WORD_LENGTH};
+
+/// From this depth on, small candidate sets are searched exactly
+pub const EXACT_DEPTH: usize = 4;
+
+/// Most candidates searched exactly, see [`EXACT_DEPTH`]
+pub const EXACT_CANDIDATES: usize = 100;
+
+/// Scores a guess by the expected information of it and of the best follow-ups in each group of
+/// candidates it leaves, `depth` guesses in all, in bits. A depth of 1 is the [`Average`]
+/// strategy.
+///
+/// From [`EXACT_DEPTH`] on, once there are at most [`EXACT_CANDIDATES`] candidates, it scores like
+/// [`Optimal`] instead, by minus the expected number of guesses. Not in hard mode, which the exact
+/// search doesn't know about.
+///
+/// [`Average`]: crate::solver::Average
+pub struct Lookahead {
+  guesses: Vec<DictWord>,
+  depth: usize,
+  /// How many of the most informative guesses are tried as follow-ups
+  breadth: usize,
+  /// The follow-ups of the candidate sets scored so far
+  follow_ups: Mutex<HashMap<Vec<DictWord>, Vec<DictWord>>>,
+  /// Only follow-ups hard mode allows are tried
+  hard: bool,
+  exact: Optimal,
+}
+
+impl Lookahead {
+  /// Looks `depth` guesses ahead, trying the `breadth` guesses of `allowed` most informative about
+  /// the candidates as follow-ups
+  pub fn new<S: AsRef<str>>(allowed: &[S], depth: usize, breadth: usize) -> Lookahead {
+    Lookahead {
+      guesses: allowed
+        .iter()
+        .filter_map(|word| DictWord::new(word.as_ref()))
+        .collect(),
+      depth: depth.max(1),
+      breadth,
+      follow_ups: Mutex::new(HashMap::new()),
+      hard: false,
+      exact: Optimal::new(allowed),
+    }
+  }
+
+  /// Only tries the follow-ups hard mode allows after each guess, see
+  /// [`engine::respects_hard_mode`]
+  pub fn hard_mode(mut self, hard: bool) -> Lookahead {
+    self.hard = hard;
+    self
+  }
+
+  /// The most informative guesses about `candidates`
+  fn follow_ups(&self, candidates: &[DictWord]) -> Vec<DictWord> {
+    let mut follow_ups = self.follow_ups.lock().unwrap();
+    let shortlist = follow_ups.entry(candidates.to_vec()).or_insert_with(|| {
+      let mut buffers = ScoringBuffers::new();
+      let mut scored: Vec<(f64, DictWord)> = self
+        .guesses
+        .iter()
+        .map(|guess| {
+          let sizes = buffers.word_bucket_sizes(guess, candidates);
+          (engine::entropy(sizes), *guess)
+        })
+        .collect();
+      scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap());
+      scored
+        .into_iter()
+        .take(self.breadth)
+        .map(|(_, guess)| guess)
+        .collect()
+    });
+    shortlist.clone()
+  }
+
+  /// Expected information of `guess` and of the best follow-ups after it, `depth` guesses in all
+  fn information(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    depth: usize,
+    follow_ups: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    if depth <= 1 {
+      return engine::entropy(buffers.word_bucket_sizes(guess, candidates));
+    }
+
+    let mut groups: HashMap<usize, Vec<DictWord>> = HashMap::new();
+    for word in candidates {
+      groups
+        .entry(guess.pattern_code(word))
+        .or_default()
+        .push(*word);
+    }
+
+    let total = candidates.len() as f64;
+    let guess_text = guess.to_string();
+    groups
+      .iter()
+      .map(|(&code, group)| {
+        let size = group.len() as f64;
+        let hard_follow_ups: Vec<DictWord>;
+        let follow_ups = if self.hard {
+          let marks: [Mark; WORD_LENGTH] = mark::unpack(code as u32);
+          hard_follow_ups = follow_ups
+            .iter()
+            .filter(|follow_up| {
+              engine::respects_hard_mode(&follow_up.to_string(), &guess_text, &marks)
+            })
+            .copied()
+            .collect();
+          &hard_follow_ups
+        } else {
+          follow_ups
+        };
+        // Two words are told apart by guessing either
+        let rest = if group.len() <= 2 {
+          size.log2()
+        } else {
+          follow_ups
+            .iter()
+            .chain(group.iter())
+            .map(|follow_up| self.information(follow_up, group, depth - 1, follow_ups, buffers))
+            .fold(0.0, f64::max)
+        };
+        size / total * ((total / size).log2() + rest)
+      })
+      .sum()
+  }
+}
+
+impl Strategy for Lookahead {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    if self.depth >= EXACT_DEPTH && candidates.len() <= EXACT_CANDIDATES && !self.hard {
+      return self.exact.score(guess, candidates);
+    }
+    if self.depth == 1 {
+      return self.information(guess, candidates, 1, &[], buffers);
+    }
+
+    let follow_ups = self.follow_ups(candidates);
+    self.information(guess, candidates, self.depth, &follow_ups, buffers)
+  }
+
+  fn name(&self) -> String {
+    match self.hard {
+      false => format!("lookahead {} {}", self.depth, self.breadth),
+      true => format!("hard lookahead {} {}", self.depth, self.breadth),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const IGHT: [&str; 6] = ["fight", "light", "might", "night", "right", "sight"];
+
+  /// The score of `fight` against the `-ight` words, looking a guess further with `allowed`
+  fn fight(allowed: &[&str], hard: bool) -> f64 {
+    let candidates: Vec<DictWord> = IGHT.iter().filter_map(|word| DictWord::new(word)).collect();
+    let lookahead = Lookahead::new(allowed, 2, allowed.len()).hard_mode(hard);
+    lookahead.score(&DictWord::new("fight").unwrap(), &candidates)
+  }
+
+  #[test]
+  fn hard_mode_follow_ups_keep_the_greens() {
+    // merls tells light, might, right, sight and night apart, but drops the greens of fight
+    let allowed = [
+      "fight", "light", "might", "night", "right", "sight", "merls",
+    ];
+    assert!(fight(&allowed, false) > fight(&allowed, true));
+    assert_eq!(fight(&allowed, true), fight(&IGHT, false));
+    assert_eq!(fight(&allowed, true), fight(&IGHT, true));
+  }
+}
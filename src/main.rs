@@ -1,397 +1,2569 @@
-use clap::Parser;
-use itertools::Itertools;
-use rayon::prelude::*;
-use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufRead, Result};
+use clap::{Parser, Subcommand};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, debug_span, info, Level};
+use tracing_subscriber::fmt::format::FmtSpan;
+use wordle_solver::adversarial::{self, Adversarial};
+use wordle_solver::arena::WordArena;
+use wordle_solver::bounds;
+use wordle_solver::constraints::{Constraint, Constraints};
+use wordle_solver::diff::StateDiff;
+use wordle_solver::difficulty::Tier;
+use wordle_solver::engine::{self, HardModeViolation};
+use wordle_solver::feedback::{Lying, Wordle};
+use wordle_solver::game_score::GameScore;
+use wordle_solver::guarantee::Guarantee;
+use wordle_solver::lookahead::Lookahead;
+use wordle_solver::mark::Mark;
+use wordle_solver::optimal::Optimal;
+use wordle_solver::pattern::Pattern;
+use wordle_solver::priors::PriorModel;
+use wordle_solver::progress::Progress;
+use wordle_solver::random::Rng;
+use wordle_solver::solver::{
+  best_attempt, best_attempt_with, compute_guess_scores, compute_information_value,
+  get_suggestions, get_suggestions_with_progress, reduce_dictionary, sample_attempt,
+  strategy_param, to_word, top_suggestions, weight_of, CandidateWeights, DictWord, Fibble,
+  GameState, LargestGroup, PositionalFrequency, Schedule, Scored, SolverObserver, SolverSession,
+  Strategy, StrategyRegistry, Until, Weighted, DEFAULT_LAMBDA, WORD_LENGTH,
+};
+use wordle_solver::tree::{DecisionTree, TreeStrategy};
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-enum Mark {
-  NotPresent = 0,
-  WrongPosition = 1,
-  RightPosition = 2,
-}
+use book::BookFormat;
+use error::SolverError;
+use frequency::Frequencies;
+use language::Language;
+use overlay::{Overlay, OverlayState};
+use quordle::BoardPolicy;
+use ranking::{Rank, SortKey, TieBreak};
+use session::{Session, Sessions};
+use table::Columns;
+use tally::Tally;
+use theme::Theme;
+use tree_export::TreeFormat;
 
-type DictString = String;
+mod analyze;
+mod benchmark;
+mod book;
+mod bundled;
+mod cache;
+mod crosswordle;
+mod error;
+mod frequency;
+mod host;
+mod http;
+mod humans;
+mod language;
+mod learn;
+mod length;
+mod list;
+mod overlay;
+mod peaks;
+mod practice;
+mod primel;
+mod probe;
+mod progress_bar;
+mod quordle;
+mod ranking;
+mod session;
+mod table;
+mod tally;
+mod theme;
+mod transcript;
+mod tree_export;
+#[cfg(feature = "tui")]
+mod tui;
+mod warmle;
+mod web;
+mod xordle;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Strategy {
-  WorstCase,
-  Gambling(f64),
-  Average,
-}
+type DictString = str;
 
 const SHOWN_GUESSES: usize = 10;
 
-fn compute_guess_scores<'a>(
-  words_all: &Vec<&'a DictString>,
-  words_reduced: &Vec<&'a DictString>,
-  strategy: Strategy,
-) -> HashMap<&'a DictString, f64> {
-  return words_all
-    .par_iter()
-    .map(|&x| (x, compute_information_value(x, &words_reduced, strategy)))
-    .collect();
+fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+where
+  P: AsRef<Path>,
+{
+  let file = File::open(filename)?;
+  Ok(io::BufReader::new(file).lines())
 }
 
-fn compute_bucket_sizes(guess: &DictString, words: &Vec<&DictString>) -> Vec<usize> {
-  words
-    .into_iter()
-    .map(|w| (compute_bucket(guess, w), w))
-    .into_group_map()
-    .into_iter()
-    .map(|(_, g)| g.len())
-    .collect::<Vec<_>>()
+/// Shows the best guesses found so far on stderr while scoring runs, redrawing them in place.
+/// Does nothing when stderr is not a terminal.
+struct LiveTop {
+  enabled: bool,
+  shown_lines: Mutex<usize>,
 }
 
-fn compute_information_value(
-  guess: &DictString,
-  words: &Vec<&DictString>,
-  strategy: Strategy,
-) -> f64 {
-  let mut bucket_sizes = compute_bucket_sizes(guess, words);
+impl LiveTop {
+  fn new() -> LiveTop {
+    LiveTop {
+      enabled: progress_bar::shown(),
+      shown_lines: Mutex::new(0),
+    }
+  }
+}
 
-  match strategy {
-    Strategy::WorstCase => {
-      let worst_case_count = bucket_sizes.into_iter().max().unwrap_or(0) as f64;
-      return (words.len() as f64 / worst_case_count).log2();
+impl SolverObserver for LiveTop {
+  fn on_scoring_progress(&self, progress: Progress) {
+    if !self.enabled {
+      return;
     }
-    Strategy::Average => {
-      let information_amount: f64 = bucket_sizes
-        .into_iter()
-        .map(|sz| {
-          let guess_probability = sz as f64 / words.len() as f64;
-          let log_info = (1.0 / guess_probability).log2();
-          return guess_probability * log_info;
-        })
-        .sum();
-
-      return information_amount;
-    }
-    Strategy::Gambling(gambling_factor) => {
-      bucket_sizes.sort_by(|a, b| {
-        if a > b {
-          Ordering::Less
-        } else if a < b {
-          Ordering::Greater
-        } else {
-          Ordering::Equal
-        }
-      });
 
-      let mut total_size = 0;
+    let mut shown_lines = self.shown_lines.lock().unwrap();
+    let mut text = String::new();
+    if *shown_lines > 0 {
+      // Back to the start of the block drawn last time, then clear it
+      text.push_str(&format!("\x1b[{}F", *shown_lines));
+    }
+    text.push_str("\x1b[J");
 
-      for size in bucket_sizes {
-        total_size += size;
-        let new_gambling = total_size as f64 / words.len() as f64;
-        if new_gambling > gambling_factor {
-          return (words.len() as f64 / size as f64).log2();
-        }
+    if progress.is_finished() {
+      *shown_lines = 0;
+    } else {
+      text.push_str(&format!("Scoring... {:.0}%\n", 100.0 * progress.fraction()));
+      for (word, score) in progress.leaders.iter() {
+        text.push_str(&format!("  {} {:.4}\n", word, score));
       }
-
-      return 0.0;
+      *shown_lines = progress.leaders.len() + 1;
     }
+
+    eprint!("{}", text);
   }
 }
 
-/// This function tries to faithfully reproduce the same algorithm as found
-/// in the original Wordle. Letters from the word get "used up" first by their presence
-/// at the exact same position (i.e. "green" marks). Then if a letter appears multiple times in
-/// the guess but at the wrong position, it will start using up the same letter in the word, to
-/// ensure that if there is just a single occurence of the guessed letter in the word, only the
-/// first occurrence in the guess gets marked "yellow" (wrong position)
-///
-// #[memoize]
-fn compute_bucket(guess: &DictString, word: &DictString) -> Vec<Mark> {
-  let mut used = vec![false; word.len()];
-  let mut result = vec![Mark::NotPresent; word.len()];
+fn top_words<'a>(scored: &[(&'a DictString, f64)]) -> Vec<&'a str> {
+  scored
+    .iter()
+    .take(SHOWN_GUESSES)
+    .map(|(word, _)| *word)
+    .collect()
+}
+
+/// How suggestions are shown
+struct DisplayOptions {
+  /// Show what changed after each feedback
+  verbose: bool,
+  columns: Option<Columns>,
+  sort: SortKey,
+  /// Rank suggestions and pick the word to try by these objectives rather than the score, with
+  /// --rank
+  rank: Option<Rank>,
+  tie_break: TieBreak,
+  frequencies: Frequencies,
+  /// How likely each candidate is to be the answer, with --priors
+  weights: Option<&'static CandidateWeights>,
+  /// Only these words are recommended, with --max-obscurity
+  familiar: Option<HashSet<String>>,
+  theme: Theme,
+  /// How typed guesses are spelled
+  language: Language,
+  /// How much the word to try favors candidates, see --lambda
+  lambda: f64,
+  /// Only suggest words hard mode allows, with --hard
+  hard: bool,
+  /// Only suggest words Antiwordle allows, which hard mode does and without the letters found
+  /// absent, with --anti
+  anti: bool,
+  /// One mark of every pattern is a lie, with --fibble
+  fibble: bool,
+  /// Only play words still finding every answer in time, with --guarantee
+  guarantee: Option<Guarantee>,
+  /// Draw the word to try at random at this temperature, 0 for the best, see --temperature
+  temperature: f64,
+  /// Draws the words to try, seeded with --seed
+  rng: Mutex<Rng>,
+}
 
-  for ((index, guess_char), word_char) in guess.chars().enumerate().zip(word.chars()) {
-    if word_char == guess_char {
-      used[index] = true;
-      result[index] = Mark::RightPosition;
+impl DisplayOptions {
+  /// How much the word to try favors candidates when scoring with `strategy`, see --lambda
+  fn lambda_for(&self, strategy: &dyn Strategy) -> f64 {
+    if strategy.counts_wins() {
+      0.0
+    } else {
+      self.lambda
     }
   }
 
-  for (guess_index, guess_char) in guess.chars().enumerate() {
-    for (word_index, word_char) in word.chars().enumerate() {
-      if result[guess_index] == Mark::RightPosition {
-        continue;
-      }
-      if used[word_index] {
-        continue;
-      }
-      if word_index == guess_index {
-        continue;
-      }
-
-      if guess_char == word_char {
-        used[word_index] = true;
-        result[guess_index] = Mark::WrongPosition;
-        break;
-      }
+  /// The chance `word` is the answer among `candidates`, by the weights of --priors if given
+  fn answer_chance(&self, word: &str, candidates: &[&str]) -> f64 {
+    let weight = |word: &str| match self.weights {
+      Some(weights) => weight_of(weights, &to_word(word)),
+      None => 1.0,
+    };
+    if candidates.contains(&word) {
+      let total: f64 = candidates.iter().map(|candidate| weight(candidate)).sum();
+      weight(word) / total
+    } else {
+      0.0
     }
   }
+}
 
-  return result;
+/// `scored` ordered by the objectives of --rank, `candidates` being the words that can still be
+/// the answer
+fn ranked<'a>(
+  scored: &Scored<'a>,
+  candidates: &[&DictString],
+  rank: &Rank,
+  display: &DisplayOptions,
+) -> Scored<'a> {
+  let chances: HashMap<&str, f64> = candidates
+    .iter()
+    .map(|&word| (word, display.answer_chance(word, candidates)))
+    .collect();
+  ranking::rank(scored, candidates, rank, &display.frequencies, &chances)
 }
 
-fn reduce_dictionary<'a>(
-  guess: &DictString,
-  marks: &Vec<Mark>,
-  dict: &Vec<&'a DictString>,
-) -> Vec<&'a DictString> {
-  return dict
-    .into_par_iter()
-    .filter(|word| &compute_bucket(guess, word) == marks)
-    .map(|&x| x)
+/// Shows a guess on colored tiles when stdout is a terminal
+fn print_tiles(word: &str, marks: &[Mark], display: &DisplayOptions) {
+  if io::stdout().is_terminal() {
+    println!("{}", display.theme.paint(word, marks));
+  }
+}
+
+/// The scored words in `familiar`, or None if none is
+fn only_familiar<'a>(scored: &Scored<'a>, familiar: &HashSet<String>) -> Option<Scored<'a>> {
+  let kept: Scored<'a> = scored
+    .iter()
+    .filter(|(word, _)| familiar.contains(*word))
+    .copied()
     .collect();
+  if kept.is_empty() {
+    None
+  } else {
+    Some(kept)
+  }
 }
 
-fn get_suggestions<'a>(
-  dict: &Vec<&'a DictString>,
-  reduced_dict: &Vec<&'a DictString>,
-  strategy: Strategy,
-) -> (Vec<(&'a DictString, f64)>, Vec<(&'a DictString, f64)>) {
-  let scores = compute_guess_scores(&dict, &reduced_dict, strategy);
+/// The scored words in `familiar`, or all of them if none is or there is no such set
+fn recommended<'a>(scored: &Scored<'a>, familiar: Option<&HashSet<String>>) -> Scored<'a> {
+  familiar
+    .and_then(|familiar| only_familiar(scored, familiar))
+    .unwrap_or_else(|| scored.clone())
+}
 
-  let score_criteria = |a: &&DictString, b: &&DictString| {
-    let diff = scores.get(a).unwrap_or(&0.0) - scores.get(b).unwrap_or(&0.0);
-    if diff < 0.0 {
-      Ordering::Greater
-    } else if diff > 0.0 {
-      Ordering::Less
-    } else {
-      Ordering::Equal
-    }
+/// The scored words common enough to be recommended, or all of them if none is
+fn familiar<'a>(scored: &Scored<'a>, display: &DisplayOptions) -> Scored<'a> {
+  recommended(scored, display.familiar.as_ref())
+}
+
+/// The word to suggest among the recommended ones, see [`best_attempt_with`]. `guesses` are all
+/// the candidates.
+fn attempt<'a>(
+  suggestions: &Scored<'a>,
+  guesses: &Scored<'a>,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+) -> Option<&'a DictString> {
+  let suggestions = familiar(suggestions, display);
+  let familiar_guesses = familiar(guesses, display);
+  let candidates: Vec<&str> = guesses.iter().map(|&(word, _)| word).collect();
+  let chance = match familiar_guesses.first() {
+    Some(&(word, _)) => display.answer_chance(word, &candidates),
+    None => 0.0,
   };
+  let lambda = display.lambda_for(strategy);
+  best_attempt_with(&suggestions, &familiar_guesses, chance, lambda)
+}
 
-  let mut cloned = dict.clone();
-  cloned.par_sort_by(score_criteria);
+/// The word to suggest: the best guess unless the best candidate, which could win right away,
+/// scores as well with its bonus, or one drawn at random with --temperature, explaining the pick
+/// with -v. None when no candidate is left.
+fn pick_attempt<'a>(
+  suggestions: &Scored<'a>,
+  guesses: &Scored<'a>,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+) -> Option<&'a DictString> {
+  let (sug_word, sug_score) = *familiar(suggestions, display).first()?;
+  let (guess_word, guess_score) = *familiar(guesses, display).first()?;
+  let candidates: Vec<&str> = guesses.iter().map(|&(word, _)| word).collect();
+  let lambda = display.lambda_for(strategy);
+  let bonus = lambda * display.answer_chance(guess_word, &candidates);
 
-  let top5sugg = (&cloned)
-    .into_iter()
-    .map(|&x| (x, *scores.get(&x).unwrap()))
-    .collect();
+  if let Some(rank) = &display.rank {
+    let (attempt, score) =
+      *ranked(&familiar(suggestions, display), &candidates, rank, display).first()?;
+    info!(
+      "Picked {} ({:.4}), ranked first by --rank, the best guess being {} ({:.4})",
+      attempt, score, sug_word, sug_score
+    );
+    return Some(attempt);
+  }
 
-  let mut reduced_cloned = reduced_dict.clone();
-  reduced_cloned.par_sort_by(score_criteria);
+  if display.temperature > 0.0 {
+    let chance = display.answer_chance(guess_word, &candidates);
+    let attempt = sample_attempt(
+      &familiar(suggestions, display),
+      &familiar(guesses, display),
+      chance,
+      lambda,
+      display.temperature,
+      &mut display.rng.lock().unwrap(),
+    )
+    .unwrap_or(guess_word);
+    let temperature = display.temperature;
+    info!(
+      "Drew {} at a temperature of {}, the best guess being {} ({:.4}) and the best candidate {} \
+       ({:.4} with a bonus of {:.4} for its chance to win now)",
+      attempt, temperature, sug_word, sug_score, guess_word, guess_score, bonus
+    );
+    return Some(attempt);
+  }
 
-  let top5guess = (&reduced_cloned)
-    .into_iter()
-    .map(|&x| (x, *scores.get(&x).unwrap()))
-    .collect();
+  let attempt = attempt(suggestions, guesses, strategy, display).unwrap_or(guess_word);
+  if attempt == sug_word && attempt != guess_word {
+    info!(
+      "Picked {} ({:.4}) over the best candidate {} ({:.4} with a bonus of {:.4} for its chance \
+       to win now)",
+      sug_word, sug_score, guess_word, guess_score, bonus
+    );
+  } else {
+    info!(
+      "Picked the candidate {} ({:.4} with a bonus of {:.4} for its chance to win now) over the \
+       best guess {} ({:.4})",
+      guess_word, guess_score, bonus, sug_word, sug_score
+    );
+  }
+  Some(attempt)
+}
 
-  return (top5sugg, top5guess);
+/// Number of the best suggestions tried with --guarantee before giving up
+const GUARANTEE_CHOICES: usize = 20;
+
+/// `attempt` if it still finds any of `candidates` within `turns` guesses, or else the best of
+/// the first [`GUARANTEE_CHOICES`] suggestions that does, or else the word of `allowed` the search
+/// found
+fn guaranteed_attempt<'a>(
+  guarantee: &Guarantee,
+  attempt: &'a str,
+  suggestions: &Scored<'a>,
+  allowed: &[&'a DictString],
+  candidates: &[&DictString],
+  turns: usize,
+) -> Option<&'a str> {
+  let candidates: Vec<DictWord> = candidates.iter().map(|word| to_word(word)).collect();
+  let ranked = std::iter::once(attempt)
+    .chain(
+      suggestions
+        .iter()
+        .take(GUARANTEE_CHOICES)
+        .map(|&(word, _)| word),
+    )
+    .find(|word| guarantee.fits(&to_word(word), &candidates, turns));
+  ranked.or_else(|| {
+    let found = guarantee.find(&candidates, turns)?;
+    allowed.iter().copied().find(|&word| to_word(word) == found)
+  })
 }
 
-fn read_lines<P>(filename: P) -> Result<io::Lines<io::BufReader<File>>>
-where
-  P: AsRef<Path>,
-{
-  let file = File::open(filename)?;
-  Ok(io::BufReader::new(file).lines())
+/// With --guarantee, swaps `attempt` for a word finding the answer within [`benchmark::MAX_TRIES`]
+/// when it might not, `tries` guesses having been played. Keeps it when no word is sure to.
+fn within_limit<'a>(
+  attempt: &'a str,
+  suggestions: &Scored<'a>,
+  allowed: &[&'a DictString],
+  candidates: &[&DictString],
+  tries: usize,
+  display: &DisplayOptions,
+) -> &'a str {
+  let guarantee = match &display.guarantee {
+    Some(guarantee) if tries < benchmark::MAX_TRIES => guarantee,
+    _ => return attempt,
+  };
+
+  let turns = benchmark::MAX_TRIES - tries;
+  match guaranteed_attempt(guarantee, attempt, suggestions, allowed, candidates, turns) {
+    Some(safe) => {
+      if safe != attempt {
+        info!(
+          "Picked {} over {}, which might not find the answer within {} more guesses",
+          safe, attempt, turns
+        );
+      }
+      safe
+    }
+    None => {
+      println!(
+        "No word is sure to find the answer within {} more guesses, trying {:?} anyway",
+        turns, attempt
+      );
+      attempt
+    }
+  }
 }
 
-fn interactive(
-  dictionary: Vec<DictString>,
-  reducing_dictionary: Vec<DictString>,
-  strategy: Strategy,
+fn print_suggestions(
+  suggestions: &Scored,
+  guesses: &Scored,
+  candidates: &[&DictString],
+  display: &DisplayOptions,
 ) {
-  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
-  let mut reducing_dictionary_ref = reducing_dictionary.iter().collect();
+  let (unconstrained_best, unconstrained_guesses) = (suggestions.first(), guesses);
+  let (suggestions, guesses) = &(familiar(suggestions, display), familiar(guesses, display));
 
-  let stdin = io::stdin();
+  let sorted;
+  let (suggestions, guesses) = if let Some(rank) = &display.rank {
+    let rank = |scored| ranked(scored, candidates, rank, display);
+    sorted = (rank(suggestions), rank(guesses));
+    (&sorted.0, &sorted.1)
+  } else if display.sort == SortKey::Bits {
+    (suggestions, guesses)
+  } else {
+    let sort = |scored| ranking::sort(scored, candidates, display.sort, &display.frequencies);
+    sorted = (sort(suggestions), sort(guesses));
+    (&sorted.0, &sorted.1)
+  };
+
+  if let Some(columns) = &display.columns {
+    table::print_table("Suggestions", suggestions, candidates, columns, display);
+    table::print_table("Guesses", guesses, candidates, columns, display);
+  } else {
+    println!(
+      "Suggestions: {:?} {:?}",
+      suggestions.len(),
+      suggestions.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+    println!(
+      "Guesses: {:?} {:?}",
+      guesses.len(),
+      guesses.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+  }
+
+  if let (Some(familiar), Some(&(word, score))) = (&display.familiar, unconstrained_best) {
+    if !familiar.contains(word) {
+      println!(
+        "FYI, the best suggestion overall is {:?} ({:.4}), too obscure for --max-obscurity",
+        word, score
+      );
+    }
+  }
+  if let Some(familiar) = &display.familiar {
+    if !candidates.is_empty() && only_familiar(unconstrained_guesses, familiar).is_none() {
+      println!("FYI, every candidate is too obscure for --max-obscurity, showing them all");
+    }
+  }
+}
 
-  let (sugg1, sugg2) = get_suggestions(&dictionary_ref, &reducing_dictionary_ref, strategy);
+/// For the heuristic strategies, which only estimate information, shows the fewest guesses any
+/// strategy could need from here
+fn print_bounds(strategy: &dyn Strategy, candidates: usize) {
+  if strategy.heuristic().is_none() {
+    return;
+  }
 
+  let bounds = bounds::guess_bounds(candidates, WORD_LENGTH);
   println!(
-    "Suggestions: {:?} {:?}",
-    sugg1.len(),
-    sugg1.into_iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    "No strategy can do better than {:.3} guesses on average, {} in the worst case",
+    bounds.expected, bounds.worst
   );
+}
+
+/// With --largest-group, shows how many candidates the best suggestions leave at most, the count
+/// their scores rank by
+fn print_largest_groups(strategy: &dyn Strategy, suggestions: &Scored, candidates: usize) {
+  if strategy.name() != LargestGroup.name() {
+    return;
+  }
+
+  let counts: Vec<(&str, usize)> = suggestions
+    .iter()
+    .take(SHOWN_GUESSES)
+    .map(|&(word, score)| (word, LargestGroup::count(score)))
+    .collect();
   println!(
-    "Guesses: {:?} {:?}",
-    sugg2.len(),
-    sugg2.into_iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    "Largest groups left of {} candidates: {:?}",
+    candidates, counts
   );
+}
 
-  for line in stdin.lock().lines() {
-    let line_content = line.unwrap();
-    let word_marks: Vec<&str> = line_content.split(' ').into_iter().collect();
-    let used_word = String::from(word_marks[0]);
-    let marks = word_marks[1];
+/// Handles a `session new [name]`, `session switch <name>` or `session list` command
+fn session_command(sessions: &mut Sessions, arguments: &str, display: &DisplayOptions) {
+  let arguments: Vec<&str> = arguments.split_whitespace().collect();
+  let result = match arguments.as_slice() {
+    ["new"] => sessions.create(None),
+    ["new", name] => sessions.create(Some(name)),
+    ["switch", name] => sessions.switch(name),
+    ["list"] => {
+      for (current, session) in sessions.list() {
+        println!(
+          "{} {}: {} turns, {} candidates",
+          if current { "*" } else { " " },
+          session.name,
+          session.board.len(),
+          session.candidates.len()
+        );
+      }
+      return;
+    }
+    _ => Err("Usage: session new [name] | session switch <name> | session list".to_string()),
+  };
 
-    println!("Got word {} and marks: {}", used_word, marks);
+  match result {
+    Ok(session) => {
+      println!("Session {}", session.name);
+      print_suggestions(
+        &session.suggestions,
+        &session.guesses,
+        &session.candidates,
+        display,
+      );
+    }
+    Err(message) => println!("{}", message),
+  }
+}
 
-    let update_marks: Vec<Mark> = marks
-      .chars()
-      .map(|c| match c {
-        '-' => Mark::NotPresent,
-        '+' => Mark::WrongPosition,
-        _ => Mark::RightPosition,
-      })
-      .collect();
+/// Reads a `word marks` line of feedback
+fn parse_feedback(line: &str) -> Result<(String, Vec<Mark>), SolverError> {
+  let invalid = |reason: String| SolverError::InvalidFeedback {
+    input: line.to_string(),
+    reason,
+  };
 
-    reducing_dictionary_ref =
-      reduce_dictionary(&used_word, &update_marks, &reducing_dictionary_ref);
+  let (word, marks) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+    &[word, marks] => (word, marks),
+    _ => return Err(invalid("expected a word and its marks".to_string())),
+  };
+  let marks = marks
+    .chars()
+    .map(Mark::try_from)
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|err| invalid(err.to_string()))?;
+  if word.chars().count() != WORD_LENGTH || marks.len() != WORD_LENGTH {
+    return Err(invalid(format!(
+      "expected {} letters and {} marks",
+      WORD_LENGTH, WORD_LENGTH
+    )));
+  }
 
-    let (ref sugg1, ref sugg2) =
-      get_suggestions(&dictionary_ref, &reducing_dictionary_ref, strategy);
+  Ok((word.to_string(), marks))
+}
 
-    println!(
-      "Suggestions: {:?} {:?}",
-      sugg1.len(),
-      sugg1.into_iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
-    );
-    println!(
-      "Guesses: {:?} {:?}",
-      sugg2.len(),
-      sugg2.into_iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
-    );
+/// Shows the game of `session` on the overlay, if there is one
+fn update_overlay(
+  overlay: &Option<Overlay>,
+  session: &Session,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+) {
+  if let Some(overlay) = overlay {
+    let strategy = strategy
+      .at_turn(session.board.len() + 1, session.candidates.len())
+      .unwrap_or(strategy);
+    let suggestion = attempt(&session.suggestions, &session.guesses, strategy, display);
+    overlay.update(OverlayState {
+      board: session.board.clone(),
+      candidates: session.candidates.len(),
+      suggestion: suggestion.map(str::to_string),
+    });
+  }
+}
 
-    let (sug_word, sug_score) = sugg1[0];
-    let (guess_word, guess_score) = sugg2[0];
+/// The words of `dictionary` the game of `session` hasn't refused
+fn allowed_words<'a>(
+  dictionary: &[&'a DictString],
+  session: &Session,
+  display: &DisplayOptions,
+) -> Vec<&'a DictString> {
+  let allowed: Vec<&DictString> = dictionary
+    .iter()
+    .filter(|&&word| !session.rejected.contains(word))
+    .filter(|&&word| !display.hard || hard_mode_allows(word, &session.board, display))
+    .copied()
+    .collect();
+  // Words tie-breaking by the candidates move along the game
+  display
+    .tie_break
+    .order(&allowed, &session.candidates, &display.frequencies)
+}
 
-    let attempt_word = if sug_score >= guess_score + 0.005 {
-      sug_word
-    } else {
-      guess_word
-    };
+/// The candidates left after `guess` got `marks`, one of which is a lie with --fibble
+fn reduce_candidates<'a>(
+  guess: &str,
+  marks: &[Mark],
+  candidates: &[&'a DictString],
+  display: &DisplayOptions,
+) -> Vec<&'a DictString> {
+  match Pattern::try_from(marks) {
+    Ok(pattern) if display.fibble => {
+      engine::reduce_with(&Lying(Wordle), guess, pattern.index() as usize, candidates)
+    }
+    _ => reduce_dictionary(guess, marks, candidates),
+  }
+}
 
-    println!("Suggest you try {:?}", attempt_word);
+/// The first rule of hard mode `word` breaks after `guess` got `marks`, or of Antiwordle with
+/// --anti
+fn rule_violation(
+  word: &str,
+  guess: &str,
+  marks: &[Mark],
+  display: &DisplayOptions,
+) -> Option<HardModeViolation> {
+  if display.anti {
+    engine::anti_wordle_violation(word, guess, marks)
+  } else {
+    engine::hard_mode_violation(word, guess, marks)
   }
 }
 
-fn play_word(
-  word: String,
-  dictionary: Vec<DictString>,
-  reducing_dictionary: Vec<DictString>,
-  strategy: Strategy,
-) {
-  let dict_ref: Vec<&DictString> = dictionary.iter().collect();
-  let mut reducing_dict_ref: Vec<&DictString> = reducing_dictionary.iter().collect();
+/// Whether hard mode lets `word` be played after the guesses of `board`
+fn hard_mode_allows(word: &str, board: &[(String, Vec<Mark>)], display: &DisplayOptions) -> bool {
+  hard_mode_violation(word, board, display).is_none()
+}
 
-  let mut tries = 0;
-  loop {
-    let (ref suggestions, ref guesses) = get_suggestions(&dict_ref, &reducing_dict_ref, strategy);
+/// The first rule of hard mode `word` breaks after the guesses of `board`
+fn hard_mode_violation(
+  word: &str,
+  board: &[(String, Vec<Mark>)],
+  display: &DisplayOptions,
+) -> Option<HardModeViolation> {
+  board
+    .iter()
+    .find_map(|(guess, marks)| rule_violation(word, guess, marks, display))
+}
 
-    if guesses.len() == 0 {
-      println!("Stumped, cannot figure it out");
-      break;
-    } else if guesses.len() == 1 {
-      tries += 1;
+/// With --hard, `attempt` unless hard mode refuses it after the guesses of `board`, in which case
+/// the best of `suggestions` hard mode allows
+fn hard_attempt<'a>(
+  attempt: &'a str,
+  suggestions: &Scored<'a>,
+  board: &[(String, Vec<Mark>)],
+  display: &DisplayOptions,
+) -> &'a str {
+  if !display.hard {
+    return attempt;
+  }
+  let violation = match hard_mode_violation(attempt, board, display) {
+    Some(violation) => violation,
+    None => return attempt,
+  };
+
+  let allowed = suggestions
+    .iter()
+    .map(|&(word, _)| word)
+    .find(|word| hard_mode_allows(word, board, display));
+  match allowed {
+    Some(word) => {
       println!(
-        "Got it on try {:?}! The answer is: {:?}",
-        tries, guesses[0].0
+        "Hard mode refuses {:?} ({}), trying {:?} instead",
+        attempt, violation, word
       );
-      break;
-    } else {
+      word
+    }
+    None => {
       println!(
-        "Suggestions: {:?} {:?}",
-        suggestions.len(),
-        suggestions
-          .into_iter()
-          .take(SHOWN_GUESSES)
-          .collect::<Vec<_>>()
+        "Hard mode refuses {:?} ({}) and every other suggestion, trying it anyway",
+        attempt, violation
       );
+      attempt
+    }
+  }
+}
+
+/// Adds a word the game refused to the ban list file
+fn record_rejected(file: &str, word: &str) -> io::Result<()> {
+  let mut out = std::fs::OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(file)?;
+  writeln!(out, "{}", word)
+}
+
+/// Words the game refused in earlier games, one per line. A missing file is an empty list.
+fn read_ban_list(file: &str, language: &Language) -> HashSet<String> {
+  match read_lines(file) {
+    Ok(lines) => lines
+      .map_while(|line| line.ok())
+      .map(|line| language.fold(line.trim()))
+      .filter(|word| !word.is_empty())
+      .collect(),
+    Err(_) => HashSet::new(),
+  }
+}
+
+/// Files the interactive mode reads and writes
+struct GameFiles<'a> {
+  /// Where refused words are recorded
+  ban_list: Option<&'a str>,
+  /// Where the game is saved after each turn
+  save: Option<&'a str>,
+  /// Saved game to continue
+  resume: Option<&'a str>,
+}
+
+fn interactive(
+  dictionary: WordArena,
+  reducing_dictionary: WordArena,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+  overlay: Option<u16>,
+  files: GameFiles,
+) -> Result<(), SolverError> {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let reducing_dictionary_ref: Vec<&DictString> = reducing_dictionary.iter().collect();
+
+  let stdin = io::stdin();
+  let live_top = LiveTop::new();
+
+  let opening_strategy = strategy
+    .at_turn(1, reducing_dictionary_ref.len())
+    .unwrap_or(strategy);
+  let opening = get_suggestions_with_progress(
+    &dictionary_ref,
+    &reducing_dictionary_ref,
+    opening_strategy,
+    |p| live_top.on_scoring_progress(p),
+  );
+  print_suggestions(&opening.0, &opening.1, &reducing_dictionary_ref, display);
+  print_bounds(opening_strategy, reducing_dictionary_ref.len());
+  print_largest_groups(opening_strategy, &opening.0, reducing_dictionary_ref.len());
+  let mut sessions = Sessions::new(reducing_dictionary_ref, opening);
+
+  let overlay = match overlay {
+    Some(port) => Some(Overlay::start(port, display.theme).map_err(|err| {
+      SolverError::Command(format!(
+        "Couldn't serve the overlay on port {}: {}",
+        port, err
+      ))
+    })?),
+    None => None,
+  };
+  if let Some(file) = files.resume {
+    let state: GameState = fs::read_to_string(file)
+      .map_err(|err| SolverError::io(file, err))?
+      .parse()
+      .map_err(|err| SolverError::Command(format!("{}: {}", file, err)))?;
+    resume(
+      sessions.current(),
+      &state,
+      &dictionary_ref,
+      strategy,
+      display,
+    );
+    if state.strategy != strategy.name() {
       println!(
-        "Guesses: {:?} {:?}",
-        guesses.len(),
-        guesses.into_iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+        "The game was saved playing the {} strategy, now playing {}",
+        state.strategy,
+        strategy.name()
       );
+    }
+  }
+  update_overlay(&overlay, sessions.current(), strategy, display);
 
-      let (sug_word, sug_score) = suggestions[0];
-      let (guess_word, guess_score) = guesses[0];
-
-      // let remaining_guess_bits = (1.0 / guesses.len() as f64).log2();
-      // let after_suggestion_bits = remaining_guess_bits - sug_score;
-      // let after_guess_bits = remaining_guess_bits - guess_score;
+  let mut lines = stdin.lock().lines();
+  while let Some(line) = lines.next() {
+    let line_content = line.map_err(|err| SolverError::io("standard input", err))?;
+    if let Some(arguments) = line_content.strip_prefix("session") {
+      session_command(&mut sessions, arguments, display);
+      update_overlay(&overlay, sessions.current(), strategy, display);
+      continue;
+    }
 
-      let attempt_word = if sug_score >= guess_score + 0.005 {
-        sug_word
-      } else {
-        guess_word
+    if let Some(guess) = line_content.strip_prefix("evaluate ") {
+      let guess = display.language.fold(guess.trim());
+      let session = sessions.current();
+      match probe::evaluate(&guess, &session.candidates, strategy) {
+        Some(score) => println!("{} scores {}", guess, score),
+        None => println!("Strings to evaluate have {} letters", WORD_LENGTH),
+      }
+      continue;
+    }
+    if let Some(arguments) = line_content.strip_prefix("analyze") {
+      let session = sessions.current();
+      analyze::run(
+        arguments,
+        session,
+        &allowed_words(&dictionary_ref, session, display),
+        strategy,
+      );
+      continue;
+    }
+    if line_content.trim() == "share" {
+      let marks: Vec<Vec<Mark>> = sessions
+        .current()
+        .board
+        .iter()
+        .map(|(_, marks)| marks.clone())
+        .collect();
+      println!("{}", display.theme.share_grid(&marks));
+      continue;
+    }
+    if line_content.trim() == "burn" {
+      let session = sessions.current();
+      let burners = probe::burn_guesses(
+        &allowed_words(&dictionary_ref, session, display),
+        &session.candidates,
+        &session.constraints,
+        strategy,
+      );
+      println!(
+        "Burn guesses: {:?} {:?}",
+        burners.len(),
+        burners.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+      );
+      continue;
+    }
+    if line_content.trim() == "best-probe" {
+      let session = sessions.current();
+      let start = match session.suggestions.first() {
+        Some(&(start, _)) => start,
+        None => {
+          println!("No suggestion to start from yet");
+          continue;
+        }
       };
+      let (probe, score) = probe::best_probe(
+        start,
+        &session.candidates,
+        strategy,
+        display.language.alphabet(),
+      );
+      println!("Best probe found: {:?} scoring {}", probe, score);
+      continue;
+    }
 
-      tries += 1;
+    let turns = if line_content.trim() == "paste" {
+      println!("Paste the game, then an empty line:");
+      let mut block: Vec<String> = vec![];
+      for line in lines.by_ref() {
+        let line = line.map_err(|err| SolverError::io("standard input", err))?;
+        if line.trim().is_empty() && transcript::started(&block) {
+          break;
+        }
+        block.push(line);
+      }
+      match transcript::parse(&block.join("\n")) {
+        Ok(turns) => turns
+          .into_iter()
+          .map(|(word, marks)| (display.language.fold(&word), marks))
+          .collect(),
+        Err(message) => {
+          println!("{}", message);
+          continue;
+        }
+      }
+    } else if let Some(word) = line_content.strip_prefix("rejected ") {
+      // No feedback, the suggestions are just computed again without the word
+      let word = display.language.fold(word.trim());
+      let session = sessions.current();
+      session.candidates.retain(|&candidate| candidate != word);
+      if let Some(file) = files.ban_list {
+        if let Err(err) = record_rejected(file, &word) {
+          eprintln!("Couldn't add {:?} to {}: {}", word, file, err);
+        }
+      }
+      println!("Left out {:?}", word);
+      session.rejected.insert(word);
+      vec![]
+    } else {
+      match parse_feedback(&line_content) {
+        Ok((used_word, update_marks)) => vec![(display.language.fold(&used_word), update_marks)],
+        Err(err) => {
+          println!("{}", err);
+          continue;
+        }
+      }
+    };
 
-      println!("Try {:?}, word {:?}", tries, attempt_word);
+    let session = sessions.current();
+    let candidates_before = session.candidates.len();
+    let undo = (
+      session.candidates.clone(),
+      session.constraints.clone(),
+      session.board.len(),
+    );
+    let mut learned = vec![];
+    for (used_word, update_marks) in turns.iter() {
+      let marks: String = update_marks.iter().map(|mark| mark.to_char()).collect();
+      println!("Got word {} and marks: {}", used_word, marks);
+      print_tiles(used_word, update_marks, display);
+      if let (true, Some(violation)) = (
+        display.hard,
+        hard_mode_violation(used_word, &session.board, display),
+      ) {
+        println!(
+          "Warning: hard mode wouldn't allow {:?}, {}",
+          used_word, violation
+        );
+      }
 
-      let outcome = compute_bucket(attempt_word, &word);
+      let before = session.candidates.len();
+      session.candidates = debug_span!("reducing", candidates = before)
+        .in_scope(|| reduce_candidates(used_word, update_marks, &session.candidates, display));
+      debug!(
+        "{} {} left {} of {} candidates",
+        used_word,
+        marks,
+        session.candidates.len(),
+        before
+      );
+      // What a lying pattern tells of each letter is unsure
+      if !display.fibble {
+        learned.extend(session.constraints.apply(used_word, update_marks));
+      }
+      session
+        .board
+        .push((used_word.clone(), update_marks.clone()));
+    }
+    if session.candidates.is_empty() {
+      // Nothing is learned from marks no word fits, the game goes on as before them
+      println!("No words match, check the marks");
+      session.candidates = undo.0;
+      session.constraints = undo.1;
+      session.board.truncate(undo.2);
+      continue;
+    }
 
-      if outcome == vec![Mark::RightPosition; 5] {
-        println!("Actually guessed it!");
-        break;
-      } else {
-        println!("Outcome: {:?}", outcome);
+    let allowed = allowed_words(&dictionary_ref, session, display);
+    let turn_strategy = strategy
+      .at_turn(session.board.len() + 1, session.candidates.len())
+      .unwrap_or(strategy);
+    let (sugg1, sugg2) =
+      get_suggestions_with_progress(&allowed, &session.candidates, turn_strategy, |p| {
+        live_top.on_scoring_progress(p)
+      });
 
-        reducing_dict_ref = reduce_dictionary(&attempt_word, &outcome, &reducing_dict_ref);
+    if display.verbose {
+      print!(
+        "{}",
+        StateDiff::new(
+          candidates_before,
+          session.candidates.len(),
+          learned,
+          &top_words(&session.suggestions),
+          &top_words(&sugg1)
+        )
+      );
+    }
+    session.suggestions = sugg1;
+    session.guesses = sugg2;
+
+    print_suggestions(
+      &session.suggestions,
+      &session.guesses,
+      &session.candidates,
+      display,
+    );
+    print_bounds(turn_strategy, session.candidates.len());
+    print_largest_groups(
+      turn_strategy,
+      &session.suggestions,
+      session.candidates.len(),
+    );
+
+    let attempt_word = match pick_attempt(
+      &session.suggestions,
+      &session.guesses,
+      turn_strategy,
+      display,
+    ) {
+      Some(attempt_word) => attempt_word,
+      None => {
+        println!("No words match, check the marks");
+        continue;
       }
+    };
+    let attempt_word = within_limit(
+      attempt_word,
+      &session.suggestions,
+      &allowed,
+      &session.candidates,
+      session.board.len(),
+      display,
+    );
+    println!("Suggest you try {:?}", attempt_word);
+    update_overlay(&overlay, session, strategy, display);
+    if let Some(file) = files.save {
+      fs::write(file, game_state(session, strategy).to_string())
+        .map_err(|err| SolverError::io(file, err))?;
     }
   }
-}
 
-fn read_dict(file: &str) -> Vec<DictString> {
-  read_lines(file)
-    .unwrap()
-    .map(|l| l.unwrap())
-    .filter(|l| l.chars().count() == 5 && &l.to_lowercase() == l)
-    .collect()
+  Ok(())
 }
 
-/// A wordle solver
-#[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None)]
-struct Args {
-  /// Path to the word dictionary to use
-  #[clap(short, long, default_value = "words.txt")]
-  dict: String,
-
-  /// Path to a reduced guess dictionary to use
-  #[clap(long)]
-  guesses: Option<String>,
+/// Where the game of `session` stands, to save it
+fn game_state(session: &Session, strategy: &dyn Strategy) -> GameState {
+  GameState {
+    history: session.board.clone(),
+    candidates: session
+      .candidates
+      .iter()
+      .map(|word| word.to_string())
+      .collect(),
+    strategy: strategy.name(),
+  }
+}
 
-  /// Use a gambling strategy (instead of a best-average case default)
-  #[clap(short, long)]
-  gambling: Option<f64>,
+/// Puts `session` back where the saved game was and shows the suggestions from there. Saved
+/// candidates missing from the answer dictionary are dropped.
+fn resume<'a>(
+  session: &mut Session<'a>,
+  state: &GameState,
+  dictionary: &[&'a DictString],
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+) {
+  let saved: HashSet<&str> = state.candidates.iter().map(String::as_str).collect();
+  session.candidates.retain(|word| saved.contains(word));
+  for (word, marks) in &state.history {
+    session.constraints.apply(word, marks);
+    session.board.push((word.clone(), marks.clone()));
+  }
+  println!(
+    "Resumed after {} guesses, {} candidates left",
+    state.history.len(),
+    session.candidates.len()
+  );
+  for (word, marks) in &state.history {
+    print_tiles(word, marks, display);
+  }
 
-  /// Use the worst case strategy (instead of best average case default). Good against Absurdle
-  #[clap(short, long)]
-  pessimistic: bool,
+  let allowed = allowed_words(dictionary, session, display);
+  let strategy = strategy
+    .at_turn(session.board.len() + 1, session.candidates.len())
+    .unwrap_or(strategy);
+  let (suggestions, guesses) = get_suggestions(&allowed, &session.candidates, strategy);
+  session.suggestions = suggestions;
+  session.guesses = guesses;
+  print_suggestions(
+    &session.suggestions,
+    &session.guesses,
+    &session.candidates,
+    display,
+  );
+}
 
-  /// Disables interactive mode and replays a game to guess the specified word
-  #[clap(short, long)]
-  word: Option<String>,
+/// Who answers the guesses of [`play_word`]
+enum Host {
+  /// Marks the guesses against this answer, with -w
+  Word(String),
+  /// Keeps the most candidates it can like Absurdle, with --absurdle
+  Absurdle,
 }
 
-fn main() {
-  let args = Args::parse();
+impl Host {
+  /// The marks `guess` gets, `candidates` being the words still consistent with the game. With
+  /// --fibble one mark, drawn at random, is a lie unless the guess is the answer.
+  fn feedback(
+    &self,
+    guess: &str,
+    candidates: &[&DictString],
+    display: &DisplayOptions,
+  ) -> Vec<Mark> {
+    let mut marks = match self {
+      Host::Word(word) => engine::compute_bucket(guess, word),
+      Host::Absurdle => adversarial::absurdle_feedback(guess, candidates),
+    };
+    if display.fibble && marks.iter().any(|&mark| mark != Mark::RightPosition) {
+      let mut rng = display.rng.lock().unwrap();
+      let position = rng.below(marks.len());
+      let lies: Vec<Mark> = [Mark::NotPresent, Mark::WrongPosition, Mark::RightPosition]
+        .iter()
+        .copied()
+        .filter(|&mark| mark != marks[position])
+        .collect();
+      marks[position] = lies[rng.below(lies.len())];
+    }
+    marks
+  }
+}
 
-  let dictionary: Vec<DictString> = read_dict(&args.dict);
+/// Plays a game against `host`, returning the number of tries if it was solved
+fn play_word(
+  host: &Host,
+  dictionary: &WordArena,
+  reducing_dictionary: &WordArena,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+) -> Option<usize> {
+  let mut dict_ref: Vec<&DictString> = dictionary.iter().collect();
+  let mut reducing_dict_ref: Vec<&DictString> = reducing_dictionary.iter().collect();
+  let mut constraints = Constraints::new();
+  let mut previous_top: Vec<&str> = vec![];
+  let mut last_feedback: Option<(usize, Vec<Constraint>)> = None;
+  let mut rows: Vec<Vec<Mark>> = vec![];
+  let mut board: Vec<(String, Vec<Mark>)> = vec![];
 
-  let dictionary_reduced: Vec<DictString> = match args.guesses {
-    None => dictionary.clone(),
-    Some(file) => read_dict(&file),
-  };
+  let first_turn = progress_bar::Bar::new("Scoring guesses");
 
-  let strategy = match (args.gambling, args.pessimistic) {
-    (None, false) => Strategy::Average,
-    (None, true) => Strategy::WorstCase,
-    (Some(factor), false) => Strategy::Gambling(factor),
-    (_, _) => {
-      panic!("Wrong set of options")
+  let mut tries = 0;
+  loop {
+    let strategy = strategy
+      .at_turn(tries + 1, reducing_dict_ref.len())
+      .unwrap_or(strategy);
+    let ordered = display
+      .tie_break
+      .order(&dict_ref, &reducing_dict_ref, &display.frequencies);
+    let (ref suggestions, ref guesses) = if tries == 0 {
+      get_suggestions_with_progress(&ordered, &reducing_dict_ref, strategy, |p| {
+        first_turn.update(&p)
+      })
+    } else {
+      get_suggestions(&ordered, &reducing_dict_ref, strategy)
+    };
+
+    let current_top = top_words(suggestions);
+    if let (true, Some((candidates_before, learned))) = (display.verbose, last_feedback.take()) {
+      print!(
+        "{}",
+        StateDiff::new(
+          candidates_before,
+          reducing_dict_ref.len(),
+          learned,
+          &previous_top,
+          &current_top
+        )
+      );
+    }
+    previous_top = current_top;
+
+    if guesses.is_empty() {
+      println!("Stumped, cannot figure it out");
+      return None;
+    } else if let [(answer, _)] = guesses.as_slice() {
+      tries += 1;
+      println!("Got it on try {:?}! The answer is: {:?}", tries, answer);
+      rows.push(vec![Mark::RightPosition; WORD_LENGTH]);
+      println!("{}", display.theme.share_grid(&rows));
+      return Some(tries);
+    } else {
+      print_suggestions(suggestions, guesses, &reducing_dict_ref, display);
+      print_bounds(strategy, reducing_dict_ref.len());
+      print_largest_groups(strategy, suggestions, reducing_dict_ref.len());
+
+      let attempt_word = match pick_attempt(suggestions, guesses, strategy, display) {
+        Some(attempt_word) => attempt_word,
+        None => {
+          println!("Stumped, cannot figure it out");
+          return None;
+        }
+      };
+      let attempt_word = within_limit(
+        attempt_word,
+        suggestions,
+        &dict_ref,
+        &reducing_dict_ref,
+        tries,
+        display,
+      );
+      let attempt_word = hard_attempt(attempt_word, suggestions, &board, display);
+      tries += 1;
+
+      println!("Try {:?}, word {:?}", tries, attempt_word);
+
+      let outcome = host.feedback(attempt_word, &reducing_dict_ref, display);
+      print_tiles(attempt_word, &outcome, display);
+      rows.push(outcome.clone());
+      board.push((attempt_word.to_string(), outcome.clone()));
+
+      if outcome == vec![Mark::RightPosition; WORD_LENGTH] {
+        println!("Actually guessed it!");
+        println!("{}", display.theme.share_grid(&rows));
+        return Some(tries);
+      } else {
+        println!("Outcome: {:?}", outcome);
+
+        let candidates_before = reducing_dict_ref.len();
+        reducing_dict_ref = debug_span!("reducing", candidates = candidates_before)
+          .in_scope(|| reduce_candidates(attempt_word, &outcome, &reducing_dict_ref, display));
+        debug!(
+          "{} left {} of {} candidates",
+          attempt_word,
+          reducing_dict_ref.len(),
+          candidates_before
+        );
+        let learned = if display.fibble {
+          vec![]
+        } else {
+          constraints.apply(attempt_word, &outcome)
+        };
+        last_feedback = Some((candidates_before, learned));
+        if display.hard {
+          dict_ref.retain(|word| rule_violation(word, attempt_word, &outcome, display).is_none());
+        }
+      }
+    }
+  }
+}
+
+/// Plays against `host`, then with --loop against new answers drawn from `reducing_dictionary`
+/// (or Absurdle again), tallying the results after every game
+fn play_games(
+  host: Host,
+  dictionary: &WordArena,
+  reducing_dictionary: &WordArena,
+  strategy: &dyn Strategy,
+  display: &DisplayOptions,
+  looping: bool,
+) {
+  let answers: Vec<&DictString> = reducing_dictionary.iter().collect();
+  let mut tally = Tally::default();
+  let mut host = host;
+  loop {
+    let tries = play_word(&host, dictionary, reducing_dictionary, strategy, display);
+    if !looping {
+      return;
+    }
+    tally.record(tries);
+    tally.print();
+
+    if let Host::Word(_) = host {
+      match display.rng.lock().unwrap().choose(&answers) {
+        Some(answer) => host = Host::Word(answer.to_string()),
+        None => return,
+      }
+    }
+    println!("New game");
+  }
+}
+
+/// Reads the words of the language of [`WORD_LENGTH`] letters, spelled as the game spells them
+fn read_dict(file: &str, language: &Language) -> Result<WordArena, SolverError> {
+  let lines = read_lines(file).map_err(|err| SolverError::io(file, err))?;
+  dict_words(file, lines, language, Some(WORD_LENGTH))
+}
+
+/// Like [`read_dict`], but the words of `length` letters, by default as [`dict_words`] picks it,
+/// and the bundled `list` of `language_name` if `file` is `bundled`
+fn load_dict(
+  file: &str,
+  list: bundled::List,
+  language_name: &str,
+  language: &Language,
+  length: Option<usize>,
+) -> Result<WordArena, SolverError> {
+  if file != bundled::NAME {
+    let lines = read_lines(file).map_err(|err| SolverError::io(file, err))?;
+    return dict_words(file, lines, language, length);
+  }
+  let text = bundled::words(language_name, list).map_err(SolverError::Command)?;
+  dict_words(
+    file,
+    text.lines().map(|line| Ok(line.to_string())),
+    language,
+    length,
+  )
+}
+
+/// The words of the language with `length` letters among `lines`, read from `file`. Without a
+/// length, [`WORD_LENGTH`] if some words have it and otherwise the length most words have.
+fn dict_words<I>(
+  file: &str,
+  lines: I,
+  language: &Language,
+  length: Option<usize>,
+) -> Result<WordArena, SolverError>
+where
+  I: Iterator<Item = io::Result<String>>,
+{
+  let mut spelled = vec![];
+  // The words of each length, to pick the length or tell what the dictionary is for when none fits
+  let mut lengths: HashMap<usize, usize> = HashMap::new();
+  for line in lines {
+    let line = line.map_err(|err| SolverError::io(file, err))?;
+    if line.to_lowercase() != line {
+      continue;
+    }
+    let word = language.fold(&line);
+    if !language.spells(&word) {
+      continue;
     }
+    *lengths.entry(word.chars().count()).or_default() += 1;
+    spelled.push(word);
+  }
+
+  let most = lengths
+    .iter()
+    .max_by_key(|&(&length, &count)| (count, length))
+    .map(|(&length, _)| length);
+  let wanted = match (length, most) {
+    (Some(length), _) => length,
+    (None, Some(most)) if !lengths.contains_key(&WORD_LENGTH) => most,
+    (None, _) => WORD_LENGTH,
   };
+  let mut seen = HashSet::new();
+  let mut words = WordArena::new();
+  for word in spelled {
+    if word.chars().count() == wanted && seen.insert(word.clone()) {
+      words.push(&word);
+    }
+  }
 
-  match args.word {
-    None => {
-      return interactive(dictionary, dictionary_reduced, strategy);
+  if words.is_empty() {
+    return Err(SolverError::EmptyDictionary {
+      path: file.to_string(),
+      wanted,
+      length: most,
+    });
+  }
+  Ok(words)
+}
+
+/// A wordle solver
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+  /// Path to the word dictionary to use, or `bundled` for Wordle's English guesses built into the
+  /// program (with the bundled feature)
+  #[clap(short, long, default_value = "words.txt")]
+  dict: String,
+
+  /// Serve a page showing the interactive game as it goes on this port, e.g. for a stream
+  /// overlay
+  #[clap(long)]
+  overlay: Option<u16>,
+
+  /// Path to a reduced guess dictionary to use, or `bundled` for Wordle's English answers built
+  /// into the program (with the bundled feature)
+  #[clap(long)]
+  guesses: Option<String>,
+
+  /// Letters in the words of the game, or digits with --primel. By default 5 when the dictionary
+  /// has 5 letter words, and otherwise the length most of its words have. Games of other lengths,
+  /// up to 8, are scored by the strategies of the groups of candidates or of letter frequencies,
+  /// played interactively, with --word, benchmark, peaks or warmle
+  #[clap(long)]
+  length: Option<usize>,
+
+  /// Rank guesses with the strategy of this name, its parameters after a colon, e.g. gambling:0.5
+  /// or lookahead:2. Strategies are named average, worst-case, gambling, risk, largest-group,
+  /// max-splits, expected-tiles, positional-frequency, least-information, fibble, optimal,
+  /// expected-score, speedrun, fewest-failures, lookahead or adversarial, the strategy flags like
+  /// --pessimistic being shorthands for them
+  #[clap(long)]
+  strategy: Option<String>,
+
+  /// Use a gambling strategy (instead of a best-average case default)
+  #[clap(short, long)]
+  gambling: Option<f64>,
+
+  /// Use the worst case strategy (instead of best average case default). Good against Absurdle
+  #[clap(short, long)]
+  pessimistic: bool,
+
+  /// Blend the worst case and the average: score guesses by this much of the information in the
+  /// worst case plus the rest of the average information, from 0 (the default average) to 1 (the
+  /// worst case of --pessimistic)
+  #[clap(long)]
+  risk: Option<f64>,
+
+  /// Rank guesses by the number of guesses to pin the answer against Absurdle, which keeps the
+  /// largest group of candidates, looking this many guesses ahead. Scores are minus that number
+  #[clap(long)]
+  adversarial: Option<usize>,
+
+  /// Use different strategies along the game, e.g. "average until turn 2, worst-case until 20
+  /// candidates, optimal". Stages end "until turn <n>" or "until <n> candidates", the last goes
+  /// on to the end. Strategies are named average, worst-case, gambling <factor>, risk <r>,
+  /// largest-group, max-splits, expected-tiles, positional-frequency, least-information, fibble,
+  /// optimal, expected-score <failure score>, speedrun <turns>, fewest-failures, lookahead
+  /// <depth> or adversarial <depth>
+  #[clap(long)]
+  schedule: Option<String>,
+
+  /// Once this many candidates or fewer are left, rank guesses by the exact expected number of
+  /// guesses to win, as --optimal does but trying every guess, whatever the strategy picked
+  /// before. 50 takes a few seconds a turn
+  #[clap(long)]
+  endgame: Option<usize>,
+
+  /// Play the guesses of a decision tree file, one line per answer like "salet bbbyb courd bybbb
+  /// ninth ggggg". Where the tree doesn't go, guesses are ranked by the strategy picked
+  #[clap(long)]
+  tree: Option<String>,
+
+  /// Rank guesses by the number of candidates in the largest group they leave, fewer is better,
+  /// ties broken by the default average information. Scores are minus that number
+  #[clap(long)]
+  largest_group: bool,
+
+  /// Rank guesses by the number of groups they split the candidates into, ties broken by the
+  /// default average information
+  #[clap(long)]
+  max_splits: bool,
+
+  /// Rank guesses by the expected number of green and yellow tiles they get. Much faster than
+  /// the default but less accurate
+  #[clap(long)]
+  expected_tiles: bool,
+
+  /// Rank guesses by an information estimate computed from letter/position frequencies, fast
+  /// enough for dictionaries of 100k+ words
+  #[clap(long)]
+  positional_frequency: bool,
+
+  /// Play Fibble, where one mark of every pattern is a lie: keep the candidates whose pattern is
+  /// one mark away from the one given, and rank guesses by the information the lying patterns
+  /// tell, unless another strategy is picked
+  #[clap(long)]
+  fibble: bool,
+
+  /// Play Antiwordle: suggest the guesses telling the least about the answer, among those its
+  /// rules allow: hard mode's, no letter found absent and no word played again
+  #[clap(long)]
+  anti: bool,
+
+  /// Rank guesses as --positional-frequency does while more than 500 candidates are left, then
+  /// with the strategy picked. Speeds up the first turns with large dictionaries
+  #[clap(long)]
+  fast: bool,
+
+  /// Rank guesses by the expected number of guesses to win, searching the game tree. Scores are
+  /// minus that number. Takes about 20 seconds to pick the first guess
+  #[clap(long)]
+  optimal: bool,
+
+  /// Rank guesses by the expected score of the game, searching the game tree: the turn the answer
+  /// is found on, or --failure-score when it isn't within 6. Scores are minus that number
+  #[clap(long)]
+  expected_score: bool,
+
+  /// What not finding the answer within 6 guesses scores with --expected-score
+  #[clap(long, default_value = "7")]
+  failure_score: f64,
+
+  /// Rank guesses by the chance of finding the answer by --target-turn, searching the game tree,
+  /// and past that turn by the expected score of --expected-score. Scores are minus the chance of
+  /// missing it
+  #[clap(long)]
+  speedrun: bool,
+
+  /// The turn --speedrun tries to find the answer by
+  #[clap(long, default_value = "3")]
+  target_turn: usize,
+
+  /// Rank guesses by the chance of not finding the answer within 6, searching the game tree, ties
+  /// broken by the expected score. Scores are minus the expected score, failing scoring a million
+  #[clap(long)]
+  fewest_failures: bool,
+
+  /// Rank guesses by the information of the guess and of the best follow-ups to it, this many
+  /// guesses in all, in bits. 1 is the default strategy, 2 avoids openers that look good but leave
+  /// groups hard to split and takes minutes to pick the first guess. From 4 on, the game tree is
+  /// searched exactly once 100 candidates or fewer are left
+  #[clap(long, default_value = "1")]
+  depth: usize,
+
+  /// Path to answer priors (see the learn-priors subcommand), `nyt` for a built-in model of New
+  /// York Times answers or `frequency` to favor the words most common according to
+  /// --frequencies. Weighs each candidate by how likely it is to be the answer when computing the
+  /// average information. Frequency tiers only count with --frequencies
+  #[clap(long)]
+  priors: Option<String>,
+
+  /// How much the word to try favors the best candidate over the best guess, which may not be
+  /// the answer: the candidate's chance of being the answer times this many bits is added to its
+  /// score. 0 always plays the best guess [default: 1]
+  #[clap(long)]
+  lambda: Option<f64>,
+
+  /// Only play words that still find the answer within 6 tries whatever it is, as far as a
+  /// bounded search can tell, instead of the best ranked word when it might not
+  #[clap(long)]
+  guarantee: bool,
+
+  /// Draw the word to try at random instead of playing the best, the better scoring words more
+  /// often: the higher the temperature, the more often others than the best. Scores are in bits
+  /// for most strategies, where 0.02 varies the openers at little cost and 0.1 plays much worse
+  #[clap(long)]
+  temperature: Option<f64>,
+
+  /// Seed of everything drawn at random (words to try with --temperature, answers to practice
+  /// or sample in benchmarks), to play the same games again. Seeded from the clock by default
+  #[clap(long)]
+  seed: Option<u64>,
+
+  /// Play in hard mode: only suggest words that keep the greens and use the letters found so
+  /// far, and score guesses by how well the words hard mode still allows follow them up
+  #[clap(long)]
+  hard: bool,
+
+  /// Disables interactive mode and replays a game to guess the specified word
+  #[clap(short, long)]
+  word: Option<String>,
+
+  /// Once a game with --word, --absurdle or the host subcommand ends, start another with an
+  /// answer of the reduced dictionary drawn at random, showing the results of all the games
+  #[clap(long = "loop")]
+  loop_games: bool,
+
+  /// Disables interactive mode and plays against Absurdle, which keeps as many candidates as it
+  /// can with each feedback rather than a fixed answer
+  #[clap(long)]
+  absurdle: bool,
+
+  /// Only suggest words from the reduced guess dictionary, i.e. words that can be the answer
+  #[clap(long)]
+  answers_only: bool,
+
+  /// Play Primel: the words are the primes of 5 digits, generated rather than read from --dict
+  /// and --guesses
+  #[clap(long)]
+  primel: bool,
+
+  /// File of words the game refused, left out of the dictionaries. The `rejected` command of the
+  /// interactive mode adds to it
+  #[clap(long)]
+  ban_list: Option<String>,
+
+  /// Save the interactive game to this file after every turn
+  #[clap(long)]
+  save: Option<String>,
+
+  /// Continue the interactive game saved in this file with --save
+  #[clap(long)]
+  resume: Option<String>,
+
+  /// Only guess words spelled with these letters, e.g. `aeioustrln`, for letter bank and other
+  /// house rules. Answers are limited to them too, as others could never be guessed
+  #[clap(long)]
+  allowed_letters: Option<String>,
+
+  /// Letter rules of the game: en, es, de, fr, or a rule file of `alphabet <letters>` and
+  /// `fold <from> <to>` lines, e.g. `fold é e` to play é as e
+  #[clap(long, default_value = "en")]
+  language: String,
+
+  /// Tile colors and emoji squares: classic, high-contrast, colorblind, or a file of
+  /// `<right|wrong|absent> #rrggbb <emoji>` lines
+  #[clap(long, default_value = "classic")]
+  theme: String,
+
+  /// Show suggestions as a table with these columns, from word, bits, worst (candidates left in
+  /// the worst case), remaining (expected candidates left), candidate (can be the answer),
+  /// frequency and probability (of being the answer)
+  #[clap(long)]
+  columns: Option<Columns>,
+
+  /// Order the shown suggestions by bits (the strategy's score), worst (fewest candidates left
+  /// in the worst case), expected (fewest candidates expected to be left) or frequency. The word
+  /// to try still follows the strategy
+  #[clap(long, default_value = "bits")]
+  sort: SortKey,
+
+  /// Rank suggestions and pick the word to try by several objectives, each breaking the ties of
+  /// those before it, e.g. "entropy,candidate,freq". Objectives are entropy (the strategy's
+  /// score), candidate (the chance of being the answer, see --priors), freq (see --frequencies),
+  /// worst and expected (fewest candidates left in the worst case and on average)
+  #[clap(long)]
+  rank: Option<Rank>,
+
+  /// Order guesses with equal scores as listed in the dictionary, alphabetical, by frequency (see
+  /// --frequencies), candidates-first (words that can still be the answer) or fewest-repeats
+  /// (fewest repeated letters)
+  #[clap(long, default_value = "listed")]
+  tie_break: TieBreak,
+
+  /// Path to a file of word frequencies, one `word count` pair per line
+  #[clap(long)]
+  frequencies: Option<String>,
+
+  /// Only recommend words among the given percentage of most common words of the dictionary
+  /// (see --frequencies), e.g. 50 for the commoner half. The best word overall is still mentioned
+  #[clap(long)]
+  max_obscurity: Option<f64>,
+
+  /// After each feedback, show how many candidates were eliminated, what was learned about the
+  /// word and which top suggestions dropped out. Also explains on stderr why each word was
+  /// picked, and with -vv how long each phase took
+  #[clap(short, long, parse(from_occurrences))]
+  verbose: u64,
+
+  /// Only show the results: no progress while scoring and no diagnostics but errors
+  #[clap(short, long)]
+  quiet: bool,
+
+  #[clap(subcommand)]
+  command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+  /// Full screen interface with the board, a keyboard, suggestions and candidates (needs the
+  /// `tui` feature)
+  Tui,
+  /// Serve a point-and-click web interface
+  Web {
+    /// Port to listen on
+    #[clap(long, default_value = "8080")]
+    port: u16,
+
+    /// Number of game states whose suggestions are kept for other requests
+    #[clap(long, default_value = "1024")]
+    cache: usize,
+
+    /// Directory of the WebAssembly build made by wasm-bindgen, run by the page to solve in the
+    /// browser. Without it the page asks the server
+    #[clap(long, default_value = "pkg")]
+    pkg: String,
+  },
+  /// Analyze a game in progress
+  Analyze {
+    #[clap(subcommand)]
+    analysis: Analysis,
+  },
+  /// Play against the answers with the chosen strategy and report how many guesses it needs
+  Benchmark {
+    /// Play this many answers drawn at random instead of every answer once
+    #[clap(long)]
+    sample: Option<usize>,
+
+    /// Draw common answers more often, in proportion to their --frequencies
+    #[clap(long)]
+    by_frequency: bool,
+
+    /// Number of hardest answers to list along with the guesses played
+    #[clap(long, default_value = "10")]
+    worst: usize,
+
+    /// Play this word first in every game
+    #[clap(long)]
+    opener: Option<String>,
+
+    /// Write one CSV row per answer to this file, grouped by the marks the first guess got, with
+    /// the second guess played for each group
+    #[clap(long)]
+    export: Option<String>,
+
+    /// Exit with an error when an answer isn't solved within six guesses
+    #[clap(long)]
+    require_all_solved: bool,
+  },
+  /// Find guesses giving the colors of a finished game, row by row, for the given answer
+  Crosswordle {
+    /// The answer of the game
+    #[clap(long)]
+    answer: String,
+
+    /// The marks of a row, e.g. `-+--o`, from top to bottom, can be repeated
+    #[clap(long)]
+    row: Vec<String>,
+
+    /// Each guess must keep the greens and use the letters found by the rows above it
+    #[clap(long)]
+    hard: bool,
+  },
+  /// Fit answer priors (frequency tier weights) to the answers of past games, for --priors
+  LearnPriors {
+    /// Practice stats file to learn from, can be repeated
+    #[clap(long)]
+    log: Vec<String>,
+
+    /// File listing past answers one per line, can be repeated
+    #[clap(long)]
+    answers: Vec<String>,
+
+    /// Where to save the fitted priors
+    #[clap(long, default_value = "priors.txt")]
+    out: String,
+  },
+  /// List the words of the reduced guess dictionary matching the given letters, without a
+  /// history of guesses
+  List {
+    /// Known letters by position with `.` for the others, e.g. `..a.e`
+    #[clap(long)]
+    green: Option<String>,
+
+    /// Letters in the word but not at the given positions (counted from 1), e.g. `r:1,3 e:2`
+    #[clap(long)]
+    yellow: Option<String>,
+
+    /// Letters not in the word, e.g. `stln`
+    #[clap(long)]
+    gray: Option<String>,
+  },
+  /// Find the best second guess after an opener for every feedback it can get
+  OpeningBook {
+    /// The first guess
+    #[clap(long)]
+    opener: String,
+
+    /// Output format: text or csv
+    #[clap(long, default_value = "text")]
+    format: BookFormat,
+
+    /// File to write the book to instead of printing it
+    #[clap(long)]
+    output: Option<String>,
+  },
+  /// Play several boards at once with the same guesses, like Quordle
+  Quordle {
+    /// Number of boards
+    #[clap(long, default_value = "4")]
+    boards: usize,
+
+    /// Number of guesses allowed, by default that of the variant with this many boards: 5 more
+    /// than the boards, e.g. 9 for Quordle or 13 for Octordle
+    #[clap(long)]
+    budget: Option<usize>,
+
+    /// How much solving a board counts against information: close-out (finish the board closest
+    /// to done first), balanced, joint-info (information summed over the boards only) or the
+    /// number of bits a solved board is worth
+    #[clap(long, default_value = "balanced")]
+    policy: BoardPolicy,
+  },
+  /// Play two answers sharing no letter on one board, like Xordle
+  Xordle,
+  /// Play Wordle Peaks, where the marks tell whether the answer's letters come earlier or later
+  /// in the alphabet
+  Peaks,
+  /// Play Warmle, where the marks tell whether the answer's letters are close to the guessed ones
+  /// in the alphabet
+  Warmle {
+    /// How many letters away in the alphabet a letter is still warm
+    #[clap(long, default_value = "3")]
+    distance: u32,
+  },
+  /// Play against the program with answers of the chosen difficulty, tracking results per tier
+  Practice {
+    /// Difficulty of the answers: easy, medium or hard
+    #[clap(long, default_value = "medium")]
+    tier: Tier,
+
+    /// File the results are appended to
+    #[clap(long, default_value = "practice-stats.txt")]
+    stats: String,
+
+    /// Pick common answers more often, in proportion to their --frequencies
+    #[clap(long)]
+    by_frequency: bool,
+  },
+  /// Keep a secret answer and color your guesses, playing Wordle instead of solving it
+  Host {
+    /// Number of guesses allowed per game
+    #[clap(long, default_value = "6")]
+    max_guesses: usize,
+
+    /// Pick no answer and mark each guess to keep as many answers possible as can be, like
+    /// Absurdle, settling on one only when forced
+    #[clap(long)]
+    evil: bool,
+  },
+  /// Play every answer and write the guess played after each feedback, to play again with --tree
+  Tree {
+    /// Play this word first in every game
+    #[clap(long)]
+    opener: Option<String>,
+
+    /// Output format: text (as read by --tree) or json
+    #[clap(long, default_value = "text")]
+    format: TreeFormat,
+
+    /// File to write the tree to instead of printing it
+    #[clap(long)]
+    output: Option<String>,
+  },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Analysis {
+  /// Exact probability of solving within the given number of turns when following the
+  /// suggestions
+  Odds {
+    /// A guess played so far and its marks, e.g. `raise -+--o`, can be repeated
+    #[clap(long)]
+    history: Vec<String>,
+
+    /// Number of guesses left
+    #[clap(long)]
+    turns_left: usize,
+  },
+  /// For each guess of a game, how many bits it got compared to the best guess available
+  Game {
+    /// A guess played and its marks, e.g. `raise -+--o`, can be repeated
+    #[clap(long)]
+    history: Vec<String>,
+
+    /// File with the guesses played, one `word marks` line per guess, after --history
+    #[clap(long)]
+    transcript: Option<String>,
+  },
+  /// Compare games played by people with the solver's on the same answers, and where people
+  /// lose bits
+  Humans {
+    /// CSV file of games, with `answer` and `guesses` columns (guesses separated by spaces)
+    #[clap(long)]
+    games: String,
+  },
+}
+
+fn main() {
+  if let Err(err) = run(Args::parse()) {
+    eprintln!("error: {}", err);
+    std::process::exit(2);
+  }
+}
+
+/// Guesses the optimal search tries at each turn. Enough to find the best known average of
+/// 3.4212 guesses with the standard word lists.
+const OPTIMAL_BREADTH: usize = 10;
+
+/// Follow-ups the lookahead of --depth tries in each group of candidates, besides the group's
+/// words
+const LOOKAHEAD_BREADTH: usize = 20;
+
+/// Follow-ups the search of --adversarial tries in each group of candidates, besides the words
+/// of small groups
+const ADVERSARIAL_BREADTH: usize = 20;
+
+/// What failing scores with --expected-score and `expected-score` without a parameter
+const DEFAULT_FAILURE_SCORE: f64 = 7.0;
+
+/// The turn --speedrun and `speedrun` without a parameter try to find the answer by
+const DEFAULT_TARGET_TURN: usize = 3;
+
+/// The strategy of --speedrun: the best chance of finding the answer by `turns`, then the lowest
+/// expected score
+fn speedrun<S: AsRef<str>>(allowed: &[S], turns: usize) -> Result<Schedule, String> {
+  if !(1..=benchmark::MAX_TRIES).contains(&turns) {
+    return Err("the target turn goes from 1 to 6".to_string());
+  }
+  let within = GameScore::within(allowed, turns).with_breadth(OPTIMAL_BREADTH);
+  let expected_score = GameScore::expected_score(allowed, DEFAULT_FAILURE_SCORE);
+  Ok(
+    Schedule::new(within, Until::Turn(turns))
+      .then(expected_score.with_breadth(OPTIMAL_BREADTH), Until::End),
+  )
+}
+
+/// Candidates left from which --fast scores with the strategy picked rather than the letter
+/// frequencies. Few enough for the exact scoring to take a moment even with 100k+ allowed words.
+const FAST_CANDIDATES: usize = 500;
+
+/// The strategies of --strategy and --schedule by name, the searches needing the words they can
+/// guess
+fn strategy_registry(dictionary: &WordArena) -> StrategyRegistry {
+  let allowed: Arc<Vec<String>> = Arc::new(dictionary.iter().map(str::to_string).collect());
+  let mut registry = StrategyRegistry::builtin();
+  let words = allowed.clone();
+  registry.register("optimal", move |params| match params {
+    [] => Ok(Box::new(Optimal::new(&words).with_breadth(OPTIMAL_BREADTH))),
+    _ => Err("optimal takes no parameter".to_string()),
+  });
+  let words = allowed.clone();
+  registry.register("expected-score", move |params| {
+    let failed = match params {
+      [] => DEFAULT_FAILURE_SCORE,
+      _ => strategy_param("expected-score", params)?,
+    };
+    if failed <= benchmark::MAX_TRIES as f64 {
+      return Err(
+        "the failure score must be above 6, the worst score of a solved game".to_string(),
+      );
     }
-    Some(word) => {
-      return play_word(word, dictionary, dictionary_reduced, strategy);
+    let strategy = GameScore::expected_score(&words, failed);
+    Ok(Box::new(strategy.with_breadth(OPTIMAL_BREADTH)))
+  });
+  let words = allowed.clone();
+  registry.register("speedrun", move |params| {
+    let turns = match params {
+      [] => DEFAULT_TARGET_TURN,
+      _ => strategy_param("speedrun", params)?,
+    };
+    Ok(Box::new(speedrun(&words, turns)?))
+  });
+  let words = allowed.clone();
+  registry.register("fewest-failures", move |params| match params {
+    [] => Ok(Box::new(
+      GameScore::fewest_failures(&words).with_breadth(OPTIMAL_BREADTH),
+    )),
+    _ => Err("fewest-failures takes no parameter".to_string()),
+  });
+  let words = allowed.clone();
+  registry.register("lookahead", move |params| {
+    let depth = strategy_param("lookahead", params)?;
+    Ok(Box::new(Lookahead::new(&words, depth, LOOKAHEAD_BREADTH)))
+  });
+  registry.register("adversarial", move |params| {
+    let depth = strategy_param("adversarial", params)?;
+    Ok(Box::new(Adversarial::new(
+      &allowed,
+      depth,
+      ADVERSARIAL_BREADTH,
+    )))
+  });
+  registry
+}
+
+/// The registry specs of --strategy and of the strategy flags, shorthands for them
+fn strategy_specs(args: &Args) -> Vec<String> {
+  let flags = [
+    (args.pessimistic, "worst-case"),
+    (args.largest_group, "largest-group"),
+    (args.max_splits, "max-splits"),
+    (args.expected_tiles, "expected-tiles"),
+    (args.positional_frequency, "positional-frequency"),
+    (args.anti, "least-information"),
+    (args.optimal, "optimal"),
+    (args.fewest_failures, "fewest-failures"),
+  ];
+  let mut specs: Vec<String> = args.strategy.iter().cloned().collect();
+  specs.extend(args.gambling.map(|factor| format!("gambling:{}", factor)));
+  specs.extend(args.risk.map(|risk| format!("risk:{}", risk)));
+  specs.extend(
+    args
+      .adversarial
+      .map(|depth| format!("adversarial:{}", depth)),
+  );
+  if args.expected_score {
+    specs.push(format!("expected-score:{}", args.failure_score));
+  }
+  if args.speedrun {
+    specs.push(format!("speedrun:{}", args.target_turn));
+  }
+  specs.extend(
+    flags
+      .iter()
+      .filter(|&&(given, _)| given)
+      .map(|&(_, name)| name.to_string()),
+  );
+  specs
+}
+
+/// The most detailed diagnostics shown on stderr: errors only with `-q`, what each word was
+/// picked for with `-v`, and with `-vv` how long scoring, reducing and searching took
+fn log_level(quiet: bool, verbosity: u64) -> Level {
+  match (quiet, verbosity) {
+    (true, _) => Level::ERROR,
+    (false, 0) => Level::WARN,
+    (false, 1) => Level::INFO,
+    (false, _) => Level::DEBUG,
+  }
+}
+
+/// Most digits of the primes of --primel, whose sieve takes 10 to that power numbers
+const PRIMEL_DIGITS: usize = 7;
+
+/// Most letters of the words played, the games of each length being compiled apart, see
+/// [`length`]
+const MAX_LENGTH: usize = 8;
+
+/// Plays the game of `args` with words of `length` letters other than [`WORD_LENGTH`], with the
+/// strategies scoring the groups of candidates or letter tables. Only the interactive game,
+/// --word, benchmark, peaks and warmle play them.
+fn play_length(
+  args: Args,
+  dictionary: WordArena,
+  answers: WordArena,
+  length: usize,
+  language: &Language,
+) -> Result<(), SolverError> {
+  let guided = args.guarantee || args.temperature.is_some();
+  if args.priors.is_some() || args.depth > 1 || args.hard || args.fibble || guided {
+    return Err(SolverError::ConflictingOptions(format!(
+      "Words of {} letters are played without --priors, --depth, --hard, --fibble, --guarantee \
+       and --temperature",
+      length
+    )));
+  }
+  let registry = StrategyRegistry::builtin();
+  let mut strategies = SolverSession::builder();
+  for spec in &strategy_specs(&args) {
+    let strategy = registry.create(spec).map_err(|err| {
+      SolverError::Command(format!(
+        "Invalid strategy {} for words of {} letters: {}",
+        spec, length, err
+      ))
+    })?;
+    strategies = strategies.strategy(strategy);
+  }
+  if let Some(schedule) = &args.schedule {
+    let schedule = Schedule::parse(schedule, |name| registry.create(name))
+      .map_err(|err| SolverError::Command(format!("Invalid --schedule: {}", err)))?;
+    strategies = strategies.strategy(schedule);
+  }
+  let strategy = strategies
+    .build_strategy()
+    .map_err(|err| SolverError::ConflictingOptions(err.to_string()))?;
+
+  let frequencies = match &args.frequencies {
+    Some(file) => Frequencies::read(file).map_err(|err| SolverError::io(file, err))?,
+    None => Frequencies::default(),
+  };
+  let play = match (&args.command, args.word.as_deref()) {
+    (None, None) => length::Play::Interactive,
+    (None, Some(word)) if word.chars().count() != length => {
+      return Err(SolverError::ConflictingOptions(format!(
+        "The words of the dictionary have {} letters, {:?} doesn't",
+        length, word
+      )));
+    }
+    (None, Some(word)) => length::Play::Solve(word),
+    (
+      Some(Command::Benchmark {
+        sample,
+        by_frequency,
+        worst,
+        opener,
+        export,
+        require_all_solved,
+      }),
+      _,
+    ) => length::Play::Benchmark(
+      benchmark::Sample {
+        size: *sample,
+        frequencies: Some(&frequencies).filter(|_| *by_frequency),
+        seed: args.seed,
+      },
+      benchmark::Options {
+        opener: opener.as_deref(),
+        worst: *worst,
+        export: export.as_deref(),
+        require_all_solved: *require_all_solved,
+        guarantee: None,
+        temperature: 0.0,
+      },
+    ),
+    (Some(Command::Peaks), _) => {
+      peaks::run(dictionary, answers, length);
+      return Ok(());
+    }
+    (Some(Command::Warmle { distance }), _) => {
+      warmle::run(dictionary, answers, length, *distance, language.alphabet());
+      return Ok(());
+    }
+    (Some(_), _) => {
+      return Err(SolverError::ConflictingOptions(format!(
+        "Only the interactive game, --word, benchmark, peaks and warmle play words of {} letters, \
+         the other subcommands play {} letters",
+        length, WORD_LENGTH
+      )));
+    }
+  };
+
+  let strategy = strategy.as_ref();
+  let (dictionary, answers) = (&dictionary, &answers);
+  match length {
+    1 => length::run::<1>(&play, dictionary, answers, strategy),
+    2 => length::run::<2>(&play, dictionary, answers, strategy),
+    3 => length::run::<3>(&play, dictionary, answers, strategy),
+    4 => length::run::<4>(&play, dictionary, answers, strategy),
+    6 => length::run::<6>(&play, dictionary, answers, strategy),
+    7 => length::run::<7>(&play, dictionary, answers, strategy),
+    8 => length::run::<8>(&play, dictionary, answers, strategy),
+    _ => Err(format!(
+      "Words of {} letters are too long, the games have up to {} letters",
+      length, MAX_LENGTH
+    )),
+  }
+  .map_err(SolverError::Command)
+}
+
+/// A generator seeded with `seed`, or from the clock
+fn rng(seed: Option<u64>) -> Rng {
+  seed.map_or_else(Rng::from_time, Rng::new)
+}
+
+fn run(args: Args) -> Result<(), SolverError> {
+  tracing_subscriber::fmt()
+    .with_writer(io::stderr)
+    .with_max_level(log_level(args.quiet, args.verbose))
+    .with_span_events(FmtSpan::CLOSE)
+    .with_target(false)
+    .with_ansi(io::stderr().is_terminal())
+    .init();
+
+  if args.primel && args.guesses.is_some() {
+    return Err(SolverError::ConflictingOptions(
+      "--primel plays the primes, leave out --guesses".to_string(),
+    ));
+  }
+  let language = if args.primel {
+    Language::digits()
+  } else {
+    Language::load(&args.language)?
+  };
+
+  match args.length {
+    Some(length) if args.primel && !(1..=PRIMEL_DIGITS).contains(&length) => {
+      return Err(SolverError::ConflictingOptions(format!(
+        "--primel plays primes of 1 to {} digits",
+        PRIMEL_DIGITS
+      )));
+    }
+    Some(length) if !(1..=MAX_LENGTH).contains(&length) => {
+      return Err(SolverError::ConflictingOptions(format!(
+        "--length goes from 1 to {}",
+        MAX_LENGTH
+      )));
+    }
+    _ => {}
+  }
+
+  let dictionary: WordArena = if args.primel {
+    primel::primes(args.length.unwrap_or(WORD_LENGTH))
+  } else {
+    let list = bundled::List::Guesses;
+    load_dict(&args.dict, list, &args.language, &language, args.length)?
+  };
+  let length = dictionary
+    .iter()
+    .next()
+    .map_or(WORD_LENGTH, |word| word.chars().count());
+
+  let dictionary_reduced: WordArena = match &args.guesses {
+    None => dictionary.clone(),
+    Some(file) => {
+      let list = bundled::List::Answers;
+      load_dict(file, list, &args.language, &language, Some(length))?
+    }
+  };
+
+  // Practice and hosted games still accept any dictionary word as a guess
+  let practice = matches!(
+    args.command,
+    Some(Command::Practice { .. }) | Some(Command::Host { .. })
+  );
+  let dictionary = if args.answers_only && !practice {
+    dictionary_reduced.clone()
+  } else {
+    dictionary
+  };
+
+  let (dictionary, dictionary_reduced) = match &args.allowed_letters {
+    None => (dictionary, dictionary_reduced),
+    Some(letters) => {
+      let letters = letters.to_lowercase();
+      let spelled = |word: &&str| word.chars().all(|letter| letters.contains(letter));
+      let dictionary: WordArena = dictionary.iter().filter(spelled).collect();
+      let dictionary_reduced: WordArena = dictionary_reduced.iter().filter(spelled).collect();
+      if dictionary_reduced.is_empty() {
+        return Err(SolverError::Command(format!(
+          "No answer can be spelled with the letters {:?}",
+          letters
+        )));
+      }
+      (dictionary, dictionary_reduced)
+    }
+  };
+
+  let (dictionary, dictionary_reduced) = match &args.ban_list {
+    None => (dictionary, dictionary_reduced),
+    Some(file) => {
+      let banned = read_ban_list(file, &language);
+      let kept = |word: &&str| !banned.contains(*word);
+      let dictionary: WordArena = dictionary.iter().filter(kept).collect();
+      let dictionary_reduced: WordArena = dictionary_reduced.iter().filter(kept).collect();
+      (dictionary, dictionary_reduced)
+    }
+  };
+
+  if length != WORD_LENGTH {
+    return play_length(args, dictionary, dictionary_reduced, length, &language);
+  }
+
+  let frequencies = match &args.frequencies {
+    Some(file) => Frequencies::read(file).map_err(|err| SolverError::io(file, err))?,
+    None => Frequencies::default(),
+  };
+
+  // Guesses with equal scores keep the order of the dictionaries
+  let (dictionary, dictionary_reduced) = {
+    let answers: Vec<&str> = dictionary_reduced.iter().collect();
+    let tie_break = args.tie_break;
+    let order = |arena: &WordArena| -> WordArena {
+      let words: Vec<&str> = arena.iter().collect();
+      tie_break
+        .order(&words, &answers, &frequencies)
+        .into_iter()
+        .collect()
+    };
+    (order(&dictionary), order(&dictionary_reduced))
+  };
+
+  let weights: Option<&CandidateWeights> = match &args.priors {
+    None => None,
+    Some(priors) if priors == "frequency" => {
+      if frequencies.is_empty() {
+        return Err(SolverError::ConflictingOptions(
+          "--priors frequency needs --frequencies".to_string(),
+        ));
+      }
+      Some(Box::leak(Box::new(
+        frequencies.rank_weights(&dictionary_reduced),
+      )))
+    }
+    Some(file) => {
+      let model: PriorModel = if file == "nyt" {
+        PriorModel::nyt()
+      } else {
+        std::fs::read_to_string(file)
+          .map_err(|err| SolverError::io(file, err))?
+          .parse()
+          .map_err(|err| SolverError::Command(format!("{}: {}", file, err)))?
+      };
+      // The weights live as long as the program, for the display options to show them too
+      Some(Box::leak(Box::new(
+        frequencies.candidate_weights(&dictionary_reduced, &model),
+      )))
+    }
+  };
+
+  let registry = strategy_registry(&dictionary);
+  let mut strategies = SolverSession::builder();
+  let specs = strategy_specs(&args);
+  for spec in &specs {
+    let strategy = registry
+      .create(spec)
+      .map_err(|err| SolverError::Command(format!("Invalid strategy {}: {}", spec, err)))?;
+    strategies = strategies.strategy(strategy);
+  }
+  if args.depth == 0 {
+    return Err(SolverError::ConflictingOptions(
+      "--depth counts the guesses looked at, from 1".to_string(),
+    ));
+  }
+  let other_strategy = !specs.is_empty() || args.schedule.is_some() || weights.is_some();
+  if args.fibble && !other_strategy {
+    strategies = strategies.strategy(Fibble);
+  }
+  if args.depth > 1 || (args.hard && !other_strategy) {
+    // Hard mode needs a follow-up to see what a guess leaves, by default only the group's words
+    let allowed: Vec<&str> = dictionary.iter().collect();
+    let breadth = if args.depth > 1 { LOOKAHEAD_BREADTH } else { 0 };
+    let lookahead = Lookahead::new(&allowed, args.depth.max(2), breadth).hard_mode(args.hard);
+    strategies = strategies.strategy(lookahead);
+  }
+  if let Some(schedule) = &args.schedule {
+    let schedule = Schedule::parse(schedule, |name| registry.create(name))
+      .map_err(|err| SolverError::Command(format!("Invalid --schedule: {}", err)))?;
+    strategies = strategies.strategy(schedule);
+  }
+  if let Some(weights) = weights {
+    strategies = strategies.strategy(Weighted(weights));
+  }
+  let strategy = strategies.build_strategy().map_err(|err| {
+    SolverError::ConflictingOptions(format!(
+      "{} (--priors only works with the default average strategy)",
+      err
+    ))
+  })?;
+  let strategy: Box<dyn Strategy> = if args.fast {
+    let fast = Schedule::new(PositionalFrequency, Until::Candidates(FAST_CANDIDATES));
+    Box::new(fast.then(strategy, Until::End))
+  } else {
+    strategy
+  };
+  let strategy: Box<dyn Strategy> = match args.endgame {
+    None => strategy,
+    Some(count) => {
+      let allowed: Vec<&str> = dictionary.iter().collect();
+      let endgame = Schedule::new(strategy, Until::Candidates(count));
+      Box::new(endgame.then(Optimal::new(&allowed), Until::End))
+    }
+  };
+  let strategy: Box<dyn Strategy> = match &args.tree {
+    None => strategy,
+    Some(file) => {
+      let tree: DecisionTree = fs::read_to_string(file)
+        .map_err(|err| SolverError::io(file, err))?
+        .parse()
+        .map_err(|err| SolverError::Command(format!("{}: {}", file, err)))?;
+      let answers: Vec<&str> = dictionary_reduced.iter().collect();
+      Box::new(TreeStrategy::new(&tree, &answers, strategy))
+    }
+  };
+  let strategy = strategy.as_ref();
+
+  let familiar = match args.max_obscurity {
+    None => None,
+    Some(percent) if frequencies.is_empty() || !(percent > 0.0 && percent <= 100.0) => {
+      return Err(SolverError::ConflictingOptions(
+        "--max-obscurity takes a percentage above 0 and needs --frequencies".to_string(),
+      ))
+    }
+    Some(percent) => Some(frequencies.common_words(&dictionary, percent)),
+  };
+
+  let theme = Theme::load(&args.theme)?;
+
+  let temperature = args.temperature.unwrap_or(0.0);
+  if !(temperature >= 0.0 && temperature.is_finite()) {
+    return Err(SolverError::ConflictingOptions(
+      "--temperature takes a number from 0 up".to_string(),
+    ));
+  }
+
+  if args.rank.is_some() && (args.sort != SortKey::Bits || temperature > 0.0) {
+    return Err(SolverError::ConflictingOptions(
+      "--rank orders the suggestions and picks the word to try, leave out --sort and \
+       --temperature"
+        .to_string(),
+    ));
+  }
+
+  let display = DisplayOptions {
+    verbose: args.verbose > 0,
+    columns: args.columns,
+    sort: args.sort,
+    rank: args.rank,
+    tie_break: args.tie_break,
+    frequencies,
+    weights,
+    familiar,
+    theme,
+    language,
+    lambda: args.lambda.unwrap_or(DEFAULT_LAMBDA),
+    hard: args.hard || args.anti,
+    anti: args.anti,
+    fibble: args.fibble,
+    guarantee: args.guarantee.then(|| {
+      let allowed: Vec<&str> = dictionary.iter().collect();
+      Guarantee::new(&allowed)
+    }),
+    temperature,
+    rng: Mutex::new(rng(args.seed)),
+  };
+
+  match (args.command, args.word) {
+    (Some(Command::Tui), _) => {
+      #[cfg(feature = "tui")]
+      tui::run(dictionary, dictionary_reduced, strategy, display.theme)
+        .map_err(|err| SolverError::Command(format!("Terminal UI failed: {}", err)))?;
+
+      #[cfg(not(feature = "tui"))]
+      eprintln!("This build doesn't include the tui subcommand, rebuild with --features tui");
+    }
+    (Some(Command::Web { port, cache, pkg }), _) => {
+      let pkg = Path::new(&pkg);
+      web::serve(dictionary, dictionary_reduced, strategy, port, cache, pkg)
+        .map_err(|err| SolverError::Command(format!("Couldn't serve on port {}: {}", port, err)))?;
+    }
+    (
+      Some(Command::List {
+        green,
+        yellow,
+        gray,
+      }),
+      _,
+    ) => {
+      list::run(
+        &dictionary_reduced,
+        green.as_deref(),
+        yellow.as_deref(),
+        gray.as_deref(),
+      )?;
+    }
+    (Some(Command::Analyze { analysis }), _) => {
+      analyze::command(analysis, &dictionary, &dictionary_reduced, strategy)?;
+    }
+    (
+      Some(Command::Benchmark {
+        sample,
+        by_frequency,
+        worst,
+        opener,
+        export,
+        require_all_solved,
+      }),
+      _,
+    ) => {
+      let sample = benchmark::Sample {
+        size: sample,
+        frequencies: Some(&display.frequencies).filter(|_| by_frequency),
+        seed: args.seed,
+      };
+      let options = benchmark::Options {
+        opener: opener.as_deref(),
+        worst,
+        export: export.as_deref(),
+        require_all_solved,
+        guarantee: display.guarantee.as_ref(),
+        temperature: display.temperature,
+      };
+      benchmark::run(
+        &dictionary,
+        &dictionary_reduced,
+        strategy,
+        &sample,
+        &options,
+      )?;
+    }
+    (Some(Command::Crosswordle { answer, row, hard }), _) => {
+      crosswordle::run(&dictionary, &answer, &row, hard)?;
+    }
+    (Some(Command::LearnPriors { log, answers, out }), _) => {
+      let frequencies = &display.frequencies;
+      learn::run(
+        &dictionary_reduced,
+        frequencies,
+        &log,
+        &answers,
+        &out,
+        &display.language,
+      )?;
     }
+    (
+      Some(Command::Practice {
+        tier,
+        stats,
+        by_frequency,
+      }),
+      _,
+    ) => {
+      let frequencies = Some(&display.frequencies).filter(|_| by_frequency);
+      practice::run(
+        dictionary,
+        dictionary_reduced,
+        tier,
+        &stats,
+        frequencies,
+        rng(args.seed),
+      )
+      .map_err(|err| SolverError::io(&stats, err))?;
+    }
+    (Some(Command::Host { max_guesses, evil }), _) => host::run(
+      dictionary,
+      dictionary_reduced,
+      max_guesses,
+      evil,
+      args.loop_games,
+      &display.theme,
+      rng(args.seed),
+    ),
+    (
+      Some(Command::OpeningBook {
+        opener,
+        format,
+        output,
+      }),
+      _,
+    ) => {
+      book::run(
+        &dictionary,
+        &dictionary_reduced,
+        strategy,
+        &opener,
+        format,
+        output.as_deref(),
+      )?;
+    }
+    (
+      Some(Command::Tree {
+        opener,
+        format,
+        output,
+      }),
+      _,
+    ) => {
+      tree_export::run(
+        &dictionary,
+        &dictionary_reduced,
+        strategy,
+        display.guarantee.as_ref(),
+        opener.as_deref(),
+        format,
+        output.as_deref(),
+      )?;
+    }
+    (
+      Some(Command::Quordle {
+        boards,
+        budget,
+        policy,
+      }),
+      _,
+    ) => {
+      let budget = budget.unwrap_or_else(|| quordle::guess_budget(boards));
+      quordle::run(
+        dictionary,
+        dictionary_reduced,
+        boards,
+        budget,
+        strategy,
+        policy,
+      );
+    }
+    (Some(Command::Xordle), _) => xordle::run(dictionary, dictionary_reduced),
+    (Some(Command::Peaks), _) => peaks::run(dictionary, dictionary_reduced, WORD_LENGTH),
+    (Some(Command::Warmle { distance }), _) => warmle::run(
+      dictionary,
+      dictionary_reduced,
+      WORD_LENGTH,
+      distance,
+      display.language.alphabet(),
+    ),
+    (None, None) if args.absurdle && args.fibble => {
+      return Err(SolverError::ConflictingOptions(
+        "Absurdle doesn't lie, leave out --fibble with --absurdle".to_string(),
+      ));
+    }
+    (None, None) if args.absurdle => {
+      play_games(
+        Host::Absurdle,
+        &dictionary,
+        &dictionary_reduced,
+        strategy,
+        &display,
+        args.loop_games,
+      );
+    }
+    (None, Some(_)) if args.absurdle => {
+      return Err(SolverError::ConflictingOptions(
+        "--absurdle picks the answer as the game goes, leave out --word".to_string(),
+      ));
+    }
+    (None, None) => {
+      interactive(
+        dictionary,
+        dictionary_reduced,
+        strategy,
+        &display,
+        args.overlay,
+        GameFiles {
+          ban_list: args.ban_list.as_deref(),
+          save: args.save.as_deref(),
+          resume: args.resume.as_deref(),
+        },
+      )?;
+    }
+    (None, Some(word)) => {
+      let word = display.language.fold(&word);
+      play_games(
+        Host::Word(word),
+        &dictionary,
+        &dictionary_reduced,
+        strategy,
+        &display,
+        args.loop_games,
+      );
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn obscure_candidates_are_all_shown() {
+    let mut dictionary = WordArena::new();
+    for word in ["crane", "slate", "xylyl", "zymic"] {
+      dictionary.push(word);
+    }
+    let frequencies = Frequencies::from_counts(&[("crane", 900.0), ("slate", 500.0)]);
+    let guesses: Scored = vec![("xylyl", 1.0), ("zymic", 1.0)];
+
+    // The top quarter is crane alone, none of the candidates
+    let familiar = frequencies.common_words(&dictionary, 25.0);
+    assert_eq!(familiar, HashSet::from(["crane".to_string()]));
+    assert_eq!(only_familiar(&guesses, &familiar), None);
+    assert_eq!(recommended(&guesses, Some(&familiar)), guesses);
+    assert_eq!(
+      recommended(&vec![("crane", 6.0), ("xylyl", 1.0)], Some(&familiar)),
+      vec![("crane", 6.0)]
+    );
   }
 }
@@ -0,0 +1,134 @@
+use alloc::string::{String, ToString};
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The color Wordle gives a single letter of a guess.
+///
+/// The textual form is the one used by the interactive mode: `-` for a letter that is not in the
+/// word, `+` for a letter that is in the word but at a different position and `o` for a letter
+/// at the right position.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Mark {
+  NotPresent = 0,
+  WrongPosition = 1,
+  RightPosition = 2,
+}
+
+/// Error returned when a character or string doesn't describe a [`Mark`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseMarkError {
+  input: String,
+}
+
+impl fmt::Display for ParseMarkError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "invalid mark {:?}, expected one of '-', '+' or 'o'",
+      self.input
+    )
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseMarkError {}
+
+impl Mark {
+  /// The character used to display this mark
+  pub fn to_char(self) -> char {
+    match self {
+      Mark::NotPresent => '-',
+      Mark::WrongPosition => '+',
+      Mark::RightPosition => 'o',
+    }
+  }
+
+  /// The base-3 digit of this mark
+  pub fn to_digit(self) -> u32 {
+    self as u32
+  }
+
+  /// Converts a base-3 digit back to a mark. Returns `None` for digits above 2
+  pub fn from_digit(digit: u32) -> Option<Mark> {
+    match digit {
+      0 => Some(Mark::NotPresent),
+      1 => Some(Mark::WrongPosition),
+      2 => Some(Mark::RightPosition),
+      _ => None,
+    }
+  }
+
+  /// Reads a square of a shared result grid: green (or orange, in high contrast mode) for the
+  /// right position, yellow (or blue) for a wrong position and black or white for a letter not in
+  /// the word
+  pub fn from_emoji(c: char) -> Option<Mark> {
+    match c {
+      '🟩' | '🟧' => Some(Mark::RightPosition),
+      '🟨' | '🟦' => Some(Mark::WrongPosition),
+      '⬛' | '⬜' => Some(Mark::NotPresent),
+      _ => None,
+    }
+  }
+}
+
+impl TryFrom<char> for Mark {
+  type Error = ParseMarkError;
+
+  fn try_from(c: char) -> Result<Self, Self::Error> {
+    match c {
+      '-' => Ok(Mark::NotPresent),
+      '+' => Ok(Mark::WrongPosition),
+      'o' => Ok(Mark::RightPosition),
+      _ => Err(ParseMarkError {
+        input: c.to_string(),
+      }),
+    }
+  }
+}
+
+impl FromStr for Mark {
+  type Err = ParseMarkError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+      (Some(c), None) => Mark::try_from(c),
+      _ => Err(ParseMarkError {
+        input: s.to_string(),
+      }),
+    }
+  }
+}
+
+impl fmt::Display for Mark {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.to_char())
+  }
+}
+
+/// Packs marks into their base-3 code. The first mark is the least significant digit, so the
+/// code of an all-green 5 letter pattern is 242.
+pub fn pack<const N: usize>(marks: &[Mark; N]) -> u32 {
+  marks
+    .iter()
+    .rev()
+    .fold(0, |code, mark| code * 3 + mark.to_digit())
+}
+
+/// Unpacks a base-3 code produced by [`pack`]. Digits beyond the N-th are ignored.
+pub fn unpack<const N: usize>(code: u32) -> [Mark; N] {
+  let mut marks = [Mark::NotPresent; N];
+  let mut rest = code;
+
+  for mark in marks.iter_mut() {
+    *mark = Mark::from_digit(rest % 3).unwrap();
+    rest /= 3;
+  }
+
+  marks
+}
@@ -0,0 +1,352 @@
+//! The best guesses for real: searching the game tree for the lowest expected number of guesses.
+//!
+//! The other strategies score a guess by what it tells about the candidates right away.
+//! [`Optimal`] plays each guess out to the end, so it knows how many guesses it takes on average.
+//! Most of the tree is cut: at each turn guesses are tried from the most promising, and a guess is
+//! dropped as soon as the [`guess_bounds`] of what it leaves can't beat the best found. Candidate
+//! sets reached by several lines are searched once, see [`Transpositions`], and a set found to
+//! take at least so many guesses isn't searched again below that.
+//!
+//! The search gets slow with many candidates. Below a hundred or so it takes a few seconds, for a
+//! whole answer list it takes hours. [`Optimal::with_breadth`] only tries the most promising
+//! guesses at each turn to go faster, the result is then the best among those.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::bounds::guess_bounds;
+use crate::pattern::Pattern;
+use crate::solver::{DictWord, Strategy, WORD_LENGTH};
+use crate::transposition::{Bound, Transpositions};
+
+/// Scores a guess by minus the expected number of guesses to find the answer, playing it and then
+/// the best guesses, the solving guess included
+pub struct Optimal {
+  guesses: Vec<DictWord>,
+  /// How many guesses to try at each turn, all of them when `None`
+  breadth: Option<usize>,
+  /// Expected number of guesses of the candidate sets searched so far
+  known: Transpositions,
+  /// Whether sets are looked up in `known` before searching them
+  transpositions: bool,
+  /// Whether guesses and sets are cut by their bounds, rather than all played to the end
+  pruning: bool,
+  /// The expected number of guesses of the candidate sets being scored, and the bound of the last
+  /// guess the search tries for them
+  scored: Mutex<HashMap<Vec<DictWord>, (f64, f64)>>,
+}
+
+/// Fewest guesses `candidates` words can take on average
+fn lower_bound(candidates: usize) -> f64 {
+  guess_bounds(candidates, WORD_LENGTH).expected
+}
+
+impl Optimal {
+  /// Searches with any of `allowed` as guesses
+  pub fn new<S: AsRef<str>>(allowed: &[S]) -> Optimal {
+    Optimal {
+      guesses: allowed
+        .iter()
+        .filter_map(|word| DictWord::new(word.as_ref()))
+        .collect(),
+      breadth: None,
+      known: Transpositions::new(),
+      transpositions: true,
+      pruning: true,
+      scored: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Only tries the `breadth` most promising guesses at each turn
+  pub fn with_breadth(mut self, breadth: usize) -> Optimal {
+    self.breadth = Some(breadth);
+    self
+  }
+
+  /// Searches every set again rather than looking up what was found for it, to check the table
+  #[cfg(test)]
+  fn without_transpositions(mut self) -> Optimal {
+    self.transpositions = false;
+    self
+  }
+
+  /// Plays every guess to the end rather than cutting it by its bounds, to check the cuts
+  #[cfg(test)]
+  fn without_pruning(mut self) -> Optimal {
+    self.pruning = false;
+    self
+  }
+
+  /// Lowest expected number of guesses to find the answer among `candidates`
+  pub fn expected_guesses(&self, candidates: &[DictWord]) -> f64 {
+    let _search = tracing::debug_span!("search", candidates = candidates.len()).entered();
+    let expected = self.search(candidates, f64::INFINITY);
+    tracing::debug!(expected, "searched the game tree");
+    expected
+  }
+
+  /// Lowest expected number of guesses, or some number at least `limit` when it isn't below
+  /// `limit`
+  fn search(&self, candidates: &[DictWord], limit: f64) -> f64 {
+    let limit = if self.pruning { limit } else { f64::INFINITY };
+    match candidates.len() {
+      0 => return 0.0,
+      1 => return 1.0,
+      2 => return 1.5,
+      count if lower_bound(count) >= limit => return lower_bound(count),
+      _ => {}
+    }
+    let key = Transpositions::key(candidates, 0);
+    match self.known.get(key).filter(|_| self.transpositions) {
+      Some(Bound::Exact(known)) => return known,
+      Some(Bound::AtLeast(known)) if known >= limit => return known,
+      _ => {}
+    }
+
+    // A candidate telling all the others apart reaches the lower bound
+    let splitting = candidates.iter().any(|guess| {
+      let mut codes: Vec<_> = candidates
+        .iter()
+        .map(|word| guess.pattern_code(word))
+        .collect();
+      codes.sort_unstable();
+      codes.windows(2).all(|pair| pair[0] != pair[1])
+    });
+    if splitting && self.pruning {
+      return self.known.exact(key, lower_bound(candidates.len()));
+    }
+
+    let mut best = None;
+    let mut limit = limit;
+    for (bound, guess) in self.options(candidates) {
+      if bound >= limit {
+        break;
+      }
+      if let Ok(expected) = self.play(guess, candidates, bound, limit) {
+        if best.is_none_or(|best| expected < best) {
+          best = Some(expected);
+        }
+        if self.pruning {
+          limit = expected;
+        }
+      }
+    }
+
+    match best {
+      Some(best) => self.known.exact(key, best),
+      None => self.known.at_least(key, limit),
+    }
+  }
+
+  /// The guesses to try for `candidates` with their bounds, most promising first
+  fn options(&self, candidates: &[DictWord]) -> Vec<(f64, &DictWord)> {
+    let mut options: Vec<(f64, &DictWord)> = self
+      .guesses
+      .iter()
+      .filter_map(|guess| Some((self.guess_bound(guess, candidates)?, guess)))
+      .collect();
+    options.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    if let Some(breadth) = self.breadth {
+      options.truncate(breadth);
+    }
+    options
+  }
+
+  /// Fewest guesses `guess` can take on average, from the sizes of the buckets it leaves. `None`
+  /// when it leaves all the candidates together.
+  fn guess_bound(&self, guess: &DictWord, candidates: &[DictWord]) -> Option<f64> {
+    let mut counts = [0usize; crate::pattern::PATTERN_COUNT];
+    for word in candidates {
+      counts[guess.pattern_code(word)] += 1;
+    }
+    if counts.contains(&candidates.len()) && !candidates.contains(guess) {
+      return None;
+    }
+
+    let solved = Pattern::SOLVED.index() as usize;
+    let total = candidates.len() as f64;
+    let rest: f64 = counts
+      .iter()
+      .enumerate()
+      .filter(|&(code, &count)| code != solved && count > 0)
+      .map(|(_, &count)| count as f64 / total * lower_bound(count))
+      .sum();
+    Some(1.0 + rest)
+  }
+
+  /// Expected number of guesses playing `guess` first, starting from its `bound`. Once it can't get
+  /// below `limit`, the error has how many it takes at least.
+  fn play(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    bound: f64,
+    limit: f64,
+  ) -> Result<f64, f64> {
+    let mut words: Vec<(usize, DictWord)> = candidates
+      .iter()
+      .map(|word| (guess.pattern_code(word), *word))
+      .collect();
+    words.sort_by_key(|&(code, _)| code);
+
+    let solved = Pattern::SOLVED.index() as usize;
+    let total = candidates.len() as f64;
+    let mut expected = bound;
+    let mut start = 0;
+    while start < words.len() {
+      let code = words[start].0;
+      let end = start
+        + words[start..]
+          .iter()
+          .take_while(|(c, _)| *c == code)
+          .count();
+      if code != solved {
+        let bucket: Vec<DictWord> = words[start..end].iter().map(|&(_, word)| word).collect();
+        let share = bucket.len() as f64 / total;
+        let others = expected - share * lower_bound(bucket.len());
+        expected = others + share * self.search(&bucket, (limit - others) / share);
+        if expected >= limit {
+          return Err(expected);
+        }
+      }
+      start = end;
+    }
+
+    Ok(expected)
+  }
+}
+
+/// The best guesses get their exact expected number of guesses. The others the search tries
+/// only get a number of guesses they take at least, found while ruling them out, which is always
+/// worse. Those it doesn't try count as taking one guess more than the best.
+impl Strategy for Optimal {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    let (best, last_tried) = {
+      let mut scored = self.scored.lock().unwrap();
+      *scored.entry(candidates.to_vec()).or_insert_with(|| {
+        let last_tried = self
+          .options(candidates)
+          .last()
+          .map_or(0.0, |&(bound, _)| bound);
+        (self.expected_guesses(candidates), last_tried)
+      })
+    };
+    let expected = match self.guess_bound(guess, candidates) {
+      Some(bound) if !self.pruning => match self.play(guess, candidates, bound, f64::INFINITY) {
+        Ok(expected) | Err(expected) => expected,
+      },
+      Some(bound) if bound > best => bound,
+      Some(bound) if bound <= last_tried => {
+        // Just above the best, to tell the guesses tying with it from the worse ones
+        let limit = best + 1e-9;
+        match self.play(guess, candidates, bound, limit) {
+          Ok(expected) | Err(expected) => expected,
+        }
+      }
+      _ => 1.0 + best,
+    };
+    -expected
+  }
+
+  fn counts_wins(&self) -> bool {
+    true
+  }
+
+  fn name(&self) -> String {
+    match self.breadth {
+      None => "optimal".to_string(),
+      Some(breadth) => format!("optimal {}", breadth),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fmt::Write;
+
+  /// Words some guesses tell apart at once, the `-ight` ones left together by most and reached by
+  /// several lines
+  const WORDS: [&str; 13] = [
+    "fight", "light", "might", "night", "right", "sight", "cigar", "rebut", "humph", "awake",
+    "blush", "focal", "naval",
+  ];
+
+  /// The guesses played at each set `candidates` leads to, with what they are expected to take
+  fn tree(optimal: &Optimal, candidates: &[DictWord]) -> String {
+    let mut tree = String::new();
+    write_tree(optimal, candidates, 0, &mut tree);
+    tree
+  }
+
+  fn write_tree(optimal: &Optimal, candidates: &[DictWord], depth: usize, tree: &mut String) {
+    if candidates.len() < 2 {
+      return;
+    }
+    let (guess, score) = optimal
+      .guesses
+      .iter()
+      .map(|guess| (guess, optimal.score(guess, candidates)))
+      .fold(
+        None,
+        |best: Option<(&DictWord, f64)>, (guess, score)| match best {
+          Some((_, best_score)) if best_score >= score => best,
+          _ => Some((guess, score)),
+        },
+      )
+      .unwrap();
+    writeln!(tree, "{:depth$}{} {:.9}", "", guess, -score, depth = depth).unwrap();
+    let solved = Pattern::SOLVED.index() as usize;
+    let mut codes: Vec<usize> = candidates
+      .iter()
+      .map(|word| guess.pattern_code(word))
+      .collect();
+    codes.sort_unstable();
+    codes.dedup();
+    for code in codes.into_iter().filter(|&code| code != solved) {
+      let bucket: Vec<DictWord> = candidates
+        .iter()
+        .copied()
+        .filter(|word| guess.pattern_code(word) == code)
+        .collect();
+      write_tree(optimal, &bucket, depth + 1, tree);
+    }
+  }
+
+  #[test]
+  fn cuts_and_transpositions_keep_the_best_tree() {
+    let candidates: Vec<DictWord> = WORDS
+      .iter()
+      .filter_map(|word| DictWord::new(word))
+      .collect();
+    let searched = tree(&Optimal::new(&WORDS), &candidates);
+    for optimal in [
+      Optimal::new(&WORDS).without_transpositions(),
+      Optimal::new(&WORDS).without_pruning(),
+      Optimal::new(&WORDS)
+        .without_transpositions()
+        .without_pruning(),
+    ] {
+      assert_eq!(tree(&optimal, &candidates), searched);
+    }
+  }
+
+  #[test]
+  fn cuts_and_transpositions_keep_the_expected_guesses() {
+    let candidates: Vec<DictWord> = WORDS
+      .iter()
+      .filter_map(|word| DictWord::new(word))
+      .collect();
+    let expected = Optimal::new(&WORDS).expected_guesses(&candidates);
+    let exhaustive = Optimal::new(&WORDS)
+      .without_transpositions()
+      .without_pruning()
+      .expected_guesses(&candidates);
+    assert!(
+      (expected - exhaustive).abs() < 1e-9,
+      "{} != {}",
+      expected,
+      exhaustive
+    );
+  }
+}
@@ -0,0 +1,101 @@
+//! A page showing the game of the interactive mode as it goes, for streamers to add as a browser
+//! source overlay. The page polls `/state.json` every second.
+
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use wordle_solver::mark::Mark;
+
+use crate::http::{self, json_number, json_string};
+use crate::theme::Theme;
+
+const PAGE: &str = include_str!("../static/overlay.html");
+
+/// What the overlay shows
+#[derive(Debug, Clone, Default)]
+pub struct OverlayState {
+  /// Guesses played and their marks
+  pub board: Vec<(String, Vec<Mark>)>,
+  pub candidates: usize,
+  /// Word the solver suggests playing next
+  pub suggestion: Option<String>,
+}
+
+impl OverlayState {
+  fn to_json(&self, theme: &Theme) -> String {
+    let board: Vec<String> = self
+      .board
+      .iter()
+      .map(|(word, marks)| {
+        let marks: String = marks.iter().map(|mark| mark.to_char()).collect();
+        format!(
+          "{{\"word\":{},\"marks\":{}}}",
+          json_string(word),
+          json_string(&marks)
+        )
+      })
+      .collect();
+    let colors: Vec<String> = [Mark::NotPresent, Mark::WrongPosition, Mark::RightPosition]
+      .iter()
+      .map(|&mark| {
+        let (red, green, blue) = theme.tile(mark).color;
+        json_string(&format!("#{:02x}{:02x}{:02x}", red, green, blue))
+      })
+      .collect();
+
+    format!(
+      "{{\"board\":[{}],\"candidates\":{},\"suggestion\":{},\"colors\":[{}]}}",
+      board.join(","),
+      json_number(self.candidates as f64),
+      self
+        .suggestion
+        .as_deref()
+        .map_or("null".to_string(), json_string),
+      colors.join(",")
+    )
+  }
+}
+
+/// The overlay server, running on its own thread for as long as the program
+pub struct Overlay {
+  state: Arc<Mutex<OverlayState>>,
+}
+
+fn handle(stream: &TcpStream, state: &Mutex<OverlayState>, theme: &Theme) -> io::Result<()> {
+  let request = http::read_request(stream)?;
+  match (request.method.as_str(), request.path.as_str()) {
+    ("GET", "/") => http::respond(stream, "200 OK", "text/html; charset=utf-8", PAGE),
+    ("GET", "/state.json") => {
+      let json = state.lock().unwrap().to_json(theme);
+      http::respond(stream, "200 OK", "application/json", &json)
+    }
+    _ => http::respond(stream, "404 Not Found", "text/plain", "Not found"),
+  }
+}
+
+impl Overlay {
+  /// Starts serving the overlay on `port`, with the tile colors of `theme`
+  pub fn start(port: u16, theme: Theme) -> io::Result<Overlay> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let state = Arc::new(Mutex::new(OverlayState::default()));
+
+    let shared = state.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        let result = stream.and_then(|stream| handle(&stream, &shared, &theme));
+        if let Err(err) = result {
+          eprintln!("Overlay request failed: {}", err);
+        }
+      }
+    });
+    println!("Overlay on http://127.0.0.1:{}/", port);
+
+    Ok(Overlay { state })
+  }
+
+  pub fn update(&self, state: OverlayState) {
+    *self.state.lock().unwrap() = state;
+  }
+}
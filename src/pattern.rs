@@ -0,0 +1,191 @@
+//! The marks of a whole 5 letter guess, in forms other tools can exchange.
+//!
+//! A [`Pattern`] converts to and from its code in `0..243` (see [`crate::mark::pack`]), the
+//! `-+o` marks of the interactive mode, the `gybyy` letters many bots and spreadsheets use and
+//! the emoji squares of a shared result.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::engine;
+use crate::mark::{self, Mark};
+
+/// Number of marks of a pattern
+pub const PATTERN_LENGTH: usize = 5;
+
+/// Number of distinct patterns, `3^PATTERN_LENGTH`
+pub const PATTERN_COUNT: usize = 243;
+
+/// The marks Wordle gives a guess
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pattern([Mark; PATTERN_LENGTH]);
+
+/// Error returned when a string, code or slice of marks doesn't describe a [`Pattern`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParsePatternError {
+  input: String,
+}
+
+impl fmt::Display for ParsePatternError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "invalid pattern {:?}, expected {} marks as -+o, gyb letters or emoji squares",
+      self.input, PATTERN_LENGTH
+    )
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePatternError {}
+
+impl Pattern {
+  /// All green, the pattern of the answer
+  pub const SOLVED: Pattern = Pattern([Mark::RightPosition; PATTERN_LENGTH]);
+
+  pub fn new(marks: [Mark; PATTERN_LENGTH]) -> Pattern {
+    Pattern(marks)
+  }
+
+  /// The pattern `guess` gets if the answer is `answer`, `None` unless both have
+  /// [`PATTERN_LENGTH`] letters
+  pub fn of(guess: &str, answer: &str) -> Option<Pattern> {
+    if guess.chars().count() != PATTERN_LENGTH || answer.chars().count() != PATTERN_LENGTH {
+      return None;
+    }
+    Pattern::from_index(engine::pattern_code(guess, answer) as u8)
+  }
+
+  pub fn marks(&self) -> [Mark; PATTERN_LENGTH] {
+    self.0
+  }
+
+  /// The base-3 code of the marks, the first mark being the least significant digit
+  pub fn index(self) -> u8 {
+    mark::pack(&self.0) as u8
+  }
+
+  /// The pattern of a code returned by [`Pattern::index`]. `None` from [`PATTERN_COUNT`] on.
+  pub fn from_index(index: u8) -> Option<Pattern> {
+    if index as usize >= PATTERN_COUNT {
+      return None;
+    }
+    Some(Pattern(mark::unpack(index as u32)))
+  }
+
+  pub fn is_solved(self) -> bool {
+    self == Pattern::SOLVED
+  }
+
+  /// The marks as letters: `g` for green, `y` for yellow and `b` for black
+  pub fn to_letters(self) -> String {
+    self
+      .0
+      .iter()
+      .map(|mark| match mark {
+        Mark::RightPosition => 'g',
+        Mark::WrongPosition => 'y',
+        Mark::NotPresent => 'b',
+      })
+      .collect()
+  }
+
+  /// The marks as the emoji squares of a shared result, in the default colors
+  pub fn to_emoji(self) -> String {
+    self
+      .0
+      .iter()
+      .map(|mark| match mark {
+        Mark::RightPosition => '🟩',
+        Mark::WrongPosition => '🟨',
+        Mark::NotPresent => '⬛',
+      })
+      .collect()
+  }
+}
+
+/// Reads a mark in any of the forms of a [`Pattern`]
+fn parse_mark(c: char) -> Option<Mark> {
+  match c.to_ascii_lowercase() {
+    'g' => Some(Mark::RightPosition),
+    'y' => Some(Mark::WrongPosition),
+    'b' => Some(Mark::NotPresent),
+    c => Mark::try_from(c).ok().or_else(|| Mark::from_emoji(c)),
+  }
+}
+
+/// Parses the `-+o` marks, `gyb` letters (in either case) or emoji squares, which can be mixed
+impl FromStr for Pattern {
+  type Err = ParsePatternError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let error = || ParsePatternError {
+      input: s.to_string(),
+    };
+    let marks = s
+      .trim()
+      .chars()
+      .map(parse_mark)
+      .collect::<Option<Vec<_>>>()
+      .ok_or_else(error)?;
+    Pattern::try_from(marks.as_slice()).map_err(|_| error())
+  }
+}
+
+/// Shows the `-+o` marks of the interactive mode
+impl fmt::Display for Pattern {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for mark in self.0.iter() {
+      write!(f, "{}", mark)?;
+    }
+    Ok(())
+  }
+}
+
+impl From<[Mark; PATTERN_LENGTH]> for Pattern {
+  fn from(marks: [Mark; PATTERN_LENGTH]) -> Pattern {
+    Pattern(marks)
+  }
+}
+
+impl From<Pattern> for [Mark; PATTERN_LENGTH] {
+  fn from(pattern: Pattern) -> [Mark; PATTERN_LENGTH] {
+    pattern.0
+  }
+}
+
+impl From<Pattern> for u8 {
+  fn from(pattern: Pattern) -> u8 {
+    pattern.index()
+  }
+}
+
+impl TryFrom<u8> for Pattern {
+  type Error = ParsePatternError;
+
+  fn try_from(index: u8) -> Result<Self, Self::Error> {
+    Pattern::from_index(index).ok_or_else(|| ParsePatternError {
+      input: index.to_string(),
+    })
+  }
+}
+
+/// Converts the marks kept as a `Vec<Mark>` elsewhere, which must be [`PATTERN_LENGTH`] long
+impl TryFrom<&[Mark]> for Pattern {
+  type Error = ParsePatternError;
+
+  fn try_from(marks: &[Mark]) -> Result<Self, Self::Error> {
+    <[Mark; PATTERN_LENGTH]>::try_from(marks)
+      .map(Pattern)
+      .map_err(|_| ParsePatternError {
+        input: marks.iter().map(|mark| mark.to_char()).collect(),
+      })
+  }
+}
@@ -0,0 +1,117 @@
+//! The `peaks` subcommand: Wordle Peaks, where each letter of a guess is marked as the answer's,
+//! or as coming before or after the answer's letter in the alphabet. The game is played with the
+//! [`Peaks`] feedback rule in place of Wordle's marks, guesses ranked by the information its
+//! patterns give. [`play`] plays the games of other rules the same way, and words of any length.
+
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::io::{self, BufRead};
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::feedback::{FeedbackRule, Peaks};
+
+use crate::{DictString, SHOWN_GUESSES};
+
+/// How Peaks marks are typed
+const MARKS_HELP: &str = "`v` if the answer's letter comes earlier in the alphabet, `^` if later \
+  and `o` if it's right, e.g. `raise v^o^v`";
+
+/// Reads a line of input into the guess and the code of its pattern under `rule`, for words of
+/// `length` letters
+fn parse_turn(
+  rule: &dyn FeedbackRule,
+  length: usize,
+  marks_help: &str,
+  line: &str,
+) -> Result<(String, usize), String> {
+  let expected = || {
+    format!(
+      "Expected a {} letter guess and its marks: {}",
+      length, marks_help
+    )
+  };
+  let (guess, marks) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+    &[guess, marks] => (guess.to_lowercase(), marks),
+    _ => return Err(expected()),
+  };
+  if guess.chars().count() != length || marks.chars().count() != length {
+    return Err(expected());
+  }
+  match rule.parse_pattern(marks) {
+    Some(pattern) => Ok((guess, pattern)),
+    None => Err(expected()),
+  }
+}
+
+/// Plays Wordle Peaks interactively with words of `length` letters. Each line is the guess
+/// followed by its marks.
+pub fn run(dictionary: WordArena, answers: WordArena, length: usize) {
+  play(&Peaks, length, MARKS_HELP, dictionary, answers)
+}
+
+/// Plays the game of `rule` interactively with words of `length` letters, its marks typed as
+/// `marks_help` tells. Each line is the guess followed by its marks.
+pub fn play(
+  rule: &dyn FeedbackRule,
+  length: usize,
+  marks_help: &str,
+  dictionary: WordArena,
+  answers: WordArena,
+) {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let mut candidates: Vec<&DictString> = answers.iter().collect();
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  loop {
+    match candidates.as_slice() {
+      [] => {
+        println!("No word fits, check the marks");
+        return;
+      }
+      [answer] => {
+        println!("The answer is {:?}", answer);
+        return;
+      }
+      _ => println!("{} candidates left", candidates.len()),
+    }
+
+    // Candidates come first among guesses telling as much, they can be the answer
+    let mut scored: Vec<(&DictString, f64)> = dictionary_ref
+      .par_iter()
+      .map(|&guess| {
+        let sizes = engine::bucket_sizes_with(rule, guess, &candidates);
+        (guess, engine::entropy(&sizes))
+      })
+      .collect();
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    let best = scored[0].1;
+    let best = scored
+      .iter()
+      .take_while(|(_, information)| *information >= best)
+      .find(|(word, _)| candidates.contains(word))
+      .unwrap_or(&scored[0])
+      .0;
+    println!(
+      "Suggestions: {:?}",
+      scored.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+    println!("Suggest you try {:?}", best);
+
+    let (guess, pattern) = loop {
+      let line = match lines.next() {
+        Some(Ok(line)) => line,
+        _ => return,
+      };
+      match parse_turn(rule, length, marks_help, &line) {
+        Ok(turn) => break turn,
+        Err(error) => println!("{}", error),
+      }
+    };
+    if rule.is_solved(pattern, length) {
+      println!("Solved!");
+      return;
+    }
+    candidates = engine::reduce_with(rule, &guess, pattern, &candidates);
+  }
+}
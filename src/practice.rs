@@ -0,0 +1,204 @@
+//! Practice games: the program picks an answer of the chosen difficulty and grades the
+//! player's guesses. Results are appended to a stats file so progress can be followed per tier.
+
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+use wordle_solver::arena::WordArena;
+use wordle_solver::difficulty::{self, Tier};
+use wordle_solver::engine;
+use wordle_solver::mark::Mark;
+use wordle_solver::random::Rng;
+
+use crate::frequency::Frequencies;
+use crate::{read_lines, DictString, WORD_LENGTH};
+
+const MAX_TRIES: usize = 6;
+
+/// Outcome of one practice game, one line of the stats file
+pub struct GameRecord {
+  timestamp: u64,
+  tier: Tier,
+  pub word: String,
+  tries: usize,
+  solved: bool,
+}
+
+impl GameRecord {
+  fn to_line(&self) -> String {
+    format!(
+      "{} {} {} {} {}",
+      self.timestamp,
+      self.tier,
+      self.word,
+      self.tries,
+      if self.solved { "solved" } else { "failed" }
+    )
+  }
+
+  fn parse(line: &str) -> Option<GameRecord> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 5 {
+      return None;
+    }
+
+    Some(GameRecord {
+      timestamp: fields[0].parse().ok()?,
+      tier: fields[1].parse().ok()?,
+      word: fields[2].to_string(),
+      tries: fields[3].parse().ok()?,
+      solved: match fields[4] {
+        "solved" => true,
+        "failed" => false,
+        _ => return None,
+      },
+    })
+  }
+}
+
+pub fn read_records(stats: &str) -> Vec<GameRecord> {
+  match read_lines(stats) {
+    Ok(lines) => lines
+      .map_while(Result::ok)
+      .filter_map(|line| GameRecord::parse(&line))
+      .collect(),
+    Err(_) => vec![],
+  }
+}
+
+fn append_record(stats: &str, record: &GameRecord) -> io::Result<()> {
+  let mut file = OpenOptions::new().create(true).append(true).open(stats)?;
+  writeln!(file, "{}", record.to_line())
+}
+
+fn print_summary(records: &[GameRecord]) {
+  println!("Tier     Games  Solved  Avg tries");
+  for tier in Tier::ALL {
+    let games: Vec<&GameRecord> = records.iter().filter(|r| r.tier == tier).collect();
+    let solved: Vec<&GameRecord> = games.iter().copied().filter(|r| r.solved).collect();
+    if games.is_empty() {
+      println!("{:<8} {:>5}", tier, 0);
+      continue;
+    }
+
+    let win_rate = 100.0 * solved.len() as f64 / games.len() as f64;
+    let average_tries = if solved.is_empty() {
+      "-".to_string()
+    } else {
+      let total: usize = solved.iter().map(|r| r.tries).sum();
+      format!("{:.2}", total as f64 / solved.len() as f64)
+    };
+    println!(
+      "{:<8} {:>5}  {:>5.1}%  {:>9}",
+      tier,
+      games.len(),
+      win_rate,
+      average_tries
+    );
+  }
+}
+
+/// Plays one game with `max_tries` guesses, each guess marked by `mark` and its row shown with
+/// `show`. Returns `None` when the input ends first.
+pub fn play(
+  mut mark: impl FnMut(&str) -> Vec<Mark>,
+  dictionary: &WordArena,
+  max_tries: usize,
+  show: impl Fn(&str, &[Mark]) -> String,
+  lines: &mut impl Iterator<Item = io::Result<String>>,
+) -> Option<(usize, bool)> {
+  let mut tries = 0;
+  while tries < max_tries {
+    let guess = lines.next()?.ok()?.trim().to_lowercase();
+    if guess.chars().count() != WORD_LENGTH {
+      println!("Guesses have {} letters", WORD_LENGTH);
+      continue;
+    }
+    if !dictionary.contains(&guess) {
+      println!("{:?} is not in the dictionary", guess);
+      continue;
+    }
+
+    tries += 1;
+    let marks = mark(&guess);
+    println!("{}  ({}/{})", show(&guess, &marks), tries, max_tries);
+
+    if marks.iter().all(|&mark| mark == Mark::RightPosition) {
+      return Some((tries, true));
+    }
+  }
+
+  Some((tries, false))
+}
+
+/// Serves answers from the chosen tier until the input ends, recording every game in `stats`.
+/// With `frequencies`, common answers are picked more often, in proportion to their frequency.
+/// Answers are drawn with `rng`.
+pub fn run(
+  dictionary: WordArena,
+  answers: WordArena,
+  tier: Tier,
+  stats: &str,
+  frequencies: Option<&Frequencies>,
+  mut rng: Rng,
+) -> io::Result<()> {
+  let answers: Vec<&DictString> = answers.iter().collect();
+  let pool: Vec<&DictString> = answers
+    .iter()
+    .copied()
+    .filter(|word| Tier::of_score(difficulty::neighbours(word, &answers)) == tier)
+    .collect();
+  if pool.is_empty() {
+    println!("No {} words in the answer list", tier);
+    return Ok(());
+  }
+
+  let weights: Vec<f64> = match frequencies {
+    Some(frequencies) => pool.iter().map(|word| frequencies.get(word)).collect(),
+    None => vec![],
+  };
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+
+  loop {
+    let answer = match rng.weighted_index(&weights) {
+      Some(index) => pool[index],
+      None => *rng.choose(&pool).unwrap(),
+    };
+    println!(
+      "New {} word ({} in this tier), {} tries:",
+      tier,
+      pool.len(),
+      MAX_TRIES
+    );
+
+    let mark = |guess: &str| engine::compute_bucket(guess, answer);
+    let show = |guess: &str, marks: &[Mark]| {
+      let marks: String = marks.iter().map(|mark| mark.to_char()).collect();
+      format!("{} {}", guess, marks)
+    };
+    let (tries, solved) = match play(mark, &dictionary, MAX_TRIES, show, &mut lines) {
+      Some(result) => result,
+      None => return Ok(()),
+    };
+    if solved {
+      println!("Solved in {}!", tries);
+    } else {
+      println!("The word was {:?}", answer);
+    }
+
+    let record = GameRecord {
+      timestamp: SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0),
+      tier,
+      word: answer.to_string(),
+      tries,
+      solved,
+    };
+    append_record(stats, &record)?;
+    print_summary(&read_records(stats));
+  }
+}
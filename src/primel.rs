@@ -0,0 +1,27 @@
+//! Primel, where the words are the primes of 5 digits and the letters their digits.
+//!
+//! The feedback is Wordle's, so only the dictionary differs: the primes are generated with a sieve
+//! rather than read from a file. With the numbers both the guesses and the answers, every guess
+//! can be the answer.
+
+use wordle_solver::arena::WordArena;
+
+/// The primes written with `digits` digits, the first not 0, smallest first
+pub fn primes(digits: usize) -> WordArena {
+  let low = 10usize.pow(digits as u32 - 1);
+  let high = 10usize.pow(digits as u32);
+  let mut composite = vec![false; high];
+  let mut words = WordArena::new();
+  for number in 2..high {
+    if composite[number] {
+      continue;
+    }
+    if number >= low {
+      words.push(&number.to_string());
+    }
+    for multiple in (number * number..high).step_by(number) {
+      composite[multiple] = true;
+    }
+  }
+  words
+}
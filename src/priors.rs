@@ -0,0 +1,184 @@
+//! How likely each candidate is to be the answer.
+//!
+//! Puzzle setters don't pick answers uniformly: common words come up far more often than obscure
+//! ones. A [`PriorModel`] turns what is known about a word into a relative weight, which the
+//! weighted strategies use instead of counting every candidate once.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// Number of frequency tiers, tier 0 holding the most common words
+pub const FREQUENCY_TIERS: usize = 5;
+
+/// The frequency tier of each word, given the frequencies of all of them. Words are ranked by
+/// frequency and the ranking is cut into [`FREQUENCY_TIERS`] tiers of equal size.
+pub fn frequency_tiers(frequencies: &[f64]) -> Vec<usize> {
+  let mut ranking: Vec<usize> = (0..frequencies.len()).collect();
+  ranking.sort_by(|&a, &b| {
+    frequencies[b]
+      .partial_cmp(&frequencies[a])
+      .unwrap_or(core::cmp::Ordering::Equal)
+  });
+
+  let mut tiers = alloc::vec![0; frequencies.len()];
+  for (rank, index) in ranking.into_iter().enumerate() {
+    tiers[index] = rank * FREQUENCY_TIERS / frequencies.len();
+  }
+  tiers
+}
+
+/// Whether the word looks like a plural: it ends with an S that isn't part of `ss`, `us` or `is`
+pub fn is_plural(word: &str) -> bool {
+  word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") && !word.ends_with("is")
+}
+
+/// Whether the word looks like a past tense: it ends with `ed` but not `eed`
+pub fn is_past_tense(word: &str) -> bool {
+  word.ends_with("ed") && !word.ends_with("eed")
+}
+
+/// Relative weights of words being the answer. A word's weight is the product of the weight of
+/// its frequency tier and the weights of the features it has.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorModel {
+  /// Weight of each frequency tier, most common first
+  pub tier_weights: [f64; FREQUENCY_TIERS],
+  /// Weight of words that look like plurals, see [`is_plural`]
+  pub plural_weight: f64,
+  /// Weight of words that look like past tenses, see [`is_past_tense`]
+  pub past_tense_weight: f64,
+}
+
+impl Default for PriorModel {
+  /// Every word equally likely
+  fn default() -> PriorModel {
+    PriorModel {
+      tier_weights: [1.0; FREQUENCY_TIERS],
+      plural_weight: 1.0,
+      past_tense_weight: 1.0,
+    }
+  }
+}
+
+impl PriorModel {
+  /// A rough model of how the New York Times picks answers: common words, hardly ever plurals
+  /// or past tenses
+  pub fn nyt() -> PriorModel {
+    PriorModel {
+      tier_weights: [3.0, 2.0, 1.0, 0.4, 0.1],
+      plural_weight: 0.05,
+      past_tense_weight: 0.1,
+    }
+  }
+
+  /// Fits the tier weights to past answers. A tier's weight is how much more often its words were
+  /// answers than if answers were picked uniformly from `word_tiers`, smoothed so that tiers
+  /// without any answer yet keep a small weight.
+  ///
+  /// The feature weights are fitted the same way, comparing how often answers and words have them.
+  pub fn fit<S: AsRef<str>>(answers: &[(S, usize)], words: &[(S, usize)]) -> PriorModel {
+    let answer_tiers: Vec<usize> = answers.iter().map(|&(_, tier)| tier).collect();
+    let word_tiers: Vec<usize> = words.iter().map(|&(_, tier)| tier).collect();
+    let mut model = PriorModel::fit_tiers(&answer_tiers, &word_tiers);
+
+    let rate = |entries: &[(S, usize)], feature: fn(&str) -> bool| {
+      let count = entries
+        .iter()
+        .filter(|(word, _)| feature(word.as_ref()))
+        .count();
+      (count as f64 + 1.0) / (entries.len() as f64 + 2.0)
+    };
+    model.plural_weight = rate(answers, is_plural) / rate(words, is_plural);
+    model.past_tense_weight = rate(answers, is_past_tense) / rate(words, is_past_tense);
+
+    model
+  }
+
+  fn fit_tiers(answer_tiers: &[usize], word_tiers: &[usize]) -> PriorModel {
+    let mut model = PriorModel::default();
+    let answers = answer_tiers.len() as f64;
+    let words = word_tiers.len() as f64;
+
+    for (tier, weight) in model.tier_weights.iter_mut().enumerate() {
+      let answer_count = answer_tiers.iter().filter(|&&t| t == tier).count() as f64;
+      let word_count = word_tiers.iter().filter(|&&t| t == tier).count() as f64;
+      if word_count > 0.0 {
+        let answer_share = (answer_count + 1.0) / (answers + FREQUENCY_TIERS as f64);
+        *weight = answer_share / (word_count / words);
+      }
+    }
+
+    model
+  }
+
+  /// The weight of a word in the given frequency tier
+  pub fn weight(&self, word: &str, tier: usize) -> f64 {
+    self.tier_weights[tier.min(FREQUENCY_TIERS - 1)] * self.feature_weight(word)
+  }
+
+  /// The weight of a word from its features alone, ignoring its frequency
+  pub fn feature_weight(&self, word: &str) -> f64 {
+    let mut weight = 1.0;
+    if is_plural(word) {
+      weight *= self.plural_weight;
+    }
+    if is_past_tense(word) {
+      weight *= self.past_tense_weight;
+    }
+    weight
+  }
+}
+
+/// One `tier <index> <weight>` line per tier, then `plural <weight>` and `past-tense <weight>`
+impl fmt::Display for PriorModel {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (tier, weight) in self.tier_weights.iter().enumerate() {
+      writeln!(f, "tier {} {}", tier, weight)?;
+    }
+    writeln!(f, "plural {}", self.plural_weight)?;
+    writeln!(f, "past-tense {}", self.past_tense_weight)
+  }
+}
+
+/// Parses the [`fmt::Display`] form. Weights that aren't mentioned stay at 1, lines starting
+/// with `#` are comments.
+impl FromStr for PriorModel {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut model = PriorModel::default();
+
+    for line in s.lines().map(str::trim) {
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      match fields.as_slice() {
+        ["tier", tier, weight] => {
+          let tier: usize = tier
+            .parse()
+            .ok()
+            .filter(|&tier| tier < FREQUENCY_TIERS)
+            .ok_or_else(|| format!("invalid tier in {:?}", line))?;
+          model.tier_weights[tier] = parse_weight(weight, line)?;
+        }
+        ["plural", weight] => model.plural_weight = parse_weight(weight, line)?,
+        ["past-tense", weight] => model.past_tense_weight = parse_weight(weight, line)?,
+        _ => return Err(format!("unknown prior {:?}", line)),
+      }
+    }
+
+    Ok(model)
+  }
+}
+
+fn parse_weight(weight: &str, line: &str) -> Result<f64, String> {
+  weight
+    .parse()
+    .ok()
+    .filter(|&weight: &f64| weight >= 0.0)
+    .ok_or_else(|| format!("invalid weight in {:?}", line))
+}
@@ -0,0 +1,99 @@
+//! Scoring strings that are not in the dictionary. The real game rejects them, but clones and
+//! custom games often accept any string, and they show how much a guess could reveal at best.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use wordle_solver::constraints::Constraints;
+use wordle_solver::scoring::ScoringBuffers;
+
+use crate::{
+  compute_guess_scores, compute_information_value, DictString, DictWord, Scored, Strategy,
+};
+
+/// Scores any string of the right length as a guess against the candidates. Returns `None` if
+/// the string doesn't have the right length.
+pub fn evaluate(guess: &str, candidates: &[&DictString], strategy: &dyn Strategy) -> Option<f64> {
+  let guess = DictWord::new(guess)?;
+  let candidates: Vec<DictWord> = candidates.iter().map(|word| crate::to_word(word)).collect();
+
+  Some(compute_information_value(
+    &guess,
+    &candidates,
+    strategy,
+    &mut ScoringBuffers::new(),
+  ))
+}
+
+/// Looks for the best scoring string by changing one letter of `start` at a time for as long as
+/// that improves the score, trying the letters of `alphabet`. This finds a local optimum, usually
+/// better than any dictionary word.
+pub fn best_probe(
+  start: &str,
+  candidates: &[&DictString],
+  strategy: &dyn Strategy,
+  alphabet: &[char],
+) -> (String, f64) {
+  let candidates: Vec<DictWord> = candidates.iter().map(|word| crate::to_word(word)).collect();
+  let mut buffers = ScoringBuffers::new();
+  let mut score_of = |letters: &[char]| {
+    let word = DictWord::new(&letters.iter().collect::<String>()).unwrap();
+    compute_information_value(&word, &candidates, strategy, &mut buffers)
+  };
+
+  let mut best: Vec<char> = start.chars().collect();
+  let mut best_score = score_of(&best);
+
+  loop {
+    let mut improved = false;
+    for position in 0..best.len() {
+      for &letter in alphabet {
+        let mut probe = best.clone();
+        probe[position] = letter;
+        let score = score_of(&probe);
+        if score > best_score {
+          best = probe;
+          best_score = score;
+          improved = true;
+        }
+      }
+    }
+
+    if !improved {
+      return (best.into_iter().collect(), best_score);
+    }
+  }
+}
+
+/// Information-only guesses that test as many new letters as possible, best first: among the
+/// dictionary words with the most distinct letters nothing is known about yet, ranked by score.
+/// Known letters, greens included, are deliberately left out since they are no use in easy mode.
+pub fn burn_guesses<'a>(
+  dictionary: &[&'a DictString],
+  candidates: &[&'a DictString],
+  constraints: &Constraints,
+  strategy: &dyn Strategy,
+) -> Scored<'a> {
+  let new_letters = |word: &str| {
+    word
+      .chars()
+      .filter(|&letter| !constraints.is_known(letter))
+      .collect::<HashSet<char>>()
+      .len()
+  };
+
+  let most = dictionary
+    .iter()
+    .map(|word| new_letters(word))
+    .max()
+    .unwrap_or(0);
+  let burners: Vec<&DictString> = dictionary
+    .iter()
+    .copied()
+    .filter(|word| new_letters(word) == most)
+    .collect();
+
+  let scores = compute_guess_scores(&burners, candidates, strategy, |_| {});
+  let mut scored: Scored<'a> = burners.iter().map(|&word| (word, scores[word])).collect();
+  scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+  scored
+}
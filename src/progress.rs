@@ -0,0 +1,55 @@
+//! Progress reporting for long running operations such as scoring a full dictionary.
+
+use alloc::vec::Vec;
+
+/// A snapshot of how far along an operation is
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress<'a> {
+  /// Number of items processed so far
+  pub done: usize,
+  /// Total number of items the operation will process
+  pub total: usize,
+  /// The best items seen so far along with their scores, best first, at most
+  /// [`PROGRESS_LEADERS`] of them
+  pub leaders: Vec<(&'a str, f64)>,
+}
+
+impl<'a> Progress<'a> {
+  /// Share of the work done, between 0 and 1
+  pub fn fraction(&self) -> f64 {
+    if self.total == 0 {
+      1.0
+    } else {
+      self.done as f64 / self.total as f64
+    }
+  }
+
+  /// The best item seen so far, along with its score
+  pub fn best(&self) -> Option<(&'a str, f64)> {
+    self.leaders.first().copied()
+  }
+
+  /// Whether this is the last report of the operation
+  pub fn is_finished(&self) -> bool {
+    self.done >= self.total
+  }
+}
+
+/// How many times an operation reports progress, at most (not counting the final report)
+pub const PROGRESS_STEPS: usize = 100;
+
+/// How many of the best items a progress report includes
+pub const PROGRESS_LEADERS: usize = 10;
+
+/// Records an item in a list of leaders ordered by decreasing score, keeping at most
+/// [`PROGRESS_LEADERS`] of them. Items with equal scores keep the order they were recorded in.
+pub(crate) fn record_leader<T>(leaders: &mut Vec<(T, f64)>, item: T, score: f64) {
+  let position = leaders
+    .iter()
+    .position(|&(_, leader_score)| score > leader_score)
+    .unwrap_or(leaders.len());
+  if position < PROGRESS_LEADERS {
+    leaders.insert(position, (item, score));
+    leaders.truncate(PROGRESS_LEADERS);
+  }
+}
@@ -0,0 +1,50 @@
+//! Progress bars for long computations, drawn on stderr.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::{self, IsTerminal};
+use tracing::level_filters::LevelFilter;
+use wordle_solver::progress::Progress;
+
+const TEMPLATE: &str = "{msg} [{bar:40}] {percent:>3}% ETA {eta}";
+
+/// Whether progress is displayed: on a terminal, unless `-q` turned the diagnostics down to errors
+pub fn shown() -> bool {
+  io::stderr().is_terminal() && LevelFilter::current() >= LevelFilter::WARN
+}
+
+/// A progress bar fed by [`Progress`] reports. Hidden when stderr is not a terminal, so piped
+/// output stays clean, and with `-q`.
+pub struct Bar {
+  bar: ProgressBar,
+}
+
+impl Bar {
+  pub fn new(message: &'static str) -> Bar {
+    let bar = if shown() {
+      let style = ProgressStyle::with_template(TEMPLATE)
+        .unwrap()
+        .progress_chars("=> ");
+      ProgressBar::new(0).with_style(style)
+    } else {
+      ProgressBar::hidden()
+    };
+
+    Bar {
+      bar: bar.with_message(message),
+    }
+  }
+
+  /// Updates the bar from a report and removes it once the operation is finished
+  pub fn update(&self, progress: &Progress) {
+    // Parallel operations can deliver reports slightly out of order
+    if self.bar.is_finished() || progress.done as u64 <= self.bar.position() {
+      return;
+    }
+
+    self.bar.set_length(progress.total as u64);
+    self.bar.set_position(progress.done as u64);
+    if progress.is_finished() {
+      self.bar.finish_and_clear();
+    }
+  }
+}
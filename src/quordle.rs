@@ -0,0 +1,265 @@
+//! The `quordle` subcommand: several boards played with the same guesses, the guess for all of
+//! them chosen by a [`BoardPolicy`]. Any number of boards is played the same way, from Dordle to
+//! Duotrigordle, within the guesses the variant allows (see [`guess_budget`]).
+//!
+//! Summing the information a guess gives on each board plays badly: it keeps probing when a board
+//! is one lucky guess away from done, and every board still needs its own winning guess in the
+//! end. The policy decides how much a chance of solving a board is worth against information.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+use wordle_solver::arena::WordArena;
+use wordle_solver::pattern::Pattern;
+use wordle_solver::scoring::{self, ScoringBuffers};
+
+use crate::{
+  compute_information_value, reduce_dictionary, to_word, DictString, DictWord, Strategy,
+  SHOWN_GUESSES, WORD_LENGTH,
+};
+
+/// How the guess for all boards is chosen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoardPolicy {
+  /// Play a candidate of the board most likely to be solved, information only breaks ties
+  CloseOut,
+  /// Information summed over the boards, plus this many bits for each board expected to be
+  /// solved by the guess
+  SolveBits(f64),
+}
+
+/// What `balanced` counts a solved board as, in bits
+const BALANCED_SOLVE_BITS: f64 = 4.0;
+
+/// What `close-out` counts a solved board as: more than any guess can tell about all the boards
+const CLOSE_OUT_SOLVE_BITS: f64 = 1000.0;
+
+impl BoardPolicy {
+  fn solve_bits(self) -> f64 {
+    match self {
+      BoardPolicy::CloseOut => CLOSE_OUT_SOLVE_BITS,
+      BoardPolicy::SolveBits(bits) => bits,
+    }
+  }
+}
+
+impl FromStr for BoardPolicy {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "close-out" => Ok(BoardPolicy::CloseOut),
+      "balanced" => Ok(BoardPolicy::SolveBits(BALANCED_SOLVE_BITS)),
+      "joint-info" => Ok(BoardPolicy::SolveBits(0.0)),
+      _ => match s.parse::<f64>() {
+        Ok(bits) if bits >= 0.0 => Ok(BoardPolicy::SolveBits(bits)),
+        _ => Err(format!(
+          "unknown policy {:?}, expected close-out, balanced, joint-info or a number of bits",
+          s
+        )),
+      },
+    }
+  }
+}
+
+/// Information `guess` gives on all the unsolved boards together
+fn board_information(
+  guess: &DictWord,
+  boards: &[Vec<DictWord>],
+  strategy: &dyn Strategy,
+  buffers: &mut ScoringBuffers,
+) -> f64 {
+  boards
+    .iter()
+    .filter(|candidates| !candidates.is_empty())
+    .map(|candidates| compute_information_value(guess, candidates, strategy, buffers))
+    .sum()
+}
+
+/// The guesses the variant with `board_count` boards allows, 5 more than the boards: 6 for
+/// Wordle, 7 for Dordle, 9 for Quordle, 13 for Octordle, 21 for Sedecordle and 37 for Duotrigordle
+pub fn guess_budget(board_count: usize) -> usize {
+  board_count + 5
+}
+
+/// What is known of one of the boards
+struct Board<'a> {
+  candidates: Vec<&'a DictString>,
+  /// The guesses played on the board and their marks, up to the guess solving it
+  feedback: Vec<(String, Pattern)>,
+  solved: bool,
+}
+
+impl<'a> Board<'a> {
+  fn print(&self, number: usize) {
+    if self.solved {
+      println!("Board {}: solved in {}", number, self.feedback.len());
+    } else {
+      println!("Board {}: {} candidates", number, self.candidates.len());
+    }
+    for (guess, pattern) in &self.feedback {
+      println!("  {} {}", pattern.to_emoji(), guess);
+    }
+  }
+
+  /// Keeps the candidates matching the marks `guess` got
+  fn play(&mut self, guess: &str, pattern: Pattern) {
+    self.solved = pattern.is_solved();
+    self.candidates = reduce_dictionary(guess, &pattern.marks(), &self.candidates);
+    self.feedback.push((guess.to_string(), pattern));
+  }
+}
+
+/// Expected number of boards each candidate solves when played. Other words solve none.
+fn solve_chances<'a>(boards: &[Board<'a>]) -> HashMap<&'a DictString, f64> {
+  let mut chances = HashMap::new();
+  for board in boards.iter().filter(|board| !board.solved) {
+    for &word in &board.candidates {
+      *chances.entry(word).or_insert(0.0) += 1.0 / board.candidates.len() as f64;
+    }
+  }
+  chances
+}
+
+/// Reads a line of input into the guess and its pattern on each board not solved yet. The marks
+/// are given either for those boards only or for every board, those of solved boards ignored.
+fn parse_turn(line: &str, solved: &[bool]) -> Result<(String, Vec<Pattern>), String> {
+  let fields: Vec<&str> = line.split_whitespace().collect();
+  let unsolved_count = solved.iter().filter(|&&solved| !solved).count();
+  let marks: Vec<&str> = match fields.len().checked_sub(1) {
+    Some(count) if count == unsolved_count => fields[1..].to_vec(),
+    Some(count) if count == solved.len() => fields[1..]
+      .iter()
+      .zip(solved)
+      .filter(|(_, &solved)| !solved)
+      .map(|(&marks, _)| marks)
+      .collect(),
+    _ => {
+      return Err(format!(
+        "Expected the guess followed by its marks on each of the {} unsolved boards, or on all {}",
+        unsolved_count,
+        solved.len()
+      ))
+    }
+  };
+  if fields[0].chars().count() != WORD_LENGTH {
+    return Err(format!("Expected a {} letter guess", WORD_LENGTH));
+  }
+  let patterns = marks
+    .iter()
+    .map(|marks| marks.parse::<Pattern>())
+    .collect::<Result<Vec<_>, _>>()
+    .map_err(|error| error.to_string())?;
+  Ok((fields[0].to_lowercase(), patterns))
+}
+
+/// Plays `board_count` boards interactively with `budget` guesses. Each line is the guess
+/// followed by its marks on every board not solved yet (or on every board), in order.
+pub fn run(
+  dictionary: WordArena,
+  answers: WordArena,
+  board_count: usize,
+  budget: usize,
+  strategy: &dyn Strategy,
+  policy: BoardPolicy,
+) {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let mut boards: Vec<Board> = (0..board_count)
+    .map(|_| Board {
+      candidates: answers.iter().collect(),
+      feedback: vec![],
+      solved: false,
+    })
+    .collect();
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  for turn in 1.. {
+    for (index, board) in boards.iter().enumerate() {
+      board.print(index + 1);
+    }
+    let solved = boards.iter().filter(|board| board.solved).count();
+    if solved == board_count {
+      println!("All boards solved in {} of {} guesses!", turn - 1, budget);
+      return;
+    }
+    if turn > budget {
+      println!(
+        "Out of guesses, solved {} of {} boards",
+        solved, board_count
+      );
+      return;
+    }
+    println!("Guess {} of {}", turn, budget);
+
+    let unsolved: Vec<Vec<DictWord>> = boards
+      .iter()
+      .map(|board| {
+        if board.solved {
+          vec![]
+        } else {
+          board.candidates.iter().map(|word| to_word(word)).collect()
+        }
+      })
+      .collect();
+    let information = scoring::score_guesses(
+      &dictionary_ref,
+      &unsolved,
+      |guess, boards, buffers| board_information(&to_word(guess), boards, strategy, buffers),
+      |_| {},
+    );
+
+    // Once every board is down to one candidate nothing is left to learn, so the chance of
+    // solving a board always breaks ties, whatever the policy
+    let chances = solve_chances(&boards);
+    let chance = |word: &DictString| chances.get(word).copied().unwrap_or(0.0);
+    let mut scored: Vec<(&DictString, f64)> = dictionary_ref
+      .iter()
+      .zip(information)
+      .map(|(&word, information)| (word, information + policy.solve_bits() * chance(word)))
+      .collect();
+    scored.sort_by(|a, b| {
+      (b.1, chance(b.0))
+        .partial_cmp(&(a.1, chance(a.0)))
+        .unwrap_or(Ordering::Equal)
+    });
+
+    println!(
+      "Suggestions: {:?}",
+      scored.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+    let best = scored[0].0;
+    let targets: Vec<String> = boards
+      .iter()
+      .enumerate()
+      .filter(|(_, board)| !board.solved && board.candidates.contains(&best))
+      .map(|(index, _)| (index + 1).to_string())
+      .collect();
+    if targets.is_empty() {
+      println!("Suggest you try {:?}", best);
+    } else {
+      println!(
+        "Suggest you try {:?}, which can solve board {}",
+        best,
+        targets.join(", ")
+      );
+    }
+
+    let solved: Vec<bool> = boards.iter().map(|board| board.solved).collect();
+    let (guess, patterns) = loop {
+      let line = match lines.next() {
+        Some(Ok(line)) => line,
+        _ => return,
+      };
+      match parse_turn(&line, &solved) {
+        Ok(played) => break played,
+        Err(error) => println!("{}", error),
+      }
+    };
+    let unsolved = boards.iter_mut().filter(|board| !board.solved);
+    for (board, pattern) in unsolved.zip(patterns) {
+      board.play(&guess, pattern);
+    }
+  }
+}
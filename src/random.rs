@@ -0,0 +1,76 @@
+//! A small deterministic random number generator (SplitMix64).
+//!
+//! Games and simulations pick words at random. Using our own generator keeps sequences
+//! reproducible from a seed across platforms and releases.
+
+/// SplitMix64 pseudo random number generator
+#[derive(Debug, Clone)]
+pub struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  pub fn new(seed: u64) -> Rng {
+    Rng { state: seed }
+  }
+
+  /// A generator seeded from the system clock
+  #[cfg(feature = "std")]
+  pub fn from_time() -> Rng {
+    let nanos = std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map(|elapsed| elapsed.as_nanos() as u64)
+      .unwrap_or(0);
+    Rng::new(nanos)
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// A number in `0..bound`. Panics if `bound` is 0.
+  pub fn below(&mut self, bound: usize) -> usize {
+    (self.next_u64() % bound as u64) as usize
+  }
+
+  /// A number in `0.0..1.0`
+  pub fn next_f64(&mut self) -> f64 {
+    (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  /// A random element of the slice, `None` if it's empty
+  pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+    if items.is_empty() {
+      None
+    } else {
+      Some(&items[self.below(items.len())])
+    }
+  }
+
+  /// A random index, each index being picked in proportion to its weight. `None` if no weight is
+  /// positive.
+  pub fn weighted_index(&mut self, weights: &[f64]) -> Option<usize> {
+    let total: f64 = weights.iter().filter(|&&weight| weight > 0.0).sum();
+    if total <= 0.0 {
+      return None;
+    }
+
+    let mut target = self.next_f64() * total;
+    for (index, &weight) in weights.iter().enumerate() {
+      if weight <= 0.0 {
+        continue;
+      }
+      if target < weight {
+        return Some(index);
+      }
+      target -= weight;
+    }
+
+    // Rounding can leave a tiny remainder past the last positive weight
+    weights.iter().rposition(|&weight| weight > 0.0)
+  }
+}
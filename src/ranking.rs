@@ -0,0 +1,235 @@
+//! Ordering suggestions by a metric other than the strategy's score (`--sort`), by several one
+//! after the other (`--rank`), and guesses with equal scores (`--tie-break`).
+
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use wordle_solver::engine;
+
+use crate::frequency::Frequencies;
+use crate::{DictString, Scored};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+  /// Highest strategy score first
+  Bits,
+  /// Fewest candidates left in the worst case first
+  Worst,
+  /// Fewest candidates expected to be left first
+  Expected,
+  /// Most common word first, see `--frequencies`
+  Frequency,
+}
+
+impl FromStr for SortKey {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "bits" => Ok(SortKey::Bits),
+      "worst" => Ok(SortKey::Worst),
+      "expected" => Ok(SortKey::Expected),
+      "frequency" => Ok(SortKey::Frequency),
+      _ => Err(format!(
+        "unknown sort key {:?}, expected bits, worst, expected or frequency",
+        s
+      )),
+    }
+  }
+}
+
+/// An objective of `--rank`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+  /// Highest strategy score first, the information in bits for most strategies
+  Entropy,
+  /// Most likely to be the answer first, by the weights of `--priors` if given
+  Candidate,
+  /// Most common word first, see `--frequencies`
+  Freq,
+  /// Fewest candidates left in the worst case first
+  Worst,
+  /// Fewest candidates expected to be left first
+  Expected,
+}
+
+impl FromStr for Objective {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "entropy" => Ok(Objective::Entropy),
+      "candidate" => Ok(Objective::Candidate),
+      "freq" => Ok(Objective::Freq),
+      "worst" => Ok(Objective::Worst),
+      "expected" => Ok(Objective::Expected),
+      _ => Err(format!(
+        "unknown objective {:?}, expected entropy, candidate, freq, worst or expected",
+        s
+      )),
+    }
+  }
+}
+
+/// Objectives ranking words one after the other, each breaking the ties of those before it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rank(Vec<Objective>);
+
+impl FromStr for Rank {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let objectives = s
+      .split(',')
+      .map(|objective| objective.trim().parse())
+      .collect::<Result<Vec<Objective>, String>>()?;
+    Ok(Rank(objectives))
+  }
+}
+
+/// Values of the objectives are compared to this many decimals, so that rounding errors don't
+/// break ties
+const RANK_PRECISION: f64 = 1e9;
+
+/// How guesses with equal scores are ordered. The solver keeps the order of the words it is given
+/// for those, so the dictionaries are put in this order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TieBreak {
+  /// As listed in the dictionary files
+  Listed,
+  Alphabetical,
+  /// Most common word first, see `--frequencies`
+  Frequency,
+  /// Words that can still be the answer first
+  CandidatesFirst,
+  /// Words with the fewest repeated letters first
+  FewestRepeats,
+}
+
+impl FromStr for TieBreak {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "listed" => Ok(TieBreak::Listed),
+      "alphabetical" => Ok(TieBreak::Alphabetical),
+      "frequency" => Ok(TieBreak::Frequency),
+      "candidates-first" => Ok(TieBreak::CandidatesFirst),
+      "fewest-repeats" => Ok(TieBreak::FewestRepeats),
+      _ => Err(format!(
+        "unknown tie-break {:?}, expected listed, alphabetical, frequency, candidates-first or \
+         fewest-repeats",
+        s
+      )),
+    }
+  }
+}
+
+impl TieBreak {
+  /// `words` in this order, `candidates` being the words that can still be the answer. Words
+  /// tying on it keep their order.
+  pub fn order<'a>(
+    self,
+    words: &[&'a DictString],
+    candidates: &[&DictString],
+    frequencies: &Frequencies,
+  ) -> Vec<&'a DictString> {
+    let mut ordered = words.to_vec();
+    match self {
+      TieBreak::Listed => {}
+      TieBreak::Alphabetical => ordered.sort(),
+      TieBreak::Frequency => ordered.sort_by(|a, b| {
+        frequencies
+          .get(b)
+          .partial_cmp(&frequencies.get(a))
+          .unwrap_or(Ordering::Equal)
+      }),
+      TieBreak::CandidatesFirst => {
+        let candidates: HashSet<&str> = candidates.iter().copied().collect();
+        ordered.sort_by_key(|word| !candidates.contains(word));
+      }
+      TieBreak::FewestRepeats => ordered.sort_by_key(|word| {
+        let letters: HashSet<char> = word.chars().collect();
+        word.chars().count() - letters.len()
+      }),
+    }
+    ordered
+  }
+}
+
+/// Number of candidates left in the worst case after playing `word`
+pub fn worst_remaining(word: &str, candidates: &[&DictString]) -> u32 {
+  engine::bucket_sizes(word, candidates)
+    .into_iter()
+    .max()
+    .unwrap_or(0)
+}
+
+/// Number of candidates expected to be left after playing `word`
+pub fn expected_remaining(word: &str, candidates: &[&DictString]) -> f64 {
+  let squares: u64 = engine::bucket_sizes(word, candidates)
+    .into_iter()
+    .map(|size| size as u64 * size as u64)
+    .sum();
+  squares as f64 / candidates.len().max(1) as f64
+}
+
+/// Sorts scored words by `key`, best first. Ties keep the strategy's order.
+pub fn sort<'a>(
+  scored: &Scored<'a>,
+  candidates: &[&DictString],
+  key: SortKey,
+  frequencies: &Frequencies,
+) -> Scored<'a> {
+  // Smaller values come first
+  let metric = |word: &str, score: f64| match key {
+    SortKey::Bits => -score,
+    SortKey::Worst => worst_remaining(word, candidates) as f64,
+    SortKey::Expected => expected_remaining(word, candidates),
+    SortKey::Frequency => -frequencies.get(word),
+  };
+
+  let mut keyed: Vec<(f64, (&'a DictString, f64))> = scored
+    .par_iter()
+    .map(|&(word, score)| (metric(word, score), (word, score)))
+    .collect();
+  keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+  keyed.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Sorts scored words by the objectives of `rank`, best first, `chances` being how likely each of
+/// `candidates` is to be the answer. Ties on all of them keep the strategy's order.
+pub fn rank<'a>(
+  scored: &Scored<'a>,
+  candidates: &[&DictString],
+  rank: &Rank,
+  frequencies: &Frequencies,
+  chances: &HashMap<&str, f64>,
+) -> Scored<'a> {
+  // Smaller values come first
+  let metric = |word: &str, score: f64, objective: Objective| {
+    let value = match objective {
+      Objective::Entropy => -score,
+      Objective::Candidate => -chances.get(word).copied().unwrap_or(0.0),
+      Objective::Freq => -frequencies.get(word),
+      Objective::Worst => worst_remaining(word, candidates) as f64,
+      Objective::Expected => expected_remaining(word, candidates),
+    };
+    (value * RANK_PRECISION).round()
+  };
+
+  let mut keyed: Vec<(Vec<f64>, (&'a DictString, f64))> = scored
+    .par_iter()
+    .map(|&(word, score)| {
+      let values = rank
+        .0
+        .iter()
+        .map(|&objective| metric(word, score, objective))
+        .collect();
+      (values, (word, score))
+    })
+    .collect();
+  keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+  keyed.into_iter().map(|(_, entry)| entry).collect()
+}
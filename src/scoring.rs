@@ -0,0 +1,247 @@
+//! Guess scoring that doesn't allocate per call.
+//!
+//! The functions here take a [`ScoringBuffers`] owned by the caller. The buffers grow to fit the
+//! word length on first use and are reused afterwards, so scoring the same guess against many
+//! candidate sets (or many guesses against one set) does no heap allocation.
+
+use alloc::vec::Vec;
+
+use crate::engine;
+use crate::feedback::{FeedbackRule, Wordle};
+use crate::progress::{record_leader, Progress, PROGRESS_STEPS};
+use crate::word::Word;
+
+pub use crate::engine::{pattern_code, MAX_WORD_LEN};
+
+/// Scratch space for the scoring functions. Create one per thread and reuse it.
+#[derive(Debug, Default, Clone)]
+pub struct ScoringBuffers {
+  counts: Vec<u32>,
+  sizes: Vec<u32>,
+  weights: Vec<f64>,
+  masses: Vec<f64>,
+}
+
+impl ScoringBuffers {
+  pub fn new() -> ScoringBuffers {
+    ScoringBuffers::default()
+  }
+
+  /// Subdivides the candidates by the pattern they give for `guess` and returns the sizes of the
+  /// non-empty buckets, in no particular order.
+  pub fn bucket_sizes<S: AsRef<str>>(&mut self, guess: &str, candidates: &[S]) -> &mut [u32] {
+    self.bucket_sizes_with(&Wordle, guess, candidates)
+  }
+
+  /// Like [`ScoringBuffers::bucket_sizes`], but the buckets are the patterns of the given rule
+  pub fn bucket_sizes_with<R, S>(&mut self, rule: &R, guess: &str, candidates: &[S]) -> &mut [u32]
+  where
+    R: FeedbackRule + ?Sized,
+    S: AsRef<str>,
+  {
+    let patterns = rule.pattern_count(guess.chars().count());
+    if self.counts.len() < patterns {
+      self.counts.resize(patterns, 0);
+    }
+
+    for candidate in candidates {
+      self.counts[rule.pattern_code(guess, candidate.as_ref())] += 1;
+    }
+
+    self.collect_sizes(patterns)
+  }
+
+  /// Like [`ScoringBuffers::bucket_sizes`], but for words already decoded to a fixed length
+  pub fn word_bucket_sizes<const N: usize>(
+    &mut self,
+    guess: &Word<N>,
+    candidates: &[Word<N>],
+  ) -> &mut [u32] {
+    let patterns = 3usize.pow(N as u32);
+    if self.counts.len() < patterns {
+      self.counts.resize(patterns, 0);
+    }
+
+    for candidate in candidates {
+      self.counts[guess.pattern_code(candidate)] += 1;
+    }
+
+    self.collect_sizes(patterns)
+  }
+
+  /// Like [`ScoringBuffers::word_bucket_sizes`], but returns the total weight of each non-empty
+  /// bucket, `weights` giving the weight of each candidate
+  pub fn word_bucket_weights<const N: usize>(
+    &mut self,
+    guess: &Word<N>,
+    candidates: &[Word<N>],
+    weights: &[f64],
+  ) -> &mut [f64] {
+    let patterns = 3usize.pow(N as u32);
+    if self.weights.len() < patterns {
+      self.weights.resize(patterns, 0.0);
+    }
+
+    for (candidate, &weight) in candidates.iter().zip(weights) {
+      self.weights[guess.pattern_code(candidate)] += weight;
+    }
+
+    self.masses.clear();
+    for weight in self.weights[..patterns].iter_mut() {
+      if *weight > 0.0 {
+        self.masses.push(*weight);
+        *weight = 0.0;
+      }
+    }
+
+    &mut self.masses
+  }
+
+  fn collect_sizes(&mut self, patterns: usize) -> &mut [u32] {
+    self.sizes.clear();
+    for count in self.counts[..patterns].iter_mut() {
+      if *count > 0 {
+        self.sizes.push(*count);
+        *count = 0;
+      }
+    }
+
+    &mut self.sizes
+  }
+}
+
+/// Expected information (in bits) of playing `guess`, i.e. the entropy of its bucket sizes
+pub fn average_information<S: AsRef<str>>(
+  guess: &str,
+  candidates: &[S],
+  buffers: &mut ScoringBuffers,
+) -> f64 {
+  engine::entropy(buffers.bucket_sizes(guess, candidates))
+}
+
+/// Information (in bits) of `guess` assuming Wordle answers with the largest bucket
+pub fn worst_case_information<S: AsRef<str>>(
+  guess: &str,
+  candidates: &[S],
+  buffers: &mut ScoringBuffers,
+) -> f64 {
+  engine::worst_case_information(buffers.bucket_sizes(guess, candidates))
+}
+
+/// Information (in bits) of the bucket found at the `gambling_factor` percentile when buckets
+/// are ordered from largest to smallest. A factor of 0 is the worst case.
+pub fn gambling_information<S: AsRef<str>>(
+  guess: &str,
+  candidates: &[S],
+  gambling_factor: f64,
+  buffers: &mut ScoringBuffers,
+) -> f64 {
+  engine::gambling_information(buffers.bucket_sizes(guess, candidates), gambling_factor)
+}
+
+/// Scores every guess against the candidates using `score`, which is given a fresh or reused
+/// [`ScoringBuffers`] for each call.
+///
+/// `progress` is called roughly [`PROGRESS_STEPS`] times with the share of guesses scored and the
+/// best guesses found so far (higher scores are better). With the `std` feature the guesses are
+/// scored in parallel, so the callback may be invoked from several threads and reports may
+/// arrive slightly out of order. The returned scores are in the same order as the guesses.
+#[cfg(feature = "std")]
+pub fn score_guesses<'a, G, C, F, P>(
+  guesses: &'a [G],
+  candidates: &[C],
+  score: F,
+  progress: P,
+) -> Vec<f64>
+where
+  G: AsRef<str> + Sync,
+  C: Sync,
+  F: Fn(&str, &[C], &mut ScoringBuffers) -> f64 + Sync,
+  P: Fn(Progress<'a>) + Sync,
+{
+  use rayon::prelude::*;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Mutex;
+
+  let total = guesses.len();
+  let step = (total / PROGRESS_STEPS).max(1);
+  let done = AtomicUsize::new(0);
+  let leaders: Mutex<Vec<(usize, f64)>> = Mutex::new(Vec::new());
+
+  guesses
+    .par_iter()
+    .enumerate()
+    .map_init(ScoringBuffers::new, |buffers, (index, guess)| {
+      let value = score(guess.as_ref(), candidates, buffers);
+
+      let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+      let report = finished.is_multiple_of(step) || finished == total;
+      let leaders_now = {
+        let mut leaders = leaders.lock().unwrap();
+        record_leader(&mut leaders, index, value);
+        if report {
+          Some(leaders.clone())
+        } else {
+          None
+        }
+      };
+
+      if let Some(leaders_now) = leaders_now {
+        progress(Progress {
+          done: finished,
+          total,
+          leaders: leaders_now
+            .into_iter()
+            .map(|(index, value)| (guesses[index].as_ref(), value))
+            .collect(),
+        });
+      }
+
+      value
+    })
+    .collect()
+}
+
+/// Scores every guess against the candidates using `score`, reusing one [`ScoringBuffers`].
+///
+/// `progress` is called roughly [`PROGRESS_STEPS`] times with the share of guesses scored and the
+/// best guesses found so far (higher scores are better). The returned scores are in the same order
+/// as the guesses.
+#[cfg(not(feature = "std"))]
+pub fn score_guesses<'a, G, C, F, P>(
+  guesses: &'a [G],
+  candidates: &[C],
+  score: F,
+  progress: P,
+) -> Vec<f64>
+where
+  G: AsRef<str> + Sync,
+  C: Sync,
+  F: Fn(&str, &[C], &mut ScoringBuffers) -> f64 + Sync,
+  P: Fn(Progress<'a>) + Sync,
+{
+  let total = guesses.len();
+  let step = (total / PROGRESS_STEPS).max(1);
+  let mut buffers = ScoringBuffers::new();
+  let mut leaders: Vec<(&'a str, f64)> = Vec::new();
+
+  guesses
+    .iter()
+    .enumerate()
+    .map(|(index, guess)| {
+      let value = score(guess.as_ref(), candidates, &mut buffers);
+      record_leader(&mut leaders, guess.as_ref(), value);
+
+      let finished = index + 1;
+      if finished.is_multiple_of(step) || finished == total {
+        progress(Progress {
+          done: finished,
+          total,
+          leaders: leaders.clone(),
+        });
+      }
+
+      value
+    })
+    .collect()
+}
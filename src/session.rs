@@ -0,0 +1,98 @@
+//! Several puzzles solved side by side in interactive mode, e.g. to help two friends at once
+//! without paying for the opening suggestions twice.
+
+use std::collections::HashSet;
+use wordle_solver::constraints::Constraints;
+use wordle_solver::mark::Mark;
+
+use crate::{DictString, Scored};
+
+/// The state of one puzzle
+pub struct Session<'a> {
+  pub name: String,
+  pub candidates: Vec<&'a DictString>,
+  pub constraints: Constraints,
+  pub suggestions: Scored<'a>,
+  pub guesses: Scored<'a>,
+  /// Guesses entered so far and their marks
+  pub board: Vec<(String, Vec<Mark>)>,
+  /// Words the game refused, left out of the suggestions
+  pub rejected: HashSet<String>,
+}
+
+/// All open sessions and the one the feedback applies to
+pub struct Sessions<'a> {
+  candidates: Vec<&'a DictString>,
+  opening: (Scored<'a>, Scored<'a>),
+  sessions: Vec<Session<'a>>,
+  current: usize,
+}
+
+impl<'a> Sessions<'a> {
+  /// Starts with a single session named "1". New sessions start from `candidates` and the
+  /// already computed `opening` suggestions.
+  pub fn new(candidates: Vec<&'a DictString>, opening: (Scored<'a>, Scored<'a>)) -> Sessions<'a> {
+    let mut sessions = Sessions {
+      candidates,
+      opening,
+      sessions: vec![],
+      current: 0,
+    };
+    sessions.create(None).unwrap();
+    sessions
+  }
+
+  pub fn current(&mut self) -> &mut Session<'a> {
+    &mut self.sessions[self.current]
+  }
+
+  /// Opens a new session and switches to it. Without a name, the session is numbered.
+  pub fn create(&mut self, name: Option<&str>) -> Result<&Session<'a>, String> {
+    let name = match name {
+      Some(name) => name.to_string(),
+      None => (1..)
+        .map(|number: usize| number.to_string())
+        .find(|name| self.find(name).is_none())
+        .unwrap(),
+    };
+    if self.find(&name).is_some() {
+      return Err(format!("There already is a session named {:?}", name));
+    }
+
+    self.sessions.push(Session {
+      name,
+      candidates: self.candidates.clone(),
+      constraints: Constraints::new(),
+      suggestions: self.opening.0.clone(),
+      guesses: self.opening.1.clone(),
+      board: vec![],
+      rejected: HashSet::new(),
+    });
+    self.current = self.sessions.len() - 1;
+    Ok(&self.sessions[self.current])
+  }
+
+  /// Makes the named session the current one
+  pub fn switch(&mut self, name: &str) -> Result<&Session<'a>, String> {
+    self.current = self
+      .find(name)
+      .ok_or_else(|| format!("There is no session named {:?}", name))?;
+    Ok(&self.sessions[self.current])
+  }
+
+  /// All sessions, along with whether they are the current one
+  pub fn list(&self) -> impl Iterator<Item = (bool, &Session<'a>)> {
+    self
+      .sessions
+      .iter()
+      .enumerate()
+      .map(move |(index, session)| (index == self.current, session))
+  }
+
+  fn find(&self, name: &str) -> Option<usize> {
+    self
+      .sessions
+      .iter()
+      .position(|session| session.name == name)
+  }
+}
@@ -0,0 +1,1555 @@
+//! Picking guesses: scoring every allowed word against the remaining candidates.
+//!
+//! This is what the command line solver runs each turn, for programs embedding it:
+//!
+//! ```no_run
+//! use wordle_solver::engine::compute_bucket;
+//! use wordle_solver::solver::{best_attempt, get_suggestions, reduce_dictionary, Average};
+//!
+//! let allowed = vec!["crane", "slate", "plate", "elate"];
+//! let mut candidates = vec!["plate", "elate", "slate"];
+//!
+//! let marks = compute_bucket("crane", "plate");
+//! candidates = reduce_dictionary("crane", &marks, &candidates);
+//! let (suggestions, guesses) = get_suggestions(&allowed, &candidates, &Average);
+//! println!("Play {:?}", best_attempt(&suggestions, &guesses, candidates.len()));
+//! ```
+//!
+//! Scoring functions of your own plug in by implementing [`Strategy`]:
+//!
+//! ```no_run
+//! use wordle_solver::solver::{get_suggestions, DictWord, Strategy};
+//!
+//! /// Prefers the guesses leaving the most groups of candidates
+//! struct MostGroups;
+//!
+//! impl Strategy for MostGroups {
+//!   fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+//!     let mut patterns: Vec<_> = candidates.iter().map(|word| guess.pattern_code(word)).collect();
+//!     patterns.sort_unstable();
+//!     patterns.dedup();
+//!     patterns.len() as f64
+//!   }
+//! }
+//!
+//! let words = vec!["crane", "slate", "plate", "elate"];
+//! let (suggestions, _) = get_suggestions(&words, &words, &MostGroups);
+//! ```
+//!
+//! It needs the `std` feature, as scoring runs on all cores.
+
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::arena::WordArena;
+use crate::engine;
+use crate::heuristics::LetterTables;
+use crate::mark::Mark;
+use crate::progress::Progress;
+use crate::random::Rng;
+use crate::scoring::{self, ScoringBuffers};
+use crate::word::Word;
+
+/// Number of letters of the words the solver plays
+pub const WORD_LENGTH: usize = 5;
+
+/// A word of the dictionaries, decoded for scoring
+pub type DictWord = Word<WORD_LENGTH>;
+
+/// How likely each candidate is to be the answer, relative to the others. Missing words weigh 1.
+pub type CandidateWeights = HashMap<DictWord, f64>;
+
+/// Words with their scores, best first
+pub type Scored<'a> = Vec<(&'a str, f64)>;
+
+/// How much [`best_attempt`] favors the best candidate, which can win right away: its chance of
+/// being the answer times this many bits is added to its score
+pub const DEFAULT_LAMBDA: f64 = 1.0;
+
+/// How guesses are scored. The built-in strategies are the structs below, implement it to try
+/// other scoring functions.
+pub trait Strategy: Send + Sync {
+  /// Score of `guess` against the candidates still possible, higher is better
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64;
+
+  /// [`Strategy::score`], reusing `buffers` between calls. Override it to avoid allocating for
+  /// every guess.
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    _buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    self.score(guess, candidates)
+  }
+
+  /// The letter table heuristic computing the score, for the strategies that don't need the
+  /// partition of the candidates
+  fn heuristic(&self) -> Option<fn(&LetterTables, &str) -> f64> {
+    None
+  }
+
+  /// The score from the sizes of the groups a guess splits `candidates` candidates into, for the
+  /// strategies needing nothing else. These also score words of other lengths, see
+  /// [`get_word_suggestions`].
+  fn score_sizes(&self, _sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    None
+  }
+
+  /// How likely each candidate is, for the strategies that don't count them all the same
+  fn candidate_weights(&self) -> Option<&CandidateWeights> {
+    None
+  }
+
+  /// Name of the strategy and its parameters, as recorded in a [`GameState`]
+  fn name(&self) -> String {
+    "custom".to_string()
+  }
+
+  /// Whether the score already counts the chance of the guess being the answer, as the expected
+  /// number of guesses of [`Optimal`](crate::optimal::Optimal) does. The bonus [`best_attempt`]
+  /// gives the best candidate for that chance is then left out.
+  fn counts_wins(&self) -> bool {
+    false
+  }
+
+  /// The strategy to score with on `turn`, counting from 1, with `candidates` left, for the
+  /// strategies that change along the game like [`Schedule`]. `None` to score with this one.
+  fn at_turn(&self, _turn: usize, _candidates: usize) -> Option<&dyn Strategy> {
+    None
+  }
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.as_ref().score(guess, candidates)
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    self.as_ref().score_with(guess, candidates, buffers)
+  }
+
+  fn heuristic(&self) -> Option<fn(&LetterTables, &str) -> f64> {
+    self.as_ref().heuristic()
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], candidates: usize) -> Option<f64> {
+    self.as_ref().score_sizes(sizes, candidates)
+  }
+
+  fn candidate_weights(&self) -> Option<&CandidateWeights> {
+    self.as_ref().candidate_weights()
+  }
+
+  fn name(&self) -> String {
+    self.as_ref().name()
+  }
+
+  fn counts_wins(&self) -> bool {
+    self.as_ref().counts_wins()
+  }
+
+  fn at_turn(&self, turn: usize, candidates: usize) -> Option<&dyn Strategy> {
+    self.as_ref().at_turn(turn, candidates)
+  }
+}
+
+/// The built-in strategy with this [`Strategy::name`]: `average`, `worst-case`,
+/// `gambling <factor>`, `risk <r>`, `largest-group`, `max-splits`, `expected-tiles`,
+/// `positional-frequency`, `least-information` or `fibble`. See [`StrategyRegistry::create`] for
+/// the forms accepted.
+pub fn strategy_by_name(name: &str) -> Result<Box<dyn Strategy>, String> {
+  StrategyRegistry::builtin().create(name)
+}
+
+/// Makes a strategy from the parameters given after its name
+pub type StrategyConstructor =
+  Box<dyn Fn(&[&str]) -> Result<Box<dyn Strategy>, String> + Send + Sync>;
+
+/// Strategies by name, to pick one at run time. Starts with the built-in strategies, and programs
+/// embedding the solver [`register`](StrategyRegistry::register) their own:
+///
+/// ```no_run
+/// use wordle_solver::solver::{strategy_param, StrategyRegistry, WorstCase};
+///
+/// let mut registry = StrategyRegistry::builtin();
+/// registry.register("cautious", |params| {
+///   let _turns: usize = strategy_param("cautious", params)?;
+///   Ok(Box::new(WorstCase))
+/// });
+/// let strategy = registry.create("cautious:2").unwrap();
+/// ```
+pub struct StrategyRegistry {
+  /// In the order they were registered
+  constructors: Vec<(String, StrategyConstructor)>,
+}
+
+/// The single parameter of strategy `name`, e.g. the factor of `gambling:0.5`
+pub fn strategy_param<T: FromStr>(name: &str, params: &[&str]) -> Result<T, String> {
+  match params {
+    [param] => param
+      .parse()
+      .map_err(|_| format!("invalid parameter {:?} for {}", param, name)),
+    _ => Err(format!(
+      "{} takes one parameter, e.g. {}:<value>",
+      name, name
+    )),
+  }
+}
+
+/// Registers `strategy`, which takes no parameter, under `name`
+fn without_params<S: Strategy + Copy + 'static>(
+  registry: &mut StrategyRegistry,
+  name: &'static str,
+  strategy: S,
+) {
+  registry.register(name, move |params| match params {
+    [] => Ok(Box::new(strategy)),
+    _ => Err(format!("{} takes no parameter", name)),
+  });
+}
+
+impl StrategyRegistry {
+  /// A registry without any strategy
+  pub fn new() -> StrategyRegistry {
+    StrategyRegistry {
+      constructors: vec![],
+    }
+  }
+
+  /// A registry with the strategies of [`strategy_by_name`]
+  pub fn builtin() -> StrategyRegistry {
+    let mut registry = StrategyRegistry::new();
+    without_params(&mut registry, "average", Average);
+    without_params(&mut registry, "worst-case", WorstCase);
+    registry.register("gambling", |params| {
+      Ok(Box::new(Gambling(strategy_param("gambling", params)?)))
+    });
+    registry.register("risk", |params| {
+      let risk = strategy_param("risk", params)?;
+      if !(0.0..=1.0).contains(&risk) {
+        return Err("risk goes from 0 (average) to 1 (worst case)".to_string());
+      }
+      Ok(Box::new(Risk(risk)))
+    });
+    without_params(&mut registry, "largest-group", LargestGroup);
+    without_params(&mut registry, "max-splits", MaxSplits);
+    without_params(&mut registry, "expected-tiles", ExpectedTiles);
+    without_params(&mut registry, "positional-frequency", PositionalFrequency);
+    without_params(&mut registry, "least-information", LeastInformation);
+    without_params(&mut registry, "fibble", Fibble);
+    registry
+  }
+
+  /// Makes the strategies named `name` with `constructor`, replacing any registered before
+  pub fn register<F>(&mut self, name: &str, constructor: F)
+  where
+    F: Fn(&[&str]) -> Result<Box<dyn Strategy>, String> + Send + Sync + 'static,
+  {
+    let constructor: StrategyConstructor = Box::new(constructor);
+    match self
+      .constructors
+      .iter_mut()
+      .find(|(known, _)| known == name)
+    {
+      Some((_, known)) => *known = constructor,
+      None => self.constructors.push((name.to_string(), constructor)),
+    }
+  }
+
+  /// The names registered
+  pub fn names(&self) -> impl Iterator<Item = &str> {
+    self.constructors.iter().map(|(name, _)| name.as_str())
+  }
+
+  /// The strategy of `spec`, its name followed by its parameters, either after a colon and
+  /// separated by commas like `gambling:0.5`, or after spaces like the [`Strategy::name`]
+  /// `gambling 0.5`
+  pub fn create(&self, spec: &str) -> Result<Box<dyn Strategy>, String> {
+    let spec = spec.trim();
+    let (name, params): (&str, Vec<&str>) = match spec.split_once(':') {
+      Some((name, params)) => (name.trim(), params.split(',').map(str::trim).collect()),
+      None => {
+        let mut words = spec.split_whitespace();
+        (words.next().unwrap_or(""), words.collect())
+      }
+    };
+    match self.constructors.iter().find(|(known, _)| known == name) {
+      Some((_, constructor)) => constructor(&params),
+      None => {
+        let names: Vec<&str> = self.names().collect();
+        Err(format!(
+          "unknown strategy {:?}, pick one of {}",
+          name,
+          names.join(", ")
+        ))
+      }
+    }
+  }
+}
+
+impl Default for StrategyRegistry {
+  fn default() -> StrategyRegistry {
+    StrategyRegistry::new()
+  }
+}
+
+/// Average information, the entropy of the partition of the candidates
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Average;
+
+impl Strategy for Average {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    Some(engine::entropy(sizes))
+  }
+
+  fn name(&self) -> String {
+    "average".to_string()
+  }
+}
+
+/// Minus the average information, for Antiwordle where the guesses should tell as little as
+/// possible. The chance of the guess being the answer is part of the information, so no bonus is
+/// given for it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LeastInformation;
+
+impl Strategy for LeastInformation {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    Some(-engine::entropy(sizes))
+  }
+
+  fn name(&self) -> String {
+    "least-information".to_string()
+  }
+
+  fn counts_wins(&self) -> bool {
+    true
+  }
+}
+
+/// Average information about the answer in Fibble, where one mark of every pattern is a lie, any
+/// other mark at any position being as likely. The entropy of the patterns reported rather than
+/// of the true ones, minus what the lie adds: the log2 of the `2 * WORD_LENGTH` lies each answer
+/// can get. Playing the answer wins whatever the lie, so its pattern tells it for sure.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Fibble;
+
+impl Strategy for Fibble {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    if candidates.is_empty() {
+      return 0.0;
+    }
+    let lies = 2 * WORD_LENGTH;
+    let mut counts = [0u32; crate::pattern::PATTERN_COUNT];
+    let mut lying = 0;
+    for candidate in candidates {
+      let code = guess.pattern_code(candidate);
+      if candidate == guess {
+        counts[code] += lies as u32;
+        continue;
+      }
+      lying += 1;
+      let mut place = 1;
+      for _ in 0..WORD_LENGTH {
+        let digit = code / place % 3;
+        for other in (0..3).filter(|&other| other != digit) {
+          counts[code - digit * place + other * place] += 1;
+        }
+        place *= 3;
+      }
+    }
+    engine::entropy(&counts) - (lies as f64).log2() * lying as f64 / candidates.len() as f64
+  }
+
+  fn name(&self) -> String {
+    "fibble".to_string()
+  }
+}
+
+/// Information in the worst case, the size of the largest group of candidates left
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WorstCase;
+
+impl Strategy for WorstCase {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    Some(engine::worst_case_information(sizes))
+  }
+
+  fn name(&self) -> String {
+    "worst-case".to_string()
+  }
+}
+
+/// Minus the number of candidates in the largest group left, ties broken by average information.
+/// Ranks like [`WorstCase`], but by the count itself: the information is scaled below 1, so the
+/// integer part of minus the score is the count, and guesses leaving all the candidates together
+/// still rank by how they split the rest.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LargestGroup;
+
+impl LargestGroup {
+  /// The number of candidates in the largest group, from a score
+  pub fn count(score: f64) -> usize {
+    (-score).ceil() as usize
+  }
+}
+
+impl Strategy for LargestGroup {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], candidates: usize) -> Option<f64> {
+    let largest = sizes.iter().copied().max().unwrap_or(0) as f64;
+    // The information of n candidates is at most log2(n)
+    let total = candidates as f64;
+    Some(-largest + engine::entropy(sizes) / (total.log2() + 1.0))
+  }
+
+  fn name(&self) -> String {
+    "largest-group".to_string()
+  }
+}
+
+/// Information of the group of candidates at the given percentile, the groups ordered from the
+/// largest, see [`engine::gambling_information`]. 0 is the [`WorstCase`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Gambling(pub f64);
+
+impl Strategy for Gambling {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    Some(engine::gambling_information(sizes, self.0))
+  }
+
+  fn name(&self) -> String {
+    format!("gambling {}", self.0)
+  }
+}
+
+/// Between [`WorstCase`] and [`Average`]: `risk` times the information in the worst case plus the
+/// rest times the average information. 0 is the average, 1 the worst case.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Risk(pub f64);
+
+impl Strategy for Risk {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    Some(self.0 * engine::worst_case_information(sizes) + (1.0 - self.0) * engine::entropy(sizes))
+  }
+
+  fn name(&self) -> String {
+    format!("risk {}", self.0)
+  }
+}
+
+/// Number of groups the candidates are split into, ties broken by average information. The
+/// information is scaled below 1, so it never outweighs one more group.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MaxSplits;
+
+impl Strategy for MaxSplits {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    sizes_score(
+      self,
+      buffers.word_bucket_sizes(guess, candidates),
+      candidates.len(),
+    )
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], _candidates: usize) -> Option<f64> {
+    let groups = sizes.len() as f64;
+    // The information of n groups is at most log2(n)
+    Some(groups + engine::entropy(sizes) / (groups + 1.0).log2())
+  }
+
+  fn name(&self) -> String {
+    "max-splits".to_string()
+  }
+}
+
+/// Expected number of green and yellow tiles, see [`LetterTables`]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExpectedTiles;
+
+impl Strategy for ExpectedTiles {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    heuristic_score(LetterTables::expected_tiles, guess, candidates)
+  }
+
+  fn heuristic(&self) -> Option<fn(&LetterTables, &str) -> f64> {
+    Some(LetterTables::expected_tiles)
+  }
+
+  fn name(&self) -> String {
+    "expected-tiles".to_string()
+  }
+}
+
+/// Information estimated from letter/position frequencies, see [`LetterTables`]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PositionalFrequency;
+
+impl Strategy for PositionalFrequency {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    heuristic_score(LetterTables::positional_information, guess, candidates)
+  }
+
+  fn heuristic(&self) -> Option<fn(&LetterTables, &str) -> f64> {
+    Some(LetterTables::positional_information)
+  }
+
+  fn name(&self) -> String {
+    "positional-frequency".to_string()
+  }
+}
+
+/// Average information where each candidate counts as much as it is likely to be the answer
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Weighted<'w>(pub &'w CandidateWeights);
+
+impl Strategy for Weighted<'_> {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    let weights: Vec<f64> = candidates
+      .iter()
+      .map(|word| weight_of(self.0, word))
+      .collect();
+    engine::weighted_entropy(buffers.word_bucket_weights(guess, candidates, &weights))
+  }
+
+  fn candidate_weights(&self) -> Option<&CandidateWeights> {
+    Some(self.0)
+  }
+
+  fn name(&self) -> String {
+    "weighted".to_string()
+  }
+}
+
+/// When a stage of a [`Schedule`] ends
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Until {
+  /// After this turn, counting from 1
+  Turn(usize),
+  /// Once this many candidates or fewer are left
+  Candidates(usize),
+  /// Never, for the last stage
+  End,
+}
+
+/// Different strategies along the game, e.g. the average for the first two turns, the worst case
+/// until 20 candidates are left and then an exact search. Each turn is scored with the first
+/// stage that hasn't ended.
+///
+/// The turn is only known to the programs playing the game, through [`Strategy::at_turn`].
+/// Scored without it, stages ending at a turn are skipped and only the candidates left count.
+pub struct Schedule {
+  stages: Vec<(Box<dyn Strategy>, Until)>,
+}
+
+impl Schedule {
+  /// Starts with `strategy` until `until`
+  pub fn new<S: Strategy + 'static>(strategy: S, until: Until) -> Schedule {
+    Schedule {
+      stages: vec![(Box::new(strategy), until)],
+    }
+  }
+
+  /// Adds a stage playing `strategy` until `until`
+  pub fn then<S: Strategy + 'static>(mut self, strategy: S, until: Until) -> Schedule {
+    self.stages.push((Box::new(strategy), until));
+    self
+  }
+
+  /// Reads stages like `average until turn 2, worst-case until 20 candidates, optimal`, the
+  /// strategies named as `by_name` knows them. The last stage goes on to the end.
+  pub fn parse<F>(text: &str, by_name: F) -> Result<Schedule, String>
+  where
+    F: Fn(&str) -> Result<Box<dyn Strategy>, String>,
+  {
+    let mut stages = vec![];
+    for stage in text.split(',') {
+      let (name, until) = match stage.split_once(" until ") {
+        None => (stage, Until::End),
+        Some((name, until)) => {
+          let until = match until.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["turn", turn] => turn.parse().ok().map(Until::Turn),
+            [count, "candidates"] => count.parse().ok().map(Until::Candidates),
+            _ => None,
+          };
+          let until = until.ok_or_else(|| {
+            format!(
+              "a stage ends \"until turn <n>\" or \"until <n> candidates\", not {:?}",
+              stage.trim()
+            )
+          })?;
+          (name, until)
+        }
+      };
+      stages.push((by_name(name.trim())?, until));
+    }
+    Ok(Schedule { stages })
+  }
+
+  /// The strategy of the first stage not over on `turn`, or without a turn of the first not over
+  /// with `candidates` left
+  fn stage(&self, turn: Option<usize>, candidates: usize) -> &dyn Strategy {
+    let current = self.stages.iter().find(|(_, until)| match *until {
+      Until::Turn(last) => turn.is_some_and(|turn| turn <= last),
+      Until::Candidates(count) => candidates > count,
+      Until::End => true,
+    });
+    let (strategy, _) = current.unwrap_or_else(|| self.stages.last().unwrap());
+    strategy.as_ref()
+  }
+}
+
+impl Strategy for Schedule {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.stage(None, candidates.len()).score(guess, candidates)
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    self
+      .stage(None, candidates.len())
+      .score_with(guess, candidates, buffers)
+  }
+
+  fn score_sizes(&self, sizes: &mut [u32], candidates: usize) -> Option<f64> {
+    self.stage(None, candidates).score_sizes(sizes, candidates)
+  }
+
+  fn name(&self) -> String {
+    let stages: Vec<String> = self
+      .stages
+      .iter()
+      .map(|(strategy, until)| match until {
+        Until::Turn(turn) => format!("{} until turn {}", strategy.name(), turn),
+        Until::Candidates(count) => format!("{} until {} candidates", strategy.name(), count),
+        Until::End => strategy.name(),
+      })
+      .collect();
+    stages.join(", ")
+  }
+
+  fn at_turn(&self, turn: usize, candidates: usize) -> Option<&dyn Strategy> {
+    // A stage can itself be a schedule
+    let stage = self.stage(Some(turn), candidates);
+    Some(stage.at_turn(turn, candidates).unwrap_or(stage))
+  }
+}
+
+/// The [`Strategy::score_sizes`] of a strategy scoring the groups of candidates
+fn sizes_score(strategy: &dyn Strategy, sizes: &mut [u32], candidates: usize) -> f64 {
+  strategy
+    .score_sizes(sizes, candidates)
+    .expect("the strategy scores the groups")
+}
+
+/// Score of a letter table heuristic, building the tables of the candidates
+fn heuristic_score(
+  heuristic: fn(&LetterTables, &str) -> f64,
+  guess: &DictWord,
+  candidates: &[DictWord],
+) -> f64 {
+  let tables = LetterTables::new(candidates.iter().map(|word| word.letters().iter().copied()));
+  heuristic(&tables, &guess.to_string())
+}
+
+/// Decodes a dictionary word.
+///
+/// # Panics
+///
+/// If the word doesn't have [`WORD_LENGTH`] letters.
+pub fn to_word(word: &str) -> DictWord {
+  DictWord::new(word).expect("dictionary words have WORD_LENGTH letters")
+}
+
+/// Weight of `word` as a candidate, 1 unless given
+pub fn weight_of(weights: &CandidateWeights, word: &DictWord) -> f64 {
+  weights.get(word).copied().unwrap_or(1.0)
+}
+
+/// Scores of every word of `words_all` as a guess against the candidates `words_reduced`,
+/// reporting the progress as it goes
+pub fn compute_guess_scores<'a, 'p, P>(
+  words_all: &'p [&'a str],
+  words_reduced: &[&'a str],
+  strategy: &dyn Strategy,
+  progress: P,
+) -> HashMap<&'a str, f64>
+where
+  P: Fn(Progress<'p>) + Sync,
+{
+  let candidates: Vec<DictWord> = words_reduced.iter().map(|w| to_word(w)).collect();
+
+  let scores = match strategy.heuristic() {
+    // The letter tables only need to be built once for all guesses
+    Some(heuristic) => {
+      let tables = LetterTables::new(words_reduced.iter().map(|word| word.chars()));
+      scoring::score_guesses(
+        words_all,
+        &candidates,
+        |guess, _, _| heuristic(&tables, guess),
+        progress,
+      )
+    }
+    None => match strategy.candidate_weights() {
+      // Look the weights up once rather than for every guess
+      Some(weights) => {
+        let weights: Vec<f64> = candidates
+          .iter()
+          .map(|word| weight_of(weights, word))
+          .collect();
+        scoring::score_guesses(
+          words_all,
+          &candidates,
+          |guess, words, buffers| {
+            engine::weighted_entropy(buffers.word_bucket_weights(&to_word(guess), words, &weights))
+          },
+          progress,
+        )
+      }
+      None => scoring::score_guesses(
+        words_all,
+        &candidates,
+        |guess, words, buffers| {
+          compute_information_value(&to_word(guess), words, strategy, buffers)
+        },
+        progress,
+      ),
+    },
+  };
+
+  words_all.iter().copied().zip(scores).collect()
+}
+
+/// Score of a single guess against the candidates `words`
+pub fn compute_information_value(
+  guess: &DictWord,
+  words: &[DictWord],
+  strategy: &dyn Strategy,
+  buffers: &mut ScoringBuffers,
+) -> f64 {
+  strategy.score_with(guess, words, buffers)
+}
+
+/// The words of `dict` still possible after `guess` got `marks`
+pub fn reduce_dictionary<'a>(guess: &str, marks: &[Mark], dict: &[&'a str]) -> Vec<&'a str> {
+  dict
+    .par_iter()
+    .filter(|word| engine::is_consistent(guess, marks, word))
+    .copied()
+    .collect()
+}
+
+/// Low bits of the mantissa dropped from scores before ranking them: the same partition can score
+/// a few units in the last place apart, depending on the order the groups were summed in. Keeping
+/// 40 of the 52 bits makes scores within about 1e-12 of their size rank as equal.
+const SCORE_DROPPED_BITS: u32 = 12;
+
+/// `score` rounded to the precision guesses are ranked at, see [`SCORE_DROPPED_BITS`]. Rounding
+/// rather than comparing within a tolerance keeps "equal" transitive.
+fn ranking_key(score: f64) -> f64 {
+  let half = 1u64 << (SCORE_DROPPED_BITS - 1);
+  let mask = !((1u64 << SCORE_DROPPED_BITS) - 1);
+  let key = f64::from_bits(score.to_bits().wrapping_add(half) & mask);
+  // -0.0 and 0.0 are the same score
+  if key == 0.0 {
+    0.0
+  } else {
+    key
+  }
+}
+
+/// A word with its score, ordered by score, then by position in its list with the first greatest
+struct Ranked<'a> {
+  word: &'a str,
+  score: f64,
+  /// The score as ranked, see [`ranking_key`]
+  key: f64,
+  position: usize,
+}
+
+impl Ord for Ranked<'_> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self
+      .key
+      .total_cmp(&other.key)
+      .then_with(|| other.position.cmp(&self.position))
+  }
+}
+
+impl PartialOrd for Ranked<'_> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for Ranked<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Ranked<'_> {}
+
+/// Words with their scores, yielded best first. Equal scores keep the order of the words given.
+/// Only the words taken get sorted, so the few best of a whole dictionary cost little more than
+/// scoring it.
+pub struct RankedGuesses<'a> {
+  heap: BinaryHeap<Ranked<'a>>,
+}
+
+impl<'a> RankedGuesses<'a> {
+  fn new(words: &[&'a str], scores: &HashMap<&'a str, f64>) -> RankedGuesses<'a> {
+    let ranked = words.iter().enumerate().map(|(position, &word)| {
+      let score = scores.get(word).copied().unwrap_or(0.0);
+      Ranked {
+        word,
+        score,
+        key: ranking_key(score),
+        position,
+      }
+    });
+    RankedGuesses {
+      heap: ranked.collect(),
+    }
+  }
+
+  /// The `n` best words, best first
+  pub fn top(self, n: usize) -> Scored<'a> {
+    self.take(n).collect()
+  }
+}
+
+impl<'a> Iterator for RankedGuesses<'a> {
+  type Item = (&'a str, f64);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.heap.pop().map(|ranked| (ranked.word, ranked.score))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.heap.len(), Some(self.heap.len()))
+  }
+}
+
+impl ExactSizeIterator for RankedGuesses<'_> {}
+
+/// Scores every word of `dict` against the candidates `reduced_dict`. Returns the words of `dict`
+/// and those of `reduced_dict`, each ranked best first.
+pub fn rank_suggestions<'a>(
+  dict: &[&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+) -> (RankedGuesses<'a>, RankedGuesses<'a>) {
+  rank_suggestions_with_progress(dict, reduced_dict, strategy, |_| {})
+}
+
+/// [`rank_suggestions`], reporting the progress of the scoring as it goes
+pub fn rank_suggestions_with_progress<'a, 'p, P>(
+  dict: &'p [&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+  progress: P,
+) -> (RankedGuesses<'a>, RankedGuesses<'a>)
+where
+  P: Fn(Progress<'p>) + Sync,
+{
+  let _scoring = tracing::debug_span!(
+    "scoring",
+    strategy = %strategy.name(),
+    guesses = dict.len(),
+    candidates = reduced_dict.len()
+  )
+  .entered();
+  let scores = compute_guess_scores(dict, reduced_dict, strategy, progress);
+  (
+    RankedGuesses::new(dict, &scores),
+    RankedGuesses::new(reduced_dict, &scores),
+  )
+}
+
+/// The `n` best words of `dict` and of `reduced_dict`, see [`rank_suggestions`]
+pub fn top_suggestions<'a>(
+  dict: &[&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+  n: usize,
+) -> (Scored<'a>, Scored<'a>) {
+  let (suggestions, guesses) = rank_suggestions(dict, reduced_dict, strategy);
+  (suggestions.top(n), guesses.top(n))
+}
+
+/// Scores every word of `dict` against the candidates `reduced_dict`. Returns all of `dict` and
+/// all of `reduced_dict`, each sorted best first. Use [`top_suggestions`] when only the best few
+/// are needed.
+pub fn get_suggestions<'a>(
+  dict: &[&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+) -> (Scored<'a>, Scored<'a>) {
+  get_suggestions_with_progress(dict, reduced_dict, strategy, |_| {})
+}
+
+/// [`get_suggestions`], reporting the progress of the scoring as it goes
+pub fn get_suggestions_with_progress<'a, 'p, P>(
+  dict: &'p [&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+  progress: P,
+) -> (Scored<'a>, Scored<'a>)
+where
+  P: Fn(Progress<'p>) + Sync,
+{
+  let (suggestions, guesses) =
+    rank_suggestions_with_progress(dict, reduced_dict, strategy, progress);
+  (suggestions.collect(), guesses.collect())
+}
+
+/// Scores of every word of `words_all` as a guess against the candidates `words_reduced`, words
+/// of `N` letters, see [`compute_guess_scores`]. `None` unless the strategy scores by letter tables
+/// or by the groups of candidates alone, the others only scoring words of [`WORD_LENGTH`] letters.
+///
+/// # Panics
+///
+/// If a word doesn't have `N` letters.
+pub fn compute_word_scores<'a, const N: usize>(
+  words_all: &[&'a str],
+  words_reduced: &[&'a str],
+  strategy: &dyn Strategy,
+) -> Option<HashMap<&'a str, f64>> {
+  let decode = |word: &str| Word::<N>::new(word).expect("the words have N letters");
+  let candidates: Vec<Word<N>> = words_reduced.iter().map(|word| decode(word)).collect();
+
+  let scores = match strategy.heuristic() {
+    Some(heuristic) => {
+      let tables = LetterTables::new(words_reduced.iter().map(|word| word.chars()));
+      scoring::score_guesses(
+        words_all,
+        &candidates,
+        |guess, _, _| heuristic(&tables, guess),
+        |_| {},
+      )
+    }
+    // Any groups tell whether the strategy scores them
+    None if strategy.score_sizes(&mut [1], 1).is_some() => scoring::score_guesses(
+      words_all,
+      &candidates,
+      |guess, words, buffers| {
+        let sizes = buffers.word_bucket_sizes(&decode(guess), words);
+        sizes_score(strategy, sizes, words.len())
+      },
+      |_| {},
+    ),
+    None => return None,
+  };
+
+  Some(words_all.iter().copied().zip(scores).collect())
+}
+
+/// [`get_suggestions`] for words of `N` letters, with the strategies
+/// [`compute_word_scores`] can score them by
+///
+/// ```
+/// use wordle_solver::solver::{get_word_suggestions, Average};
+///
+/// let words = ["bare", "care", "core", "cure", "pure", "bore"];
+/// let (suggestions, _) = get_word_suggestions::<4>(&words, &words, &Average).unwrap();
+/// // Guessing pure, bare, care, core and bore all get the same marks
+/// assert_eq!(suggestions[0].0, "care");
+/// assert_eq!(suggestions.last().unwrap().0, "pure");
+/// ```
+pub fn get_word_suggestions<'a, const N: usize>(
+  dict: &[&'a str],
+  reduced_dict: &[&'a str],
+  strategy: &dyn Strategy,
+) -> Option<(Scored<'a>, Scored<'a>)> {
+  let _scoring = tracing::debug_span!(
+    "scoring",
+    strategy = %strategy.name(),
+    guesses = dict.len(),
+    candidates = reduced_dict.len()
+  )
+  .entered();
+  let scores = compute_word_scores::<N>(dict, reduced_dict, strategy)?;
+  Some((
+    RankedGuesses::new(dict, &scores).collect(),
+    RankedGuesses::new(reduced_dict, &scores).collect(),
+  ))
+}
+
+/// Picks the word to play among the best suggestion and the best of `candidates` equally likely
+/// candidates, with [`DEFAULT_LAMBDA`], see [`best_attempt_with`]
+pub fn best_attempt<'a>(
+  suggestions: &[(&'a str, f64)],
+  guesses: &[(&'a str, f64)],
+  candidates: usize,
+) -> Option<&'a str> {
+  let chance = 1.0 / candidates.max(1) as f64;
+  best_attempt_with(suggestions, guesses, chance, DEFAULT_LAMBDA)
+}
+
+/// Picks the word to play: the best suggestion, unless the best candidate scores as well once
+/// `lambda` times `chance`, its chance of being the answer, is added to its score
+pub fn best_attempt_with<'a>(
+  suggestions: &[(&'a str, f64)],
+  guesses: &[(&'a str, f64)],
+  chance: f64,
+  lambda: f64,
+) -> Option<&'a str> {
+  match (suggestions.first(), guesses.first()) {
+    (Some(&(sug_word, sug_score)), Some(&(guess_word, guess_score))) => {
+      if sug_score >= guess_score + lambda * chance {
+        Some(sug_word)
+      } else {
+        Some(guess_word)
+      }
+    }
+    (suggestion, guess) => guess.or(suggestion).map(|&(word, _)| word),
+  }
+}
+
+/// The word to try drawn at random, a word more likely the higher it scores: the scores of
+/// `suggestions` and `guesses`, the candidates, are divided by `temperature` and exponentiated.
+/// The candidates get the bonus of [`best_attempt_with`] for their `chance` to win right away.
+/// Near 0 the best word is nearly always drawn, the higher the temperature the more often others
+/// are, until all are equally likely. A temperature of 0 picks as [`best_attempt_with`] does.
+pub fn sample_attempt<'a>(
+  suggestions: &[(&'a str, f64)],
+  guesses: &[(&'a str, f64)],
+  chance: f64,
+  lambda: f64,
+  temperature: f64,
+  rng: &mut Rng,
+) -> Option<&'a str> {
+  if temperature <= 0.0 {
+    return best_attempt_with(suggestions, guesses, chance, lambda);
+  }
+
+  let bonus = lambda * chance;
+  let mut pool: Vec<(&str, f64)> = guesses
+    .iter()
+    .map(|&(word, score)| (word, score + bonus))
+    .collect();
+  let candidates: HashSet<&str> = guesses.iter().map(|&(word, _)| word).collect();
+  for &(word, score) in suggestions {
+    if !candidates.contains(word) {
+      pool.push((word, score));
+    }
+  }
+
+  // Exponentiated from the best score down, so that the largest weight is 1
+  let best = pool
+    .iter()
+    .map(|&(_, score)| score)
+    .fold(f64::NEG_INFINITY, f64::max);
+  let weights: Vec<f64> = pool
+    .iter()
+    .map(|&(_, score)| ((score - best) / temperature).exp())
+    .collect();
+  match rng.weighted_index(&weights) {
+    Some(index) => Some(pool[index].0),
+    None => best_attempt_with(suggestions, guesses, chance, lambda),
+  }
+}
+
+/// Gets told what a [`SolverSession`] is doing, e.g. to show progress in a GUI or during a long
+/// benchmark. The methods do nothing unless overridden.
+pub trait SolverObserver: Send + Sync {
+  /// Scoring the guesses went on, called from the scoring threads
+  fn on_scoring_progress(&self, _progress: Progress<'_>) {}
+
+  /// The marks `guess` got left `after` of the `before` candidates
+  fn on_candidates_reduced(&self, _guess: &str, _before: usize, _after: usize) {}
+
+  /// The solver picked the word to play next
+  fn on_suggestion_ready(&self, _word: &str) {}
+}
+
+/// Observes nothing
+impl SolverObserver for () {}
+
+/// Where a game stands, to save it and pick it up later. The [`fmt::Display`] form has one line
+/// per guess, then the candidates:
+///
+/// ```text
+/// strategy average
+/// guess raise -+--o
+/// candidates blade plate elate
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameState {
+  /// Guesses played and the marks they got
+  pub history: Vec<(String, Vec<Mark>)>,
+  /// Words that can still be the answer
+  pub candidates: Vec<String>,
+  /// [`Strategy::name`] of the strategy playing
+  pub strategy: String,
+}
+
+impl fmt::Display for GameState {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "strategy {}", self.strategy)?;
+    for (guess, marks) in &self.history {
+      let marks: String = marks.iter().map(|mark| mark.to_char()).collect();
+      writeln!(f, "guess {} {}", guess, marks)?;
+    }
+    writeln!(f, "candidates {}", self.candidates.join(" "))
+  }
+}
+
+/// Parses the [`fmt::Display`] form, lines starting with `#` are comments
+impl FromStr for GameState {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut state = GameState::default();
+
+    for line in s.lines().map(str::trim) {
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+      match (key, value.split_whitespace().collect::<Vec<_>>().as_slice()) {
+        ("strategy", _) => state.strategy = value.trim().to_string(),
+        ("guess", &[guess, marks]) => {
+          let marks = marks
+            .chars()
+            .map(Mark::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("{} in {:?}", err, line))?;
+          state.history.push((guess.to_string(), marks));
+        }
+        ("candidates", words) => state.candidates = words.iter().map(|w| w.to_string()).collect(),
+        _ => return Err(format!("unknown game state line {:?}", line)),
+      }
+    }
+
+    Ok(state)
+  }
+}
+
+/// A game being solved, owning its dictionaries.
+///
+/// ```no_run
+/// use wordle_solver::arena::WordArena;
+/// use wordle_solver::mark::Mark;
+/// use wordle_solver::solver::{Average, SolverSession};
+///
+/// let words: WordArena = ["crane", "slate", "plate", "elate"].iter().collect();
+/// let mut session = SolverSession::new(words.clone(), words, Average);
+/// let marks: Vec<Mark> = "--o-o".chars().map(|c| c.to_string().parse().unwrap()).collect();
+/// session.apply_feedback("crane", &marks);
+/// println!("{:?} left, play {:?}", session.remaining_candidates(), session.best_attempt());
+/// ```
+pub struct SolverSession {
+  /// Words that can be played
+  allowed: WordArena,
+  /// Words that can be the answer
+  answers: WordArena,
+  strategy: Box<dyn Strategy>,
+  /// Indices in `answers` of the words still possible
+  candidates: Vec<usize>,
+  history: Vec<(String, Vec<Mark>)>,
+  observer: Option<Box<dyn SolverObserver>>,
+  /// Threads scoring runs on, rayon's global pool when `None`
+  pool: Option<rayon::ThreadPool>,
+}
+
+impl SolverSession {
+  /// Starts a game where any of `answers` can be the answer and any of `allowed` can be played
+  pub fn new<S: Strategy + 'static>(
+    allowed: WordArena,
+    answers: WordArena,
+    strategy: S,
+  ) -> SolverSession {
+    SolverSession {
+      candidates: (0..answers.len()).collect(),
+      allowed,
+      answers,
+      strategy: Box::new(strategy),
+      history: vec![],
+      observer: None,
+      pool: None,
+    }
+  }
+
+  /// Configures a session, checking the dictionaries and options before starting it
+  pub fn builder() -> SolverBuilder {
+    SolverBuilder::default()
+  }
+
+  /// Reports what the session does to `observer` from now on
+  pub fn set_observer<O: SolverObserver + 'static>(&mut self, observer: O) {
+    self.observer = Some(Box::new(observer));
+  }
+
+  fn observer(&self) -> &dyn SolverObserver {
+    self.observer.as_deref().unwrap_or(&())
+  }
+
+  pub fn strategy(&self) -> &dyn Strategy {
+    self.strategy.as_ref()
+  }
+
+  /// The words that can still be the answer
+  pub fn remaining_candidates(&self) -> Vec<&str> {
+    self
+      .candidates
+      .iter()
+      .map(|&index| &self.answers[index])
+      .collect()
+  }
+
+  /// The guesses played so far and the marks they got
+  pub fn history(&self) -> &[(String, Vec<Mark>)] {
+    &self.history
+  }
+
+  /// The guesses and candidates so far, to save the game
+  pub fn game_state(&self) -> GameState {
+    GameState {
+      history: self.history.clone(),
+      candidates: self
+        .remaining_candidates()
+        .iter()
+        .map(|word| word.to_string())
+        .collect(),
+      strategy: self.strategy.name(),
+    }
+  }
+
+  /// Keeps the candidates matching the marks `guess` got
+  pub fn apply_feedback(&mut self, guess: &str, marks: &[Mark]) {
+    let before = self.candidates.len();
+    let answers = &self.answers;
+    self
+      .candidates
+      .retain(|&index| engine::is_consistent(guess, marks, &answers[index]));
+    self.history.push((guess.to_string(), marks.to_vec()));
+    self
+      .observer()
+      .on_candidates_reduced(guess, before, self.candidates.len());
+  }
+
+  /// Every allowed word and every remaining candidate, each sorted best first, like
+  /// [`get_suggestions`]
+  pub fn best_guesses(&self) -> (Scored<'_>, Scored<'_>) {
+    let (suggestions, guesses) = self.ranked_guesses();
+    (suggestions.collect(), guesses.collect())
+  }
+
+  /// Every allowed word and every remaining candidate, each ranked best first, see
+  /// [`rank_suggestions`]
+  pub fn ranked_guesses(&self) -> (RankedGuesses<'_>, RankedGuesses<'_>) {
+    let allowed: Vec<&str> = self.allowed.iter().collect();
+    let candidates = self.remaining_candidates();
+    let observer = self.observer();
+    let rank = || {
+      rank_suggestions_with_progress(&allowed, &candidates, self.strategy.as_ref(), |progress| {
+        observer.on_scoring_progress(progress)
+      })
+    };
+    match &self.pool {
+      Some(pool) => pool.install(rank),
+      None => rank(),
+    }
+  }
+
+  /// The word to play next, see [`best_attempt`]. `None` once no candidate is left.
+  pub fn best_attempt(&self) -> Option<&str> {
+    let word = match self.candidates.as_slice() {
+      [] => None,
+      &[index] => Some(&self.answers[index]),
+      _ => {
+        let (suggestions, guesses) = self.ranked_guesses();
+        let chance = 1.0 / self.candidates.len() as f64;
+        let lambda = if self.strategy.counts_wins() {
+          0.0
+        } else {
+          DEFAULT_LAMBDA
+        };
+        best_attempt_with(&suggestions.top(1), &guesses.top(1), chance, lambda)
+      }
+    };
+    if let Some(word) = word {
+      self.observer().on_suggestion_ready(word);
+    }
+    word
+  }
+}
+
+/// Error returned by [`SolverBuilder::build`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BuildError {
+  /// No answer list was given, or it is empty
+  NoAnswers,
+  /// A word of a list doesn't have [`WORD_LENGTH`] letters
+  WrongLength { word: String },
+  /// More than one strategy was given, by [`Strategy::name`]
+  ConflictingStrategies(Vec<String>),
+  /// Scoring needs at least one thread, or the threads couldn't be started
+  Threads(String),
+}
+
+impl fmt::Display for BuildError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      BuildError::NoAnswers => write!(f, "no answer to look for"),
+      BuildError::WrongLength { word } => write!(
+        f,
+        "{:?} doesn't have {} letters, all words must",
+        word, WORD_LENGTH
+      ),
+      BuildError::ConflictingStrategies(names) => {
+        write!(f, "pick one strategy, got {}", names.join(", "))
+      }
+      BuildError::Threads(message) => write!(f, "can't start the scoring threads: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Configuration of a [`SolverSession`], checked as a whole by [`SolverBuilder::build`].
+///
+/// ```no_run
+/// use wordle_solver::arena::WordArena;
+/// use wordle_solver::solver::{SolverSession, WorstCase};
+///
+/// let answers: WordArena = ["plate", "elate", "slate"].iter().collect();
+/// let allowed: WordArena = ["crane", "plate", "elate", "slate"].iter().collect();
+/// let session = SolverSession::builder()
+///   .dictionary(allowed)
+///   .answers(answers)
+///   .strategy(WorstCase)
+///   .threads(2)
+///   .build()?;
+/// # Ok::<(), wordle_solver::solver::BuildError>(())
+/// ```
+#[derive(Default)]
+pub struct SolverBuilder {
+  dictionary: Option<WordArena>,
+  answers: Option<WordArena>,
+  strategies: Vec<Box<dyn Strategy>>,
+  threads: Option<usize>,
+  observer: Option<Box<dyn SolverObserver>>,
+}
+
+impl SolverBuilder {
+  /// Words that can be played, the answers when not given
+  pub fn dictionary(mut self, words: WordArena) -> SolverBuilder {
+    self.dictionary = Some(words);
+    self
+  }
+
+  /// Words that can be the answer
+  pub fn answers(mut self, words: WordArena) -> SolverBuilder {
+    self.answers = Some(words);
+    self
+  }
+
+  /// How to score guesses, [`Average`] when not given. Giving several is an error.
+  pub fn strategy<S: Strategy + 'static>(mut self, strategy: S) -> SolverBuilder {
+    self.strategies.push(Box::new(strategy));
+    self
+  }
+
+  /// Number of threads scoring runs on, all cores when not given
+  pub fn threads(mut self, threads: usize) -> SolverBuilder {
+    self.threads = Some(threads);
+    self
+  }
+
+  /// See [`SolverSession::set_observer`]
+  pub fn observer<O: SolverObserver + 'static>(mut self, observer: O) -> SolverBuilder {
+    self.observer = Some(Box::new(observer));
+    self
+  }
+
+  /// The strategy given, checking there is only one. For callers scoring their own word lists
+  /// with the free functions rather than a session.
+  pub fn build_strategy(&mut self) -> Result<Box<dyn Strategy>, BuildError> {
+    match self.strategies.len() {
+      0 => Ok(Box::new(Average)),
+      1 => Ok(self.strategies.remove(0)),
+      _ => Err(BuildError::ConflictingStrategies(
+        self
+          .strategies
+          .iter()
+          .map(|strategy| strategy.name())
+          .collect(),
+      )),
+    }
+  }
+
+  pub fn build(mut self) -> Result<SolverSession, BuildError> {
+    let strategy = self.build_strategy()?;
+    let answers = match self.answers {
+      Some(answers) if !answers.is_empty() => answers,
+      _ => return Err(BuildError::NoAnswers),
+    };
+    let allowed = self.dictionary.unwrap_or_else(|| answers.clone());
+    if let Some(word) = answers
+      .iter()
+      .chain(allowed.iter())
+      .find(|word| word.chars().count() != WORD_LENGTH)
+    {
+      return Err(BuildError::WrongLength {
+        word: word.to_string(),
+      });
+    }
+
+    let pool = match self.threads {
+      None => None,
+      Some(0) => return Err(BuildError::Threads("0 threads".to_string())),
+      Some(threads) => Some(
+        rayon::ThreadPoolBuilder::new()
+          .num_threads(threads)
+          .build()
+          .map_err(|err| BuildError::Threads(err.to_string()))?,
+      ),
+    };
+
+    let mut session = SolverSession::new(allowed, answers, Average);
+    session.strategy = strategy;
+    session.observer = self.observer;
+    session.pool = pool;
+    Ok(session)
+  }
+}
@@ -0,0 +1,146 @@
+//! Suggestion tables with the columns picked by `--columns`, instead of the default list of
+//! `(word, score)` pairs.
+
+use std::str::FromStr;
+
+use crate::ranking::{expected_remaining, worst_remaining};
+use crate::{DictString, DisplayOptions, Scored, SHOWN_GUESSES};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+  /// The guess
+  Word,
+  /// The score of the guess under the chosen strategy
+  Bits,
+  /// Number of candidates left in the worst case
+  Worst,
+  /// Expected number of candidates left
+  Remaining,
+  /// Whether the guess can be the answer
+  Candidate,
+  /// How common the word is, see `--frequencies`
+  Frequency,
+  /// Probability that the guess is the answer, see `--priors`
+  Probability,
+}
+
+const COLUMNS: [(&str, Column); 7] = [
+  ("word", Column::Word),
+  ("bits", Column::Bits),
+  ("worst", Column::Worst),
+  ("remaining", Column::Remaining),
+  ("candidate", Column::Candidate),
+  ("frequency", Column::Frequency),
+  ("probability", Column::Probability),
+];
+
+impl Column {
+  fn name(self) -> &'static str {
+    COLUMNS
+      .iter()
+      .find(|(_, column)| *column == self)
+      .unwrap()
+      .0
+  }
+
+  fn value(
+    self,
+    word: &str,
+    score: f64,
+    candidates: &[&DictString],
+    display: &DisplayOptions,
+  ) -> String {
+    match self {
+      Column::Word => word.to_string(),
+      Column::Bits => format!("{:.4}", score),
+      Column::Worst => worst_remaining(word, candidates).to_string(),
+      Column::Remaining => format!("{:.2}", expected_remaining(word, candidates)),
+      Column::Candidate => {
+        let is_candidate = candidates.contains(&word);
+        (if is_candidate { "yes" } else { "no" }).to_string()
+      }
+      Column::Frequency => display.frequencies.get(word).to_string(),
+      Column::Probability => {
+        format!("{:.4}", display.answer_chance(word, candidates))
+      }
+    }
+  }
+}
+
+/// The columns of a table, parsed from a comma separated list of column names
+#[derive(Debug, Clone, PartialEq)]
+pub struct Columns(pub Vec<Column>);
+
+impl FromStr for Columns {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.split(',')
+      .map(|name| {
+        let name = name.trim();
+        COLUMNS
+          .iter()
+          .find(|(known, _)| *known == name)
+          .map(|&(_, column)| column)
+          .ok_or_else(|| {
+            let known: Vec<&str> = COLUMNS.iter().map(|(known, _)| *known).collect();
+            format!("unknown column {:?}, expected {}", name, known.join(", "))
+          })
+      })
+      .collect::<Result<Vec<_>, _>>()
+      .map(Columns)
+  }
+}
+
+/// Prints the best scored words as a table with the given columns
+pub fn print_table(
+  title: &str,
+  scored: &Scored,
+  candidates: &[&DictString],
+  columns: &Columns,
+  display: &DisplayOptions,
+) {
+  println!("{}: {}", title, scored.len());
+
+  let rows: Vec<Vec<String>> = scored
+    .iter()
+    .take(SHOWN_GUESSES)
+    .map(|&(word, score)| {
+      columns
+        .0
+        .iter()
+        .map(|column| column.value(word, score, candidates, display))
+        .collect()
+    })
+    .collect();
+
+  let widths: Vec<usize> = columns
+    .0
+    .iter()
+    .enumerate()
+    .map(|(index, column)| {
+      rows
+        .iter()
+        .map(|row| row[index].chars().count())
+        .chain(std::iter::once(column.name().chars().count()))
+        .max()
+        .unwrap()
+    })
+    .collect();
+
+  let header: Vec<String> = columns
+    .0
+    .iter()
+    .zip(widths.iter())
+    .map(|(column, &width)| format!("{:>width$}", column.name(), width = width))
+    .collect();
+  println!("  {}", header.join("  "));
+  for row in rows {
+    let cells: Vec<String> = row
+      .iter()
+      .zip(widths.iter())
+      .map(|(cell, &width)| format!("{:>width$}", cell, width = width))
+      .collect();
+    println!("  {}", cells.join("  "));
+  }
+}
@@ -0,0 +1,41 @@
+//! Statistics of the games played one after the other with `--loop`
+
+use std::collections::BTreeMap;
+
+/// Results of the games played so far
+#[derive(Debug, Default)]
+pub struct Tally {
+  games: usize,
+  /// Number of games solved in each number of tries
+  solved: BTreeMap<usize, usize>,
+}
+
+impl Tally {
+  /// Counts a game, solved in `tries` or not solved
+  pub fn record(&mut self, tries: Option<usize>) {
+    self.games += 1;
+    if let Some(tries) = tries {
+      *self.solved.entry(tries).or_insert(0) += 1;
+    }
+  }
+
+  pub fn print(&self) {
+    let solved: usize = self.solved.values().sum();
+    let total: usize = self.solved.iter().map(|(tries, games)| tries * games).sum();
+    let average_tries = if solved == 0 {
+      "-".to_string()
+    } else {
+      format!("{:.2}", total as f64 / solved as f64)
+    };
+    println!("Games  Solved  Avg tries");
+    println!(
+      "{:>5}  {:>5.1}%  {:>9}",
+      self.games,
+      100.0 * solved as f64 / self.games.max(1) as f64,
+      average_tries
+    );
+    for (tries, games) in &self.solved {
+      println!("{:>5} solved in {}", games, tries);
+    }
+  }
+}
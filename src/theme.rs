@@ -0,0 +1,175 @@
+//! Tile colors and emoji squares, chosen with `--theme`.
+//!
+//! Besides the presets, a theme can be read from a file of `<mark> <color> <emoji>` lines, where
+//! the mark is `right`, `wrong` or `absent` and the color is written `#rrggbb`. Marks the file
+//! leaves out keep their classic look.
+
+use std::fs;
+use std::str::FromStr;
+use wordle_solver::mark::Mark;
+
+/// How one kind of mark is shown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+  /// Background of the tile, as red, green and blue
+  pub color: (u8, u8, u8),
+  /// Square used in shared grids
+  pub emoji: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+  pub right: Tile,
+  pub wrong: Tile,
+  pub absent: Tile,
+}
+
+impl Theme {
+  /// Green and yellow, like the original game
+  pub fn classic() -> Theme {
+    Theme {
+      right: Tile {
+        color: (0x6a, 0xaa, 0x64),
+        emoji: '🟩',
+      },
+      wrong: Tile {
+        color: (0xc9, 0xb4, 0x58),
+        emoji: '🟨',
+      },
+      absent: Tile {
+        color: (0x78, 0x7c, 0x7e),
+        emoji: '⬛',
+      },
+    }
+  }
+
+  /// Orange and blue, like the high contrast mode of the original game
+  pub fn high_contrast() -> Theme {
+    Theme {
+      right: Tile {
+        color: (0xf5, 0x79, 0x3a),
+        emoji: '🟧',
+      },
+      wrong: Tile {
+        color: (0x85, 0xc0, 0xf9),
+        emoji: '🟦',
+      },
+      ..Theme::classic()
+    }
+  }
+
+  /// Orange and blue from the Okabe-Ito palette, told apart with any kind of color blindness,
+  /// over a darker gray
+  pub fn colorblind() -> Theme {
+    Theme {
+      right: Tile {
+        color: (0xe6, 0x9f, 0x00),
+        emoji: '🟧',
+      },
+      wrong: Tile {
+        color: (0x56, 0xb4, 0xe9),
+        emoji: '🟦',
+      },
+      absent: Tile {
+        color: (0x3a, 0x3a, 0x3c),
+        emoji: '⬛',
+      },
+    }
+  }
+
+  /// The preset of this name, or else the theme in this file
+  pub fn load(name: &str) -> Result<Theme, String> {
+    match name {
+      "classic" => Ok(Theme::classic()),
+      "high-contrast" => Ok(Theme::high_contrast()),
+      "colorblind" => Ok(Theme::colorblind()),
+      file => {
+        let text = fs::read_to_string(file).map_err(|err| {
+          format!(
+            "unknown theme {:?}, expected classic, high-contrast, colorblind or a theme file ({})",
+            file, err
+          )
+        })?;
+        text.parse().map_err(|err| format!("{}: {}", file, err))
+      }
+    }
+  }
+
+  pub fn tile(&self, mark: Mark) -> Tile {
+    match mark {
+      Mark::RightPosition => self.right,
+      Mark::WrongPosition => self.wrong,
+      Mark::NotPresent => self.absent,
+    }
+  }
+
+  /// The letters of `word` on tiles of their colors, drawn with terminal escape codes
+  pub fn paint(&self, word: &str, marks: &[Mark]) -> String {
+    let mut text: String = word
+      .chars()
+      .zip(marks)
+      .map(|(letter, &mark)| {
+        let (red, green, blue) = self.tile(mark).color;
+        format!(
+          "\x1b[1;97;48;2;{};{};{}m {} ",
+          red,
+          green,
+          blue,
+          letter.to_uppercase()
+        )
+      })
+      .collect();
+    text.push_str("\x1b[0m");
+    text
+  }
+
+  /// The grid of emoji squares shared after a game, one line per guess
+  pub fn share_grid(&self, rows: &[Vec<Mark>]) -> String {
+    rows
+      .iter()
+      .map(|marks| marks.iter().map(|&mark| self.tile(mark).emoji).collect())
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+}
+
+fn parse_color(text: &str) -> Option<(u8, u8, u8)> {
+  let hex = text.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+  let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+  Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+impl FromStr for Theme {
+  type Err = String;
+
+  fn from_str(text: &str) -> Result<Self, Self::Err> {
+    let mut theme = Theme::classic();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+      let tile = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [mark, color, emoji] => {
+          let color = parse_color(color).ok_or_else(|| format!("invalid color {:?}", color))?;
+          let mut chars = emoji.chars();
+          let emoji = match (chars.next(), chars.next()) {
+            (Some(emoji), None) => emoji,
+            _ => return Err(format!("expected a single emoji, got {:?}", emoji)),
+          };
+          (*mark, Tile { color, emoji })
+        }
+        _ => return Err(format!("expected `<mark> #rrggbb <emoji>`, got {:?}", line)),
+      };
+
+      match tile {
+        ("right", tile) => theme.right = tile,
+        ("wrong", tile) => theme.wrong = tile,
+        ("absent", tile) => theme.absent = tile,
+        (mark, _) => {
+          return Err(format!(
+            "unknown mark {:?}, expected right, wrong or absent",
+            mark
+          ))
+        }
+      }
+    }
+    Ok(theme)
+  }
+}
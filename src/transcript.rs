@@ -0,0 +1,84 @@
+//! Reading a game in progress pasted in one block, to pick it up where it is.
+//!
+//! The block is either `word marks` lines, with the marks typed as in interactive mode or copied
+//! as emoji squares, or the guessed words followed by the result grid a game shares, e.g.
+//!
+//! ```text
+//! Wordle 1,234 3/6
+//! crane slate
+//! ⬛⬛🟨⬛🟩
+//! 🟩🟩🟩🟩🟩
+//! ```
+
+use std::convert::TryFrom;
+use wordle_solver::mark::Mark;
+
+use crate::WORD_LENGTH;
+
+/// Marks typed with `-+o` or as emoji squares
+fn parse_marks(text: &str) -> Option<Vec<Mark>> {
+  let marks = text
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .map(|c| Mark::try_from(c).ok().or_else(|| Mark::from_emoji(c)))
+    .collect::<Option<Vec<_>>>()?;
+  Some(marks).filter(|marks| marks.len() == WORD_LENGTH)
+}
+
+fn is_word(text: &str) -> bool {
+  text.chars().count() == WORD_LENGTH && text.chars().all(char::is_alphabetic)
+}
+
+/// Whether `line` is the header of a shared grid, like `Wordle 1,234 3/6`
+pub fn is_header(line: &str) -> bool {
+  line.trim().to_lowercase().starts_with("wordle")
+}
+
+/// Whether the pasted `lines` hold more than blank lines and a header, so that the next blank
+/// line ends the block
+pub fn started(lines: &[String]) -> bool {
+  lines
+    .iter()
+    .any(|line| !line.trim().is_empty() && !is_header(line))
+}
+
+/// Parses the turns of a pasted game, in order
+pub fn parse(block: &str) -> Result<Vec<(String, Vec<Mark>)>, String> {
+  let mut turns = vec![];
+  let mut words = vec![];
+  let mut grid = vec![];
+
+  for line in block.lines().map(str::trim).filter(|line| !line.is_empty()) {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if let Some(marks) = parse_marks(line).filter(|_| !line.chars().any(char::is_alphabetic)) {
+      grid.push(marks);
+    } else if let (2, Some(marks)) = (tokens.len(), parse_marks(tokens[1])) {
+      if !is_word(tokens[0]) {
+        return Err(format!(
+          "Expected a {} letter word in {:?}",
+          WORD_LENGTH, line
+        ));
+      }
+      turns.push((tokens[0].to_lowercase(), marks));
+    } else if tokens.iter().all(|token| is_word(token)) {
+      words.extend(tokens.iter().map(|token| token.to_lowercase()));
+    } else if !is_header(line) {
+      // The header of a shared grid is skipped, anything else is a mistake
+      return Err(format!("Couldn't read {:?}", line));
+    }
+  }
+
+  if words.len() != grid.len() {
+    return Err(format!(
+      "Got {} words for {} rows of the grid",
+      words.len(),
+      grid.len()
+    ));
+  }
+  if !turns.is_empty() && !words.is_empty() {
+    return Err("Paste either word and marks lines or words and a grid, not both".to_string());
+  }
+
+  turns.extend(words.into_iter().zip(grid));
+  Ok(turns)
+}
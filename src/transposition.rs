@@ -0,0 +1,70 @@
+//! Remembering what the game tree searches found about the candidate sets they reached.
+//!
+//! The same candidates are left by many lines of guesses, `salet` then `courd` leaving the same
+//! words as `courd` then `salet`. [`Transpositions`] keeps for each set searched its exact value,
+//! or when the search was cut at a limit the value it has at least, so that searching it again
+//! with a limit no higher is cut right away. Sets are keyed by a 128-bit hash of their words, much
+//! smaller than the words themselves when millions of sets are searched.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use crate::solver::DictWord;
+
+/// What is known of the value of a candidate set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+  Exact(f64),
+  /// The search was cut, the value is this much or more
+  AtLeast(f64),
+}
+
+/// The values of the candidate sets searched so far, safe to share between threads
+#[derive(Default)]
+pub struct Transpositions {
+  entries: Mutex<HashMap<u128, Bound>>,
+}
+
+impl Transpositions {
+  pub fn new() -> Transpositions {
+    Transpositions::default()
+  }
+
+  /// The key of `candidates` in the position `context`, like the turn for searches where it
+  /// matters
+  pub fn key(candidates: &[DictWord], context: usize) -> u128 {
+    let half = |seed: u8| {
+      let mut hasher = DefaultHasher::new();
+      (seed, context, candidates).hash(&mut hasher);
+      hasher.finish() as u128
+    };
+    half(0) << 64 | half(1)
+  }
+
+  pub fn get(&self, key: u128) -> Option<Bound> {
+    self.entries.lock().unwrap().get(&key).copied()
+  }
+
+  /// Remembers the exact `value` of the set of `key`
+  pub fn exact(&self, key: u128, value: f64) -> f64 {
+    self
+      .entries
+      .lock()
+      .unwrap()
+      .insert(key, Bound::Exact(value));
+    value
+  }
+
+  /// Remembers that the set of `key` is worth `value` or more, unless more is known already
+  pub fn at_least(&self, key: u128, value: f64) -> f64 {
+    let mut entries = self.entries.lock().unwrap();
+    let known = entries.entry(key).or_insert(Bound::AtLeast(value));
+    match *known {
+      Bound::AtLeast(lower) if lower < value => *known = Bound::AtLeast(value),
+      _ => {}
+    }
+    value
+  }
+}
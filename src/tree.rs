@@ -0,0 +1,249 @@
+//! Decision trees: the guess to play after any feedback, worked out once and reloaded.
+//!
+//! A tree file has one line per answer, with the guesses played to find it and the marks each
+//! got as `gyb` letters:
+//!
+//! ```text
+//! salet bbbyb courd bybbb ninth ggggg
+//! salet bbbyb courd bybbb tenth ggggg
+//! ```
+//!
+//! Lines share the start of their games, so together they give the guess to play for every
+//! feedback the answers can get. [`TreeStrategy`] plays a tree, scoring the guesses of the states
+//! it has without searching.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::engine;
+use crate::pattern::Pattern;
+use crate::scoring::ScoringBuffers;
+use crate::solver::{DictWord, Strategy};
+
+/// The guess to play, and the trees to go on with after each feedback it got
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionTree {
+  guess: String,
+  /// By pattern index
+  branches: BTreeMap<u8, DecisionTree>,
+}
+
+impl DecisionTree {
+  /// A tree only playing `guess`
+  pub fn new(guess: &str) -> DecisionTree {
+    DecisionTree {
+      guess: guess.to_string(),
+      branches: BTreeMap::new(),
+    }
+  }
+
+  pub fn guess(&self) -> &str {
+    &self.guess
+  }
+
+  /// The tree to go on with after the guess got `pattern`
+  pub fn branch(&self, pattern: Pattern) -> Option<&DecisionTree> {
+    self.branches.get(&pattern.index())
+  }
+
+  /// Goes on with `tree` after the guess got `pattern`
+  pub fn set_branch(&mut self, pattern: Pattern, tree: DecisionTree) {
+    self.branches.insert(pattern.index(), tree);
+  }
+
+  /// The feedbacks the guess has trees for, with them
+  pub fn branches(&self) -> impl Iterator<Item = (Pattern, &DecisionTree)> {
+    self
+      .branches
+      .iter()
+      .filter_map(|(&index, tree)| Some((Pattern::from_index(index)?, tree)))
+  }
+
+  /// The guess to play after the guesses of `history` got their patterns, `None` when the tree
+  /// doesn't go that way
+  pub fn follow(&self, history: &[(&str, Pattern)]) -> Option<&str> {
+    let mut tree = self;
+    for &(guess, pattern) in history {
+      if guess != tree.guess {
+        return None;
+      }
+      tree = tree.branch(pattern)?;
+    }
+    Some(&tree.guess)
+  }
+
+  /// Number of answers found, one per all green branch
+  pub fn answers(&self) -> usize {
+    self
+      .branches()
+      .map(|(pattern, tree)| match pattern.is_solved() {
+        true => 1,
+        false => tree.answers(),
+      })
+      .sum()
+  }
+
+  /// Adds a game, the guesses played with the patterns they got. The first guess must be the
+  /// tree's, the guesses after the same feedbacks those already in the tree, and the game must
+  /// end with the answer found.
+  pub fn add_game(&mut self, game: &[(&str, Pattern)]) -> Result<(), String> {
+    let mut tree = self;
+    for (turn, &(guess, pattern)) in game.iter().enumerate() {
+      if guess != tree.guess {
+        return Err(format!(
+          "plays {} where the tree plays {}",
+          guess, tree.guess
+        ));
+      }
+      let next = match (pattern.is_solved(), game.get(turn + 1)) {
+        (true, None) => guess,
+        (true, Some(_)) => return Err(format!("goes on after {} was solved", guess)),
+        (false, Some(&(next, _))) => next,
+        (false, None) => {
+          return Err(format!(
+            "has no guess after {} {}",
+            guess,
+            pattern.to_letters()
+          ))
+        }
+      };
+      tree = tree
+        .branches
+        .entry(pattern.index())
+        .or_insert_with(|| DecisionTree::new(next));
+    }
+    Ok(())
+  }
+
+  /// Adds the game of a tree file line, guesses and patterns alternating
+  fn add_line(&mut self, tokens: &[&str]) -> Result<(), String> {
+    let game = tokens
+      .chunks(2)
+      .map(|turn| match turn {
+        [guess, pattern] => pattern
+          .parse::<Pattern>()
+          .map(|pattern| (*guess, pattern))
+          .map_err(|err| err.to_string()),
+        _ => Err(format!("{} has no pattern", turn[0])),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    self.add_game(&game)
+  }
+
+  /// Writes the lines of the games through this tree, after `prefix`
+  fn write_lines(&self, f: &mut fmt::Formatter, prefix: &str) -> fmt::Result {
+    if self.branches.is_empty() {
+      return writeln!(f, "{}{}", prefix, self.guess);
+    }
+    for (pattern, tree) in self.branches() {
+      if pattern.is_solved() {
+        writeln!(f, "{}{} {}", prefix, self.guess, pattern.to_letters())?;
+      } else {
+        let prefix = format!("{}{} {} ", prefix, self.guess, pattern.to_letters());
+        tree.write_lines(f, &prefix)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Reads a tree file, see the [module documentation](self)
+impl FromStr for DecisionTree {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut tree: Option<DecisionTree> = None;
+    for (number, line) in s.lines().enumerate() {
+      let tokens: Vec<&str> = line.split_whitespace().collect();
+      if tokens.is_empty() {
+        continue;
+      }
+      let tree = tree.get_or_insert_with(|| DecisionTree::new(tokens[0]));
+      tree
+        .add_line(&tokens)
+        .map_err(|err| format!("line {}: {}", number + 1, err))?;
+    }
+    tree.ok_or_else(|| "the tree has no games".to_string())
+  }
+}
+
+/// Writes a tree file, one line per game
+impl fmt::Display for DecisionTree {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.write_lines(f, "")
+  }
+}
+
+/// Plays a [`DecisionTree`]: in the states the tree has, its guess scores 1 and the others 0,
+/// elsewhere the guesses are scored by another strategy
+pub struct TreeStrategy {
+  /// The tree's guess for each set of candidates it reaches
+  moves: HashMap<Vec<DictWord>, DictWord>,
+  fallback: Box<dyn Strategy>,
+}
+
+impl TreeStrategy {
+  /// Plays `tree` against `answers`, given in the order the candidates will be scored in, and
+  /// `fallback` where it doesn't go
+  pub fn new<S: AsRef<str>>(
+    tree: &DecisionTree,
+    answers: &[S],
+    fallback: Box<dyn Strategy>,
+  ) -> TreeStrategy {
+    let answers: Vec<&str> = answers.iter().map(AsRef::as_ref).collect();
+    let mut moves = HashMap::new();
+    add_moves(tree, &answers, &mut moves);
+    TreeStrategy { moves, fallback }
+  }
+
+  /// Number of sets of candidates the tree has a guess for
+  pub fn states(&self) -> usize {
+    self.moves.len()
+  }
+}
+
+/// Records the guess of `tree` for `candidates`, and of its branches for the candidates they
+/// leave
+fn add_moves(
+  tree: &DecisionTree,
+  candidates: &[&str],
+  moves: &mut HashMap<Vec<DictWord>, DictWord>,
+) {
+  let (Some(guess), false) = (DictWord::new(&tree.guess), candidates.is_empty()) else {
+    return;
+  };
+  let key: Option<Vec<DictWord>> = candidates.iter().map(|word| DictWord::new(word)).collect();
+  if let Some(key) = key {
+    moves.insert(key, guess);
+  }
+  for (pattern, branch) in tree.branches() {
+    if !pattern.is_solved() {
+      let left = engine::reduce(&tree.guess, &pattern.marks(), candidates);
+      add_moves(branch, &left, moves);
+    }
+  }
+}
+
+impl Strategy for TreeStrategy {
+  fn score(&self, guess: &DictWord, candidates: &[DictWord]) -> f64 {
+    self.score_with(guess, candidates, &mut ScoringBuffers::new())
+  }
+
+  fn score_with(
+    &self,
+    guess: &DictWord,
+    candidates: &[DictWord],
+    buffers: &mut ScoringBuffers,
+  ) -> f64 {
+    match self.moves.get(candidates) {
+      Some(best) if best == guess => 1.0,
+      Some(_) => 0.0,
+      None => self.fallback.score_with(guess, candidates, buffers),
+    }
+  }
+
+  fn name(&self) -> String {
+    format!("tree, then {}", self.fallback.name())
+  }
+}
@@ -0,0 +1,147 @@
+//! The `tree` subcommand: the guess the strategy plays after any feedback, for every answer,
+//! written out once to be read, shared or played again with --tree instead of searched anew.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::str::FromStr;
+use wordle_solver::arena::WordArena;
+use wordle_solver::guarantee::Guarantee;
+use wordle_solver::pattern::Pattern;
+use wordle_solver::progress::Progress;
+use wordle_solver::tree::DecisionTree;
+
+use crate::benchmark::{Game, Player};
+use crate::http::json_string;
+use crate::{progress_bar, DictString, Strategy};
+
+/// How the tree is written out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TreeFormat {
+  /// One line per answer with the guesses and their marks, as read by --tree
+  Text,
+  /// Nested objects, one per guess, with its branches by marks
+  Json,
+}
+
+impl FromStr for TreeFormat {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "text" => Ok(TreeFormat::Text),
+      "json" => Ok(TreeFormat::Json),
+      _ => Err(format!("unknown format {:?}, expected text or json", s)),
+    }
+  }
+}
+
+/// Writes `tree` as a JSON object with its guess, the number of answers it finds and, unless it
+/// is the answer, its branches by marks
+fn write_json(out: &mut dyn Write, tree: &DecisionTree, indent: usize) -> io::Result<()> {
+  let pad = "  ".repeat(indent);
+  write!(out, "{{\"guess\": {}", json_string(tree.guess()))?;
+  let branches: Vec<(Pattern, &DecisionTree)> = tree.branches().collect();
+  if branches.is_empty() {
+    return write!(out, "}}");
+  }
+  write!(out, ", \"answers\": {}, \"branches\": {{", tree.answers())?;
+  for (index, (pattern, branch)) in branches.iter().enumerate() {
+    let separator = if index == 0 { "" } else { "," };
+    write!(
+      out,
+      "{}\n{}    {}: ",
+      separator,
+      pad,
+      json_string(&pattern.to_letters())
+    )?;
+    write_json(out, branch, indent + 2)?;
+  }
+  write!(out, "\n{}  }}}}", pad)
+}
+
+fn write_tree(out: &mut dyn Write, tree: &DecisionTree, format: TreeFormat) -> io::Result<()> {
+  match format {
+    TreeFormat::Text => write!(out, "{}", tree)?,
+    TreeFormat::Json => {
+      write_json(out, tree, 0)?;
+      writeln!(out)?;
+    }
+  }
+  out.flush()
+}
+
+/// The guesses of `game` with the marks they got
+fn moves<'g>(game: &'g Game) -> Vec<(&'g str, Pattern)> {
+  game
+    .guesses
+    .iter()
+    .filter_map(|&guess| Some((guess, Pattern::of(guess, game.answer)?)))
+    .collect()
+}
+
+/// Plays every answer with the strategy, opening with `opener` if given, and writes the tree of
+/// the games to `output`, or prints it. Answers the strategy doesn't find are left out.
+pub fn run(
+  dictionary: &WordArena,
+  answers: &WordArena,
+  strategy: &dyn Strategy,
+  guarantee: Option<&Guarantee>,
+  opener: Option<&str>,
+  format: TreeFormat,
+  output: Option<&str>,
+) -> Result<(), String> {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let answers_ref: Vec<&DictString> = answers.iter().collect();
+  let opener = match opener {
+    Some(opener) => Some(
+      dictionary
+        .iter()
+        .find(|word| *word == opener.to_lowercase())
+        .ok_or_else(|| format!("The opener {:?} is not in the dictionary", opener))?,
+    ),
+    None => None,
+  };
+
+  let mut player =
+    Player::new(&dictionary_ref, answers_ref.clone(), strategy, opener).with_guarantee(guarantee);
+  let bar = progress_bar::Bar::new("Building tree");
+  let mut tree: Option<DecisionTree> = None;
+  let mut guesses = 0;
+  let mut missed = vec![];
+  for (index, &answer) in answers_ref.iter().enumerate() {
+    let game = player.play(answer);
+    if game.guesses.last() == Some(&answer) {
+      let moves = moves(&game);
+      let tree = tree.get_or_insert_with(|| DecisionTree::new(game.guesses[0]));
+      tree.add_game(&moves)?;
+      guesses += game.guesses.len();
+    } else {
+      missed.push(answer);
+    }
+    bar.update(&Progress {
+      done: index + 1,
+      total: answers_ref.len(),
+      leaders: vec![],
+    });
+  }
+  let tree = tree.ok_or_else(|| "No answer was found".to_string())?;
+
+  let result = match output {
+    Some(file) => {
+      File::create(file).and_then(|file| write_tree(&mut BufWriter::new(file), &tree, format))
+    }
+    None => write_tree(&mut io::stdout().lock(), &tree, format),
+  };
+  result.map_err(|err| format!("{}: {}", output.unwrap_or("stdout"), err))?;
+
+  let found = tree.answers();
+  eprintln!(
+    "Tree of {} answers, {:.4} guesses on average",
+    found,
+    guesses as f64 / found.max(1) as f64
+  );
+  if !missed.is_empty() {
+    eprintln!("Not found, left out: {}", missed.join(" "));
+  }
+  Ok(())
+}
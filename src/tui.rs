@@ -0,0 +1,300 @@
+//! Full screen terminal interface, shown by the `tui` subcommand.
+
+use std::io;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+  disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::{Frame, Terminal};
+use wordle_solver::arena::WordArena;
+use wordle_solver::mark::Mark;
+
+use crate::theme::Theme;
+use crate::{best_attempt, get_suggestions, reduce_dictionary, DictString, Strategy, WORD_LENGTH};
+
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+const HELP: &str =
+  "type a word | \u{2190}/\u{2192} pick letter | \u{2191}/\u{2193}/space change color | \
+   tab use suggestion | enter submit | esc quit";
+
+struct App<'a> {
+  dictionary: Vec<&'a DictString>,
+  candidates: Vec<&'a DictString>,
+  strategy: &'a dyn Strategy,
+  suggestions: Vec<(&'a DictString, f64)>,
+  guesses: Vec<(&'a DictString, f64)>,
+  rows: Vec<(String, Vec<Mark>)>,
+  input: String,
+  marks: Vec<Mark>,
+  cursor: usize,
+  status: String,
+  theme: Theme,
+}
+
+impl<'a> App<'a> {
+  fn new(
+    dictionary: Vec<&'a DictString>,
+    candidates: Vec<&'a DictString>,
+    strategy: &'a dyn Strategy,
+    theme: Theme,
+  ) -> App<'a> {
+    let (suggestions, guesses) = get_suggestions(&dictionary, &candidates, strategy);
+
+    App {
+      dictionary,
+      candidates,
+      strategy,
+      suggestions,
+      guesses,
+      rows: vec![],
+      input: String::new(),
+      marks: vec![],
+      cursor: 0,
+      status: String::new(),
+      theme,
+    }
+  }
+
+  /// Handles a key press. Returns false when the application should exit.
+  fn on_key(&mut self, code: KeyCode) -> bool {
+    match code {
+      KeyCode::Esc => return false,
+      KeyCode::Char(' ') | KeyCode::Up => self.cycle_mark(1),
+      KeyCode::Down => self.cycle_mark(2),
+      KeyCode::Char(c) if c.is_alphabetic() && self.input.chars().count() < WORD_LENGTH => {
+        self.input.extend(c.to_lowercase());
+        self.marks.push(Mark::NotPresent);
+        self.cursor = self.marks.len() - 1;
+      }
+      KeyCode::Backspace => {
+        self.input.pop();
+        self.marks.pop();
+        self.cursor = self.marks.len().saturating_sub(1);
+      }
+      KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+      KeyCode::Right => self.cursor = (self.cursor + 1).min(self.marks.len().saturating_sub(1)),
+      KeyCode::Tab => {
+        if let Some(word) = best_attempt(&self.suggestions, &self.guesses, self.candidates.len()) {
+          self.input = word.to_string();
+          self.marks = vec![Mark::NotPresent; WORD_LENGTH];
+          self.cursor = 0;
+        }
+      }
+      KeyCode::Enter => self.submit(),
+      _ => {}
+    }
+
+    true
+  }
+
+  fn cycle_mark(&mut self, steps: u32) {
+    if let Some(mark) = self.marks.get_mut(self.cursor) {
+      *mark = Mark::from_digit((mark.to_digit() + steps) % 3).unwrap();
+    }
+  }
+
+  fn submit(&mut self) {
+    if self.input.chars().count() != WORD_LENGTH {
+      self.status = format!("Enter a {} letter word first", WORD_LENGTH);
+      return;
+    }
+
+    let word = std::mem::take(&mut self.input);
+    let marks = std::mem::take(&mut self.marks);
+    self.cursor = 0;
+
+    self.candidates = reduce_dictionary(&word, &marks, &self.candidates);
+    let (suggestions, guesses) = get_suggestions(&self.dictionary, &self.candidates, self.strategy);
+    self.suggestions = suggestions;
+    self.guesses = guesses;
+
+    self.status = if marks.iter().all(|&mark| mark == Mark::RightPosition) {
+      format!("Solved in {}!", self.rows.len() + 1)
+    } else {
+      match self.candidates.len() {
+        0 => "No words match, check the colors".to_string(),
+        1 => format!("The answer is {}", self.candidates[0]),
+        count => format!("{} candidates left", count),
+      }
+    };
+    self.rows.push((word, marks));
+  }
+
+  fn solved(&self) -> bool {
+    self
+      .rows
+      .last()
+      .is_some_and(|(_, marks)| marks.iter().all(|&mark| mark == Mark::RightPosition))
+  }
+
+  fn letter_status(&self, letter: char) -> Option<Mark> {
+    self
+      .rows
+      .iter()
+      .flat_map(|(word, marks)| word.chars().zip(marks.iter()))
+      .filter(|&(c, _)| c == letter)
+      .map(|(_, &mark)| mark)
+      .max_by_key(|mark| mark.to_digit())
+  }
+
+  fn event_loop<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+    loop {
+      terminal.draw(|frame| self.draw(frame))?;
+
+      if let Event::Key(key) = event::read()? {
+        if key.kind == KeyEventKind::Press && !self.on_key(key.code) {
+          return Ok(());
+        }
+      }
+    }
+  }
+
+  fn draw(&self, frame: &mut Frame) {
+    let screen = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Min(0), Constraint::Length(1)])
+      .split(frame.size());
+    let columns = Layout::default()
+      .direction(Direction::Horizontal)
+      .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(screen[0]);
+    let left = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Min(0), Constraint::Length(5)])
+      .split(columns[0]);
+    let right = Layout::default()
+      .direction(Direction::Vertical)
+      .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+      .split(columns[1]);
+
+    self.draw_board(frame, left[0]);
+    self.draw_keyboard(frame, left[1]);
+    self.draw_scores(frame, right[0], "Suggestions", &self.suggestions);
+    self.draw_scores(frame, right[1], "Candidates", &self.guesses);
+
+    let status = if self.status.is_empty() {
+      HELP.to_string()
+    } else {
+      format!("{} | {}", self.status, HELP)
+    };
+    frame.render_widget(Paragraph::new(status), screen[1]);
+  }
+
+  fn draw_board(&self, frame: &mut Frame, area: Rect) {
+    let mut lines: Vec<Line> = self
+      .rows
+      .iter()
+      .map(|(word, marks)| {
+        Line::from(
+          word
+            .chars()
+            .zip(marks.iter())
+            .map(|(letter, &mark)| tile(&self.theme, letter, Some(mark), false))
+            .collect::<Vec<_>>(),
+        )
+      })
+      .collect();
+
+    lines.push(Line::from(
+      self
+        .input
+        .chars()
+        .zip(self.marks.iter())
+        .enumerate()
+        .map(|(index, (letter, &mark))| tile(&self.theme, letter, Some(mark), index == self.cursor))
+        .collect::<Vec<_>>(),
+    ));
+
+    let board = Paragraph::new(lines).block(Block::default().title("Board").borders(Borders::ALL));
+    frame.render_widget(board, area);
+  }
+
+  fn draw_keyboard(&self, frame: &mut Frame, area: Rect) {
+    let lines: Vec<Line> = KEYBOARD_ROWS
+      .iter()
+      .map(|row| {
+        Line::from(
+          row
+            .chars()
+            .map(|letter| tile(&self.theme, letter, self.letter_status(letter), false))
+            .collect::<Vec<_>>(),
+        )
+      })
+      .collect();
+
+    let keyboard =
+      Paragraph::new(lines).block(Block::default().title("Keyboard").borders(Borders::ALL));
+    frame.render_widget(keyboard, area);
+  }
+
+  fn draw_scores(&self, frame: &mut Frame, area: Rect, title: &str, scores: &[(&DictString, f64)]) {
+    let items: Vec<ListItem> = scores
+      .iter()
+      .take(area.height as usize)
+      .map(|(word, score)| ListItem::new(format!("{}  {:.3}", word, score)))
+      .collect();
+
+    let list = List::new(items).block(
+      Block::default()
+        .title(format!("{} ({})", title, scores.len()))
+        .borders(Borders::ALL),
+    );
+    frame.render_widget(list, area);
+  }
+}
+
+fn tile(theme: &Theme, letter: char, mark: Option<Mark>, selected: bool) -> Span<'static> {
+  let background = match mark {
+    Some(mark) => {
+      let (red, green, blue) = theme.tile(mark).color;
+      Color::Rgb(red, green, blue)
+    }
+    None => Color::Reset,
+  };
+
+  let mut style = Style::default().bg(background).add_modifier(Modifier::BOLD);
+  if selected {
+    style = style.add_modifier(Modifier::UNDERLINED);
+  }
+
+  Span::styled(format!(" {} ", letter.to_uppercase()), style)
+}
+
+pub fn run(
+  dictionary: WordArena,
+  reducing_dictionary: WordArena,
+  strategy: &dyn Strategy,
+  theme: Theme,
+) -> io::Result<()> {
+  let mut app = App::new(
+    dictionary.iter().collect(),
+    reducing_dictionary.iter().collect(),
+    strategy,
+    theme,
+  );
+
+  enable_raw_mode()?;
+  execute!(io::stdout(), EnterAlternateScreen)?;
+  let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+  let result = app.event_loop(&mut terminal);
+
+  disable_raw_mode()?;
+  execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+  terminal.show_cursor()?;
+
+  if app.solved() {
+    let rows: Vec<Vec<Mark>> = app.rows.iter().map(|(_, marks)| marks.clone()).collect();
+    println!("{}", theme.share_grid(&rows));
+  }
+
+  result
+}
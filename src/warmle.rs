@@ -0,0 +1,31 @@
+//! The `warmle` subcommand: Warmle, where each letter of a guess is marked as the answer's, as
+//! warm when the answer's letter at that position is a few letters away in the alphabet, or as
+//! cold. Played like [`crate::peaks`], with the [`Warmle`] feedback rule.
+
+use wordle_solver::arena::WordArena;
+use wordle_solver::feedback::Warmle;
+
+use crate::peaks;
+
+/// Plays Warmle interactively with words of `length` letters of `alphabet`, warm up to `distance`
+/// letters away. Each line is the guess followed by its marks.
+pub fn run(
+  dictionary: WordArena,
+  answers: WordArena,
+  length: usize,
+  distance: u32,
+  alphabet: &[char],
+) {
+  let marks_help = format!(
+    "`o` if the letter is right, `+` if the answer's letter is at most {} away in the alphabet \
+     and `-` otherwise, e.g. `raise -+o--`",
+    distance
+  );
+  peaks::play(
+    &Warmle::new(distance, alphabet),
+    length,
+    &marks_help,
+    dictionary,
+    answers,
+  );
+}
@@ -0,0 +1,103 @@
+//! Bindings for running the solver in a browser, built with the `wasm` feature as a `cdylib` and
+//! then packaged by `wasm-bindgen`:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features \
+//!   --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/wordle_solver.wasm
+//! ```
+//!
+//! ```js
+//! import init, { WasmSolver } from "./pkg/wordle_solver.js";
+//!
+//! await init();
+//! const solver = new WasmSolver(allowedText, answersText, "average");
+//! solver.applyFeedback("raise", "bygbb");
+//! console.log(solver.bestAttempt(), solver.suggestions(10));
+//! ```
+//!
+//! Word lists are passed as text, one word per line. Scoring happens in Rust, on the calling
+//! thread, so large dictionaries are best handled from a web worker.
+
+use wasm_bindgen::prelude::*;
+
+use crate::arena::WordArena;
+use crate::pattern::Pattern;
+use crate::solver::{strategy_by_name, SolverSession, WORD_LENGTH};
+
+/// A game being solved, see [`SolverSession`]
+#[wasm_bindgen]
+pub struct WasmSolver {
+  session: SolverSession,
+}
+
+/// The words of a list with the right length, lowercased
+fn words(text: &str) -> WordArena {
+  text
+    .split_whitespace()
+    .map(str::to_lowercase)
+    .filter(|word| word.chars().count() == WORD_LENGTH)
+    .collect()
+}
+
+#[wasm_bindgen]
+impl WasmSolver {
+  /// Starts a game. The strategy is `average`, `worst-case`, `gambling <factor>`, `risk <r>`,
+  /// `largest-group`, `max-splits`, `expected-tiles` or `positional-frequency`.
+  #[wasm_bindgen(constructor)]
+  pub fn new(allowed: &str, answers: &str, strategy: &str) -> Result<WasmSolver, JsError> {
+    let (allowed, answers) = (words(allowed), words(answers));
+    if answers.is_empty() {
+      return Err(JsError::new(&format!(
+        "no answer of {} letters given",
+        WORD_LENGTH
+      )));
+    }
+
+    let strategy = strategy_by_name(strategy).map_err(|message| JsError::new(&message))?;
+    let session = SolverSession::new(allowed, answers, strategy);
+
+    Ok(WasmSolver { session })
+  }
+
+  /// Keeps the candidates matching the marks `guess` got, as `-+o` marks, `gyb` letters or emoji
+  /// squares
+  #[wasm_bindgen(js_name = applyFeedback)]
+  pub fn apply_feedback(&mut self, guess: &str, marks: &str) -> Result<(), JsError> {
+    let pattern: Pattern = marks.parse()?;
+    let guess = guess.to_lowercase();
+    if guess.chars().count() != WORD_LENGTH {
+      return Err(JsError::new(&format!(
+        "guesses have {} letters, got {:?}",
+        WORD_LENGTH, guess
+      )));
+    }
+    self.session.apply_feedback(&guess, &pattern.marks());
+    Ok(())
+  }
+
+  /// The `count` best guesses, best first
+  pub fn suggestions(&self, count: usize) -> Vec<String> {
+    let (suggestions, _) = self.session.ranked_guesses();
+    suggestions
+      .take(count)
+      .map(|(word, _)| word.to_string())
+      .collect()
+  }
+
+  /// The word to play next, `undefined` once no candidate is left
+  #[wasm_bindgen(js_name = bestAttempt)]
+  pub fn best_attempt(&self) -> Option<String> {
+    self.session.best_attempt().map(str::to_string)
+  }
+
+  /// The words that can still be the answer
+  pub fn candidates(&self) -> Vec<String> {
+    self
+      .session
+      .remaining_candidates()
+      .into_iter()
+      .map(str::to_string)
+      .collect()
+  }
+}
@@ -0,0 +1,239 @@
+//! The `web` subcommand: serves a small point-and-click frontend, the WebAssembly build it runs
+//! the solver with and the JSON endpoint it falls back to.
+//!
+//! The WebAssembly build is the `wasm` feature packaged by wasm-bindgen (see `src/wasm.rs`), served
+//! from the `--pkg` directory under `/pkg/` with the word lists under `/words/`. The page then
+//! solves in the browser. Without the build it posts the whole game so far (one `word marks` line
+//! per guess) to `/api/suggest` and renders the suggestions it gets back, the server staying
+//! stateless.
+
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+
+use wordle_solver::arena::WordArena;
+use wordle_solver::mark::Mark;
+
+use crate::cache::LruCache;
+use crate::http::{self, json_number, json_string};
+use crate::progress_bar::Bar;
+use crate::{
+  best_attempt, get_suggestions, get_suggestions_with_progress, reduce_dictionary, DictString,
+  Strategy, WORD_LENGTH,
+};
+
+const INDEX: &str = include_str!("../static/index.html");
+
+const SHOWN_CANDIDATES: usize = 50;
+
+type Scores<'a> = Vec<(&'a DictString, f64)>;
+
+struct Server<'a> {
+  /// Directory of the WebAssembly build
+  pkg: &'a Path,
+  /// The word lists the WebAssembly build is started with, one word per line
+  allowed_text: String,
+  answers_text: String,
+  dictionary: Vec<&'a DictString>,
+  candidates: Vec<&'a DictString>,
+  strategy: &'a dyn Strategy,
+  opening: (Scores<'a>, Scores<'a>),
+  /// Suggestions by set of candidates, as many games go through the same states
+  suggestions: LruCache<Vec<&'a DictString>, (Scores<'a>, Scores<'a>)>,
+}
+
+impl<'a> Server<'a> {
+  fn handle(&self, stream: &TcpStream) -> io::Result<()> {
+    let request = http::read_request(stream)?;
+
+    match (request.method.as_str(), request.path.as_str()) {
+      ("GET", "/") | ("GET", "/index.html") => {
+        http::respond(stream, "200 OK", "text/html; charset=utf-8", INDEX)
+      }
+      ("GET", "/words/allowed.txt") => http::respond(
+        stream,
+        "200 OK",
+        "text/plain; charset=utf-8",
+        &self.allowed_text,
+      ),
+      ("GET", "/words/answers.txt") => http::respond(
+        stream,
+        "200 OK",
+        "text/plain; charset=utf-8",
+        &self.answers_text,
+      ),
+      ("GET", "/api/config") => {
+        let body = format!("{{\"strategy\":{}}}", json_string(&self.strategy.name()));
+        http::respond(stream, "200 OK", "application/json", &body)
+      }
+      ("GET", path) if path.starts_with("/pkg/") => self.package_file(stream, &path[5..]),
+      ("POST", "/api/suggest") => match parse_history(&request.body) {
+        Ok(history) => {
+          let body = self.suggest(&history);
+          http::respond(stream, "200 OK", "application/json", &body)
+        }
+        Err(message) => http::respond(stream, "400 Bad Request", "text/plain", &message),
+      },
+      _ => http::respond(stream, "404 Not Found", "text/plain", "Not found"),
+    }
+  }
+
+  /// Serves a file of the WebAssembly build. Only the files right in the directory are served.
+  fn package_file(&self, stream: &TcpStream, name: &str) -> io::Result<()> {
+    let content_type = match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+      Some("js") => "text/javascript",
+      Some("wasm") => "application/wasm",
+      Some("ts") => "text/plain",
+      _ => "application/octet-stream",
+    };
+    let inside = !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\']);
+    if !inside {
+      return http::respond(stream, "404 Not Found", "text/plain", "Not found");
+    }
+    match fs::read(self.pkg.join(name)) {
+      Ok(body) => http::respond_bytes(stream, "200 OK", content_type, &body),
+      _ => http::respond(stream, "404 Not Found", "text/plain", "Not found"),
+    }
+  }
+
+  fn suggest(&self, history: &[(String, Vec<Mark>)]) -> String {
+    let mut candidates = self.candidates.clone();
+    for (word, marks) in history {
+      candidates = reduce_dictionary(word, marks, &candidates);
+    }
+
+    let computed;
+    let (suggestions, guesses) = if history.is_empty() {
+      (&self.opening.0, &self.opening.1)
+    } else {
+      computed = match self.suggestions.get(&candidates) {
+        Some(cached) => cached,
+        None => {
+          let fresh = Arc::new(get_suggestions(
+            &self.dictionary,
+            &candidates,
+            self.strategy,
+          ));
+          self.suggestions.insert(candidates.clone(), fresh.clone());
+          fresh
+        }
+      };
+      (&computed.0, &computed.1)
+    };
+
+    let attempt = best_attempt(suggestions, guesses, candidates.len())
+      .map(json_string)
+      .unwrap_or_else(|| "null".to_string());
+
+    format!(
+      "{{\"length\":{},\"candidates\":{},\"attempt\":{},\"suggestions\":{},\"guesses\":{}}}",
+      WORD_LENGTH,
+      candidates.len(),
+      attempt,
+      scores_json(suggestions, crate::SHOWN_GUESSES),
+      scores_json(guesses, SHOWN_CANDIDATES)
+    )
+  }
+}
+
+fn scores_json(scores: &[(&DictString, f64)], limit: usize) -> String {
+  let items: Vec<String> = scores
+    .iter()
+    .take(limit)
+    .map(|(word, score)| {
+      format!(
+        "{{\"word\":{},\"score\":{}}}",
+        json_string(word),
+        json_number(*score)
+      )
+    })
+    .collect();
+
+  format!("[{}]", items.join(","))
+}
+
+pub fn parse_history(body: &str) -> Result<Vec<(String, Vec<Mark>)>, String> {
+  body
+    .lines()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| {
+      let (word, marks) = line
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| format!("Expected a word and its marks, got {:?}", line))?;
+
+      let marks = marks
+        .trim()
+        .chars()
+        .map(Mark::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| err.to_string())?;
+
+      if word.chars().count() != WORD_LENGTH || marks.len() != WORD_LENGTH {
+        return Err(format!(
+          "Expected {} letters and marks in {:?}",
+          WORD_LENGTH, line
+        ));
+      }
+
+      Ok((word.to_lowercase(), marks))
+    })
+    .collect()
+}
+
+/// Serves the frontend, handling each connection on its own thread, and the WebAssembly build in
+/// `pkg` if there is one. Suggestions are cached for the last `cache_size` sets of candidates.
+pub fn serve(
+  dictionary: WordArena,
+  reducing_dictionary: WordArena,
+  strategy: &dyn Strategy,
+  port: u16,
+  cache_size: usize,
+  pkg: &Path,
+) -> io::Result<()> {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let candidates_ref: Vec<&DictString> = reducing_dictionary.iter().collect();
+  let bar = Bar::new("Scoring opening guesses");
+  let opening = get_suggestions_with_progress(&dictionary_ref, &candidates_ref, strategy, |p| {
+    bar.update(&p)
+  });
+
+  if !pkg.join("wordle_solver_bg.wasm").is_file() {
+    println!(
+      "No WebAssembly build in {}, the page will ask the server for suggestions",
+      pkg.display()
+    );
+  }
+
+  let server = Server {
+    pkg,
+    allowed_text: dictionary_ref.join("\n"),
+    answers_text: candidates_ref.join("\n"),
+    dictionary: dictionary_ref,
+    candidates: candidates_ref,
+    strategy,
+    opening,
+    suggestions: LruCache::new(cache_size),
+  };
+
+  let listener = TcpListener::bind(("127.0.0.1", port))?;
+  println!("Serving on http://127.0.0.1:{}/", port);
+
+  thread::scope(|scope| {
+    for stream in listener.incoming() {
+      let server = &server;
+      scope.spawn(move || {
+        let result = stream.and_then(|stream| server.handle(&stream));
+        if let Err(err) = result {
+          eprintln!("Request failed: {}", err);
+        }
+      });
+    }
+  });
+
+  Ok(())
+}
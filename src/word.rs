@@ -0,0 +1,85 @@
+//! Fixed length words.
+//!
+//! Scoring compares every guess with every candidate, so the per comparison cost matters. A
+//! [`Word`] stores its letters decoded in an array sized by the word length, which keeps the
+//! pattern computation free of UTF-8 decoding and heap allocation for any length.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::mark::Mark;
+
+/// A word of exactly `N` letters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Word<const N: usize>([char; N]);
+
+impl<const N: usize> Word<N> {
+  /// Decodes a word. Returns `None` unless it has exactly `N` characters.
+  pub fn new(text: &str) -> Option<Word<N>> {
+    let mut letters = ['\0'; N];
+    let mut chars = text.chars();
+
+    for letter in letters.iter_mut() {
+      *letter = chars.next()?;
+    }
+
+    match chars.next() {
+      None => Some(Word(letters)),
+      Some(_) => None,
+    }
+  }
+
+  pub fn letters(&self) -> &[char; N] {
+    &self.0
+  }
+
+  /// The marks Wordle gives this word as a guess when the answer is `answer`.
+  ///
+  /// Same algorithm as [`crate::engine::compute_bucket`].
+  pub fn marks(&self, answer: &Word<N>) -> [Mark; N] {
+    let mut used = [false; N];
+    let mut result = [Mark::NotPresent; N];
+
+    for (index, (guess_letter, answer_letter)) in self.0.iter().zip(answer.0.iter()).enumerate() {
+      if guess_letter == answer_letter {
+        used[index] = true;
+        result[index] = Mark::RightPosition;
+      }
+    }
+
+    for (guess_index, guess_letter) in self.0.iter().enumerate() {
+      if result[guess_index] == Mark::RightPosition {
+        continue;
+      }
+      for (word_index, word_letter) in answer.0.iter().enumerate() {
+        if used[word_index] || word_index == guess_index {
+          continue;
+        }
+        if guess_letter == word_letter {
+          used[word_index] = true;
+          result[guess_index] = Mark::WrongPosition;
+          break;
+        }
+      }
+    }
+
+    result
+  }
+
+  /// The base-3 code of [`Word::marks`], see [`crate::mark::pack`]
+  pub fn pattern_code(&self, answer: &Word<N>) -> usize {
+    crate::mark::pack(&self.marks(answer)) as usize
+  }
+}
+
+impl<const N: usize> fmt::Display for Word<N> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.0.iter().try_for_each(|letter| write!(f, "{}", letter))
+  }
+}
+
+impl<const N: usize> From<Word<N>> for String {
+  fn from(word: Word<N>) -> String {
+    word.0.iter().collect()
+  }
+}
@@ -0,0 +1,213 @@
+//! The `xordle` subcommand: two answers sharing no letter, found on a single board. Each mark is
+//! the better of the marks the letter gets against either answer, so the candidates are pairs of
+//! answers and guesses are scored by what they tell about the pair.
+
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::io::{self, BufRead};
+use wordle_solver::arena::WordArena;
+use wordle_solver::engine;
+use wordle_solver::pattern::{Pattern, PATTERN_COUNT};
+
+use crate::{parse_feedback, to_word, DictString, DictWord, SHOWN_GUESSES, WORD_LENGTH};
+
+/// The pattern shown for the patterns `first` and `second` a guess gets against either answer,
+/// for every pair of codes: at each position the better of both marks
+fn combined_codes() -> Vec<u8> {
+  let mut codes = vec![0; PATTERN_COUNT * PATTERN_COUNT];
+  for first in 0..PATTERN_COUNT {
+    for second in 0..PATTERN_COUNT {
+      let (mut a, mut b, mut place, mut code) = (first, second, 1, 0);
+      for _ in 0..WORD_LENGTH {
+        code += (a % 3).max(b % 3) * place;
+        a /= 3;
+        b /= 3;
+        place *= 3;
+      }
+      codes[first * PATTERN_COUNT + second] = code as u8;
+    }
+  }
+  codes
+}
+
+/// The pairs of answers without a letter in common, as indices into `words`
+fn disjoint_pairs(words: &[DictWord]) -> Vec<(usize, usize)> {
+  (0..words.len())
+    .into_par_iter()
+    .flat_map_iter(|first| {
+      let letters = words[first].letters();
+      (first + 1..words.len())
+        .filter(move |&second| {
+          words[second]
+            .letters()
+            .iter()
+            .all(|letter| !letters.contains(letter))
+        })
+        .map(move |second| (first, second))
+    })
+    .collect()
+}
+
+/// Information `guess` gives about which of `pairs` is the answer
+fn pair_information(
+  guess: &DictWord,
+  words: &[DictWord],
+  pairs: &[(usize, usize)],
+  combined: &[u8],
+) -> f64 {
+  let codes: Vec<usize> = words.iter().map(|word| guess.pattern_code(word)).collect();
+  let mut counts = [0u32; PATTERN_COUNT];
+  for &(first, second) in pairs {
+    counts[combined[codes[first] * PATTERN_COUNT + codes[second]] as usize] += 1;
+  }
+  engine::entropy(&counts)
+}
+
+/// Keeps the pairs of `pairs` on which `guess` gets the pattern of `code`
+fn keep_fitting(
+  pairs: &mut Vec<(usize, usize)>,
+  guess: &DictWord,
+  code: u8,
+  words: &[DictWord],
+  combined: &[u8],
+) {
+  pairs.retain(|&(first, second)| {
+    let shown = combined
+      [guess.pattern_code(&words[first]) * PATTERN_COUNT + guess.pattern_code(&words[second])];
+    shown == code
+  });
+}
+
+/// Reads a line of input into the guess and the code of its marks, or what is wrong with it
+fn read_turn(line: &str) -> Result<(DictWord, u8), String> {
+  let (guess, marks) = parse_feedback(line).map_err(|err| err.to_string())?;
+  let pattern = Pattern::try_from(marks.as_slice()).map_err(|err| err.to_string())?;
+  Ok((to_word(&guess.to_lowercase()), pattern.index()))
+}
+
+/// Plays Xordle interactively. Each line is the guess followed by its marks.
+pub fn run(dictionary: WordArena, answers: WordArena) {
+  let dictionary_ref: Vec<&DictString> = dictionary.iter().collect();
+  let answers: Vec<&DictString> = answers.iter().collect();
+  let words: Vec<DictWord> = answers.iter().map(|word| to_word(word)).collect();
+  let combined = combined_codes();
+  let mut pairs = disjoint_pairs(&words);
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+  loop {
+    match pairs.as_slice() {
+      [] => {
+        println!("No pair of answers fits, check the marks");
+        return;
+      }
+      &[(first, second)] => {
+        println!(
+          "The answers are {:?} and {:?}",
+          answers[first], answers[second]
+        );
+        return;
+      }
+      _ => println!("{} pairs of answers left", pairs.len()),
+    }
+
+    // Words of the pairs left come first among guesses telling as much, they can find an answer
+    let in_pairs = |word: &DictString| {
+      pairs
+        .iter()
+        .any(|&(first, second)| answers[first] == word || answers[second] == word)
+    };
+    let mut scored: Vec<(&DictString, f64)> = dictionary_ref
+      .par_iter()
+      .map(|&guess| {
+        let information = pair_information(&to_word(guess), &words, &pairs, &combined);
+        (guess, information)
+      })
+      .collect();
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    let best = scored[0].1;
+    let best = scored
+      .iter()
+      .take_while(|(_, information)| *information >= best)
+      .find(|(word, _)| in_pairs(word))
+      .unwrap_or(&scored[0])
+      .0;
+    println!(
+      "Suggestions: {:?}",
+      scored.iter().take(SHOWN_GUESSES).collect::<Vec<_>>()
+    );
+    println!("Suggest you try {:?}", best);
+
+    let (guess, code) = loop {
+      let line = match lines.next() {
+        Some(Ok(line)) => line,
+        _ => return,
+      };
+      match read_turn(&line) {
+        Ok(turn) => break turn,
+        Err(err) => println!("{}", err),
+      }
+    };
+    keep_fitting(&mut pairs, &guess, code, &words, &combined);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WORDS: [&str; 10] = [
+    "cigar", "blond", "fjord", "tempo", "humpy", "shake", "mount", "brick", "plaid", "nymph",
+  ];
+
+  /// The pairs of [`WORDS`] left after the board `line`, a guess and its marks
+  fn fitting(line: &str) -> Result<Vec<(&'static str, &'static str)>, String> {
+    let words: Vec<DictWord> = WORDS.iter().map(|word| to_word(word)).collect();
+    let mut pairs = disjoint_pairs(&words);
+    let (guess, code) = read_turn(line)?;
+    keep_fitting(&mut pairs, &guess, code, &words, &combined_codes());
+    Ok(
+      pairs
+        .into_iter()
+        .map(|(first, second)| (WORDS[first], WORDS[second]))
+        .collect(),
+    )
+  }
+
+  #[test]
+  fn pairs_share_no_letter() {
+    let words: Vec<DictWord> = WORDS.iter().map(|word| to_word(word)).collect();
+    assert_eq!(disjoint_pairs(&words).len(), 16);
+    assert!(
+      !disjoint_pairs(&words).contains(&(0, 7)),
+      "cigar and brick share c, i and r"
+    );
+  }
+
+  #[test]
+  fn marks_are_the_better_of_both_answers() {
+    // t and e in neither, o and l yellow for blond, i yellow for cigar
+    assert_eq!(fitting("toile -+++-"), Ok(vec![("cigar", "blond")]));
+  }
+
+  #[test]
+  fn keeps_every_pair_fitting_the_board() {
+    // Only i is in the pair, out of place: cigar with either word lacking t, o, l and e
+    assert_eq!(
+      fitting("toile --+--"),
+      Ok(vec![("cigar", "humpy"), ("cigar", "nymph")])
+    );
+    // Only i, in place: brick
+    assert_eq!(
+      fitting("toile --o--"),
+      Ok(vec![("humpy", "brick"), ("brick", "nymph")])
+    );
+  }
+
+  #[test]
+  fn unreadable_marks_are_reported() {
+    assert!(fitting("toile --x--").is_err());
+    assert!(fitting("toile --+-").is_err());
+  }
+}